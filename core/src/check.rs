@@ -0,0 +1,263 @@
+//! Checking whether the links still work.
+//!
+//! The backend checks every link in the background and stores the result in [`Link::check`]. The [`CheckQueue`] decides which link is checked next, so that only a few requests are sent to the same website at once.
+
+use crate::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// How many links are checked at the same time
+pub const MAX_CONCURRENT_CHECKS: usize = 8;
+
+/// How long to wait between two requests to the same domain
+pub const DOMAIN_DELAY: Duration = Duration::from_secs(1);
+
+/// The statuses which don't tell whether the page exists: the website refuses the checker (`403`, `405`) or asks it to slow down (`429`).
+pub const INCONCLUSIVE_STATUSES: [u32; 3] = [403, 405, 429];
+
+/// The result of checking a link, see [`Link::check`]
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::check::LinkCheck;
+///
+/// let check = LinkCheck::new("https://example.com/old").status(404);
+/// assert!(check.is_broken());
+///
+/// let check = LinkCheck::new("https://example.com/old")
+///     .status(200)
+///     .redirect("https://example.com/new");
+/// assert!(!check.is_broken());
+///
+/// // the website doesn't exist anymore
+/// let check = LinkCheck::new("https://example.com").unreachable("Couldn't resolve host name");
+/// assert!(check.is_broken());
+///
+/// // the user may be offline, so the page may still work
+/// let check = LinkCheck::new("https://example.com").error("Timeout was reached");
+/// assert!(!check.is_broken());
+/// assert!(check.is_inconclusive());
+///
+/// // the website doesn't want to be checked, but the page may still work
+/// let check = LinkCheck::new("https://example.com").status(429);
+/// assert!(!check.is_broken());
+/// assert!(check.is_inconclusive());
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Hash, Eq)]
+pub struct LinkCheck {
+    /// The url which was checked. If the url of the link is changed after the check, then the check is not saved.
+    pub url: String,
+    /// When the link was checked (in UTC)
+    pub checked_at: DateTime<Utc>,
+    /// The HTTP status after following the redirects. It is `None` if the website can't be reached.
+    pub status: Option<u32>,
+    /// The url after following the redirects, if it is different from the checked url
+    pub redirect: Option<String>,
+    /// Why the website can't be reached
+    pub error: Option<String>,
+    /// Whether the `error` shows that the website is gone: its host is not found or it refuses the connection. Other errors (e.g. a timeout) may come from the user's network.
+    #[serde(default)]
+    pub unreachable: bool,
+}
+
+impl LinkCheck {
+    /// Create a check of the `url` at the current time, without any status.
+    pub fn new<T: ToString>(url: T) -> Self {
+        Self {
+            url: url.to_string(),
+            checked_at: Utc::now(),
+            status: None,
+            redirect: None,
+            error: None,
+            unreachable: false,
+        }
+    }
+
+    /// Set the HTTP status of the check
+    pub fn status(mut self, status: u32) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Set the url after the redirects. It is ignored if it is the checked url.
+    pub fn redirect<T: ToString>(mut self, redirect: T) -> Self {
+        let redirect = redirect.to_string();
+        self.redirect = Some(redirect).filter(|redirect| *redirect != self.url);
+        self
+    }
+
+    /// Set why the website can't be reached. The check is inconclusive, use [`unreachable`](#method.unreachable) if the website is gone.
+    pub fn error<T: ToString>(mut self, error: T) -> Self {
+        self.error = Some(error.to_string());
+        self
+    }
+
+    /// Set why the website can't be reached, when the error shows that the website is gone. The check is broken.
+    pub fn unreachable<T: ToString>(mut self, error: T) -> Self {
+        self.error = Some(error.to_string());
+        self.unreachable = true;
+        self
+    }
+
+    /// Whether the link is dead: the website is [`unreachable`](#structfield.unreachable) or it returns a 4xx or 5xx status. The [`INCONCLUSIVE_STATUSES`] are not broken.
+    pub fn is_broken(&self) -> bool {
+        match self.status {
+            Some(status) => status >= 400 && !self.is_inconclusive(),
+            None => self.unreachable,
+        }
+    }
+
+    /// Whether the check doesn't tell if the link works: the status is one of the [`INCONCLUSIVE_STATUSES`], or the website can't be reached but it isn't [`unreachable`](#structfield.unreachable).
+    pub fn is_inconclusive(&self) -> bool {
+        match self.status {
+            Some(status) => INCONCLUSIVE_STATUSES.contains(&status),
+            None => !self.unreachable,
+        }
+    }
+}
+
+/// Progress of checking all the links. The backend sends it to the frontend whenever it saves the checks.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct CheckProgress {
+    /// How many links are checked
+    pub checked: usize,
+    /// How many links are checked in total
+    pub total: usize,
+    /// The links which are checked since the last progress, with their new [`Link::check`]
+    pub links: Vec<Link>,
+}
+
+/// What to do next, see [`CheckQueue::next`]
+// it is used right after it is returned, so boxing the link is not worth it
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Next {
+    /// Check this link, then call [`CheckQueue::finish`]
+    Check(Link),
+    /// Every remaining link is from a domain which is being checked or was checked just now. Ask again after this time.
+    Wait(Duration),
+    /// No link is left
+    Done,
+}
+
+/// The links which are waiting to be checked
+///
+/// Only one link of a domain is checked at a time, and the next link of that domain waits for the `delay` after it. So the links of many domains are checked together, but a single website doesn't get too many requests.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::check::{CheckQueue, Next};
+/// use dsaver_core::prelude::*;
+/// use std::time::{Duration, Instant};
+///
+/// let links = vec![
+///     Link::new("https://github.com/rust-lang/rust"),
+///     Link::new("https://github.com/KRShanto/DSaver"),
+///     Link::new("https://docs.rs/chrono"),
+/// ];
+/// let mut queue = CheckQueue::new(links.clone(), Duration::from_secs(1));
+/// let now = Instant::now();
+///
+/// // the second github link waits for the first one
+/// assert_eq!(queue.next(now), Next::Check(links[0].clone()));
+/// assert_eq!(queue.next(now), Next::Check(links[2].clone()));
+/// assert!(matches!(queue.next(now), Next::Wait(_)));
+///
+/// queue.finish(&links[0], now);
+/// assert!(matches!(queue.next(now), Next::Wait(_)));
+/// assert_eq!(queue.next(now + Duration::from_secs(1)), Next::Check(links[1].clone()));
+/// assert_eq!(queue.next(now), Next::Done);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CheckQueue {
+    /// The links of every domain, in the order they were added
+    pending: Vec<(String, VecDeque<Link>)>,
+    /// The domains which are being checked
+    busy: HashSet<String>,
+    /// When the next link of a domain can be checked
+    ready_at: HashMap<String, Instant>,
+    /// How long to wait between two links of the same domain
+    delay: Duration,
+}
+
+impl CheckQueue {
+    /// Create a queue of the `links`. The links of the same domain wait for the `delay` between them.
+    pub fn new(links: Vec<Link>, delay: Duration) -> Self {
+        let mut pending: Vec<(String, VecDeque<Link>)> = Vec::new();
+        let mut positions = HashMap::new();
+
+        for link in links {
+            let domain = link.domain().unwrap_or_default();
+            let position = *positions.entry(domain.clone()).or_insert_with(|| {
+                pending.push((domain, VecDeque::new()));
+                pending.len() - 1
+            });
+
+            pending[position].1.push_back(link);
+        }
+
+        Self {
+            pending,
+            busy: HashSet::new(),
+            ready_at: HashMap::new(),
+            delay,
+        }
+    }
+
+    /// How many links are waiting
+    pub fn len(&self) -> usize {
+        self.pending.iter().map(|(_, links)| links.len()).sum()
+    }
+
+    /// Whether no link is waiting
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Take the next link which can be checked at the time `now`.
+    pub fn next(&mut self, now: Instant) -> Next {
+        if self.pending.is_empty() {
+            return Next::Done;
+        }
+
+        let mut wait = self.delay;
+
+        for i in 0..self.pending.len() {
+            let domain = &self.pending[i].0;
+            if self.busy.contains(domain) {
+                continue;
+            }
+
+            match self.ready_at.get(domain) {
+                Some(ready_at) if *ready_at > now => wait = wait.min(*ready_at - now),
+                _ => {
+                    let domain = domain.clone();
+                    let links = &mut self.pending[i].1;
+                    // a domain is only kept while it has links
+                    let link = links.pop_front().unwrap();
+
+                    if links.is_empty() {
+                        self.pending.remove(i);
+                    }
+                    self.busy.insert(domain);
+
+                    return Next::Check(link);
+                }
+            }
+        }
+
+        Next::Wait(wait)
+    }
+
+    /// Tell the queue that the `link` is checked at the time `now`, so the next link of its domain can be checked after the delay.
+    pub fn finish(&mut self, link: &Link, now: Instant) {
+        let domain = link.domain().unwrap_or_default();
+
+        self.busy.remove(&domain);
+        self.ready_at.insert(domain, now + self.delay);
+    }
+}
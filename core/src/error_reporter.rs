@@ -65,7 +65,15 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(transparent)]
 pub struct ErrorReporter {
+    /// The fields are boxed, so that a `Result<T, ErrorReporter>` stays small
+    report: Box<Report>,
+}
+
+/// The fields of an [`ErrorReporter`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+struct Report {
     /// The actual error (i.e. A panic message)
     actual_error: String,
     /// Why the error happened
@@ -89,7 +97,7 @@ impl ErrorReporter {
     ///
     /// If you do not have any such error, then return "None".
     pub fn actual_error(&self) -> &str {
-        &self.actual_error
+        &self.report.actual_error
     }
 
     /// Why the error happened
@@ -102,7 +110,7 @@ impl ErrorReporter {
     ///
     /// - Extected a file named *details.md* in your home directory which will contain some details about you. But found nothing.
     pub fn why_error(&self) -> &Vec<String> {
-        &self.why_error
+        &self.report.why_error
     }
 
     /// How to fix the error
@@ -115,7 +123,7 @@ impl ErrorReporter {
     ///
     /// - After creating the file, click the button *Show* again.
     pub fn how_to_fix(&self) -> &Vec<String> {
-        &self.how_to_fix
+        &self.report.how_to_fix
     }
 
     /// Title of the error
@@ -126,7 +134,7 @@ impl ErrorReporter {
     ///
     /// - Invalid username
     pub fn error_title(&self) -> &str {
-        &self.error_title
+        &self.report.error_title
     }
 
     /// When the error happened
@@ -139,12 +147,12 @@ impl ErrorReporter {
     ///
     /// - getting the file *details.md*
     pub fn when_error(&self) -> &str {
-        &self.when_error
+        &self.report.when_error
     }
 
     /// Type of the error
     pub fn error_type(&self) -> &ErrorType {
-        &self.error_type
+        &self.report.error_type
     }
 }
 
@@ -209,15 +217,17 @@ impl<'a> ErrorReporterBuilder<'a> {
     /// Build a new instance of [`ErrorReporter`].
     pub fn build(&self) -> ErrorReporter {
         ErrorReporter {
-            actual_error: self.actual_error.to_string(),
-            why_error: self.why_error.iter().map(|s| s.to_string()).collect(),
-            how_to_fix: self.how_to_fix.iter().map(|s| s.to_string()).collect(),
-            error_title: self.error_title.to_string(),
-            when_error: format!(
-                "Error occurred when {}",
-                self.when_error.to_string().to_lowercase()
-            ),
-            error_type: self.error_type.clone(),
+            report: Box::new(Report {
+                actual_error: self.actual_error.to_string(),
+                why_error: self.why_error.iter().map(|s| s.to_string()).collect(),
+                how_to_fix: self.how_to_fix.iter().map(|s| s.to_string()).collect(),
+                error_title: self.error_title.to_string(),
+                when_error: format!(
+                    "Error occurred when {}",
+                    self.when_error.to_string().to_lowercase()
+                ),
+                error_type: self.error_type.clone(),
+            }),
         }
     }
 }
@@ -238,10 +248,17 @@ pub enum ErrorType {
     ///
     /// This variant is from the [`validate_link`](../../../../src-tauri/target/doc/dsaver/fn.validate_link.html) command.
     InvalidOrNotFound,
-    /// 404 returned by the website.
+    /// 404 or 410 returned by the website.
     ///
-    /// This variant is from the [`validate_link`](../../../../src-tauri/target/doc/dsaver/fn.validate_link.html) command.
+    /// It is a warning, the link can still be saved. This variant is from the [`validate_link`](../../../../src-tauri/target/doc/dsaver/fn.validate_link.html) command.
     PageNotFound,
+    /// Another 4xx or 5xx status returned by the website.
+    ///
+    /// It is a warning, the link can still be saved. This variant is from the [`validate_link`](../../../../src-tauri/target/doc/dsaver/fn.validate_link.html) command.
+    HttpError {
+        /// The HTTP status, like `500`
+        status: u32,
+    },
     /// Browser not available
     ///
     /// This variant is for [`open_browser_linux`], [`open_browser_macos`] and [`open_browser_windows`] commands.
@@ -258,6 +275,57 @@ pub enum ErrorType {
     ///
     /// This variant is for every commands who didn't find the expected directory
     DirectoryNotFound,
+    /// Reading or writing a file failed.
+    ///
+    /// This variant is from the [`LinkStore`](crate::storage::LinkStore) and every commands who uses it.
+    FileSystem,
+    /// The file exists but its content is not valid.
+    ///
+    /// This variant is from the [`LinkStore`](crate::storage::LinkStore) and every commands who uses it.
+    CorruptedData,
+    /// No link found with the given id.
+    ///
+    /// This variant is from the [`LinkStore`](crate::storage::LinkStore) and every commands who uses it.
+    LinkNotFound,
+    /// A link with the same id already exists.
+    ///
+    /// This variant is from the [`LinkStore`](crate::storage::LinkStore) and every commands who uses it.
+    DuplicateLink,
+    /// The file is written by a newer version of the app.
+    ///
+    /// This variant is from the [`LinkStore`](crate::storage::LinkStore) and every commands who uses it.
+    UnsupportedVersion,
+    /// The file was corrupted and only some of the links could be recovered.
+    ///
+    /// The original file is copied into the quarantine directory.
+    ///
+    /// This variant is from the [`LinkStore::recover`](crate::storage::LinkStore::recover) and every commands who uses it.
+    DataRecovered {
+        /// How many links are recovered
+        recovered: usize,
+        /// How many links are lost
+        lost: usize,
+    },
+    /// Reading or writing the SQLite database failed.
+    ///
+    /// This variant is from the `SqliteStorage` and every commands who uses it.
+    Database,
+    /// The search query can't be parsed.
+    ///
+    /// This variant is from the [`Query::parse`](crate::query::Query::parse) and every commands who uses it.
+    InvalidQuery,
+    /// No saved search found with the given id.
+    ///
+    /// This variant is from the [`Storage`](crate::storage::Storage) and every commands who uses it.
+    SavedSearchNotFound,
+    /// The name of the tag is not valid, or it can't be used for the change.
+    ///
+    /// This variant is from the functions of the [`tag`](crate::tag) module and every commands who uses them.
+    InvalidTag,
+    /// No link has the given tag.
+    ///
+    /// This variant is from the functions of the [`tag`](crate::tag) module and every commands who uses them.
+    TagNotFound,
     /// Unknown error.
     ///
     /// This variant is for every commands who isn't sure what the error is.
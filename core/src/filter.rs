@@ -0,0 +1,251 @@
+use crate::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Which links should be shown to the user.
+///
+/// Every field is a condition. A link matches the filter if it matches all the conditions. A condition which is `None` matches every link.
+///
+/// Send it to the backend (e.g. the `query_links` command) to get only the matched links, instead of filtering all the links in the frontend.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+///
+/// // links with the tag `Rust` and the priority `B`
+/// let filter = LinkFilter::default().tags(vec!["Rust"]).priority('B');
+///
+/// assert!(filter.matches(&Link::new("https://www.rust-lang.org").tags("Rust").priority('B')));
+/// assert!(!filter.matches(&Link::new("https://www.rust-lang.org").tags("Rust").priority('A')));
+/// assert!(!filter.matches(&Link::new("https://www.python.org").tags("Python").priority('B')));
+///
+/// // links with both tags `Rust` and `Video`, but without the tag `Old`
+/// let filter = LinkFilter::default()
+///     .tags(vec!["Rust", "Video"])
+///     .tags_mode(MatchMode::All)
+///     .exclude_tags(vec!["Old"]);
+///
+/// assert!(filter.matches(&Link::new("https://www.youtube.com").tags("Rust Video")));
+/// assert!(!filter.matches(&Link::new("https://www.rust-lang.org").tags("Rust")));
+/// assert!(!filter.matches(&Link::new("https://www.youtube.com").tags("Rust Video Old")));
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct LinkFilter {
+    /// The link must have these tags or their child tags (ignoring the case, see [`Link::has_tag`]), see [`tags_mode`](LinkFilter::tags_mode).
+    ///
+    /// If it is an empty list, then no link will match.
+    pub tags: Option<Vec<String>>,
+    /// Whether the link must have any or all of the `tags`. By default it is [`MatchMode::Any`].
+    #[serde(default)]
+    pub tags_mode: MatchMode,
+    /// The link must not have any of these tags or their child tags, ignoring the case.
+    #[serde(default)]
+    pub excluded_tags: Vec<String>,
+    /// The link must be opened with these browsers, see [`browsers_mode`](LinkFilter::browsers_mode).
+    ///
+    /// If it is an empty list, then no link will match.
+    pub browsers: Option<Vec<Browser>>,
+    /// Whether the link must be opened with any or all of the `browsers`. By default it is [`MatchMode::Any`].
+    ///
+    /// A link has only one browser, so with [`MatchMode::All`] it only matches if all the `browsers` are the same.
+    #[serde(default)]
+    pub browsers_mode: MatchMode,
+    /// The link must not be opened with any of these browsers.
+    #[serde(default)]
+    pub excluded_browsers: Vec<Browser>,
+    /// The link must have this priority.
+    pub priority: Option<char>,
+    /// The priority of the link must be between these two priorities (both included), e.g. `('A', 'C')`.
+    pub priority_range: Option<(char, char)>,
+    /// The link must be from any of these domains, see [`Link::domain`].
+    ///
+    /// If it is an empty list, then no link will match.
+    pub domains: Option<Vec<String>>,
+    /// The link must be complete (`true`) or incomplete (`false`).
+    pub complete: Option<bool>,
+    /// The link must be created between these two times. The first one is included and the second one is not.
+    pub created_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// The link must not be opened since this time, see [`not_opened_since`](LinkFilter::not_opened_since).
+    pub not_opened_since: Option<DateTime<Utc>>,
+    /// The link must be broken (`true`) or not (`false`), see [`Link::is_broken`].
+    pub broken: Option<bool>,
+}
+
+/// How a link must match the list of tags or browsers of a [`LinkFilter`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum MatchMode {
+    /// The link must match any of them (OR)
+    #[default]
+    Any,
+    /// The link must match all of them (AND)
+    All,
+}
+
+impl MatchMode {
+    /// Check whether `values` are matched. `matched` checks a single value.
+    ///
+    /// An empty list is never matched.
+    fn matches<T, F: Fn(&T) -> bool>(&self, values: &[T], matched: F) -> bool {
+        match self {
+            Self::Any => values.iter().any(matched),
+            Self::All => !values.is_empty() && values.iter().all(matched),
+        }
+    }
+}
+
+impl LinkFilter {
+    /// Only match the links which have any of the given `tags`. Use [`tags_mode`](LinkFilter::tags_mode) to match all of them.
+    pub fn tags<T: ToString>(mut self, tags: Vec<T>) -> Self {
+        self.tags = Some(tags.iter().map(|tag| tag.to_string()).collect());
+        self
+    }
+
+    /// change whether the link must have any or all of the `tags`
+    pub fn tags_mode(mut self, mode: MatchMode) -> Self {
+        self.tags_mode = mode;
+        self
+    }
+
+    /// Don't match the links which have any of the given `tags`.
+    pub fn exclude_tags<T: ToString>(mut self, tags: Vec<T>) -> Self {
+        self.excluded_tags = tags.iter().map(|tag| tag.to_string()).collect();
+        self
+    }
+
+    /// Only match the links which are opened with any of the given `browsers`. Use [`browsers_mode`](LinkFilter::browsers_mode) to match all of them.
+    pub fn browsers(mut self, browsers: Vec<Browser>) -> Self {
+        self.browsers = Some(browsers);
+        self
+    }
+
+    /// change whether the link must be opened with any or all of the `browsers`
+    pub fn browsers_mode(mut self, mode: MatchMode) -> Self {
+        self.browsers_mode = mode;
+        self
+    }
+
+    /// Don't match the links which are opened with any of the given `browsers`.
+    pub fn exclude_browsers(mut self, browsers: Vec<Browser>) -> Self {
+        self.excluded_browsers = browsers;
+        self
+    }
+
+    /// Only match the links which have the given `priority`.
+    pub fn priority(mut self, priority: char) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Only match the links which have a priority between `from` and `to` (both included).
+    pub fn priority_range(mut self, from: char, to: char) -> Self {
+        self.priority_range = Some((from, to));
+        self
+    }
+
+    /// Only match the links which are from any of the given `domains`.
+    pub fn domains<T: ToString>(mut self, domains: Vec<T>) -> Self {
+        self.domains = Some(domains.iter().map(|domain| domain.to_string()).collect());
+        self
+    }
+
+    /// Only match the links which are complete or incomplete.
+    pub fn complete(mut self, complete: bool) -> Self {
+        self.complete = Some(complete);
+        self
+    }
+
+    /// Only match the links which are created at `from` or later, but before `to`.
+    pub fn created_between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.created_range = Some((from, to));
+        self
+    }
+
+    /// Only match the links which are not opened since `since`, e.g. to find the links which can be deleted.
+    ///
+    /// A link which is never opened matches if it is created before `since`, so the new links don't match.
+    pub fn not_opened_since(mut self, since: DateTime<Utc>) -> Self {
+        self.not_opened_since = Some(since);
+        self
+    }
+
+    /// Only match the links which are broken or not broken.
+    pub fn broken(mut self, broken: bool) -> Self {
+        self.broken = Some(broken);
+        self
+    }
+
+    /// Check whether the `link` matches all the conditions.
+    pub fn matches(&self, link: &Link) -> bool {
+        if let Some(tags) = &self.tags {
+            if !self.tags_mode.matches(tags, |tag| link.has_tag(tag)) {
+                return false;
+            }
+        }
+
+        if self
+            .excluded_tags
+            .iter()
+            .any(|excluded| link.has_tag(excluded))
+        {
+            return false;
+        }
+
+        if let Some(browsers) = &self.browsers {
+            if !self
+                .browsers_mode
+                .matches(browsers, |browser| *browser == link.browser)
+            {
+                return false;
+            }
+        }
+
+        if self.excluded_browsers.contains(&link.browser) {
+            return false;
+        }
+
+        if let Some(priority) = self.priority {
+            if link.priority != priority {
+                return false;
+            }
+        }
+
+        if let Some((from, to)) = self.priority_range {
+            if link.priority < from || link.priority > to {
+                return false;
+            }
+        }
+
+        if let Some(domains) = &self.domains {
+            if !matches!(link.domain(), Some(domain) if domains.contains(&domain)) {
+                return false;
+            }
+        }
+
+        if let Some(complete) = self.complete {
+            if link.complete != complete {
+                return false;
+            }
+        }
+
+        if let Some((from, to)) = self.created_range {
+            if link.created_at < from || link.created_at >= to {
+                return false;
+            }
+        }
+
+        if let Some(since) = self.not_opened_since {
+            if link.last_opened_at.unwrap_or(link.created_at) >= since {
+                return false;
+            }
+        }
+
+        if let Some(broken) = self.broken {
+            if link.is_broken() != broken {
+                return false;
+            }
+        }
+
+        true
+    }
+}
@@ -0,0 +1,623 @@
+//! A journal of the changes made by the user, so that they can be undone and redone.
+//!
+//! The journal is append-only: undoing a change doesn't remove it, but adds a new entry. So the journal is also the activity log of the user. Only the last [`MAX_JOURNAL_ENTRIES`] entries are kept.
+
+use crate::prelude::*;
+use crate::storage::{report_corrupted, report_io, root_dir, write_atomic, Storage};
+use crate::tag::TagInfo;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Name of the file where the journal is stored (inside [`ROOT_DIR`](crate::storage::ROOT_DIR)).
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// The most entries kept in the [`Journal`]. When there are more, the oldest half is dropped.
+pub const MAX_JOURNAL_ENTRIES: usize = 1000;
+
+/// Get the path of the file where the journal is stored.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # use dsaver_core::journal::journal_file;
+/// #
+/// assert_eq!(journal_file("/home/user"), Path::new("/home/user/.DSaver/journal.jsonl"));
+/// ```
+pub fn journal_file<P: AsRef<Path>>(home_dir: P) -> PathBuf {
+    root_dir(home_dir).join(JOURNAL_FILE)
+}
+
+/// A change of the links made by the user.
+///
+/// Every change can be reverted with its [`inverse`](Change::inverse).
+// the changes are few and short-lived, so boxing the links is not worth it
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Change {
+    /// A new link was added
+    Created(Link),
+    /// A link was edited
+    Edited {
+        /// The link before editing
+        before: Link,
+        /// The link after editing
+        after: Link,
+    },
+    /// A link was deleted forever
+    Deleted(Link),
+    /// A link was moved into the trash
+    Trashed(Link),
+    /// A link was moved out of the trash
+    Restored(Link),
+    /// A tag was renamed in every link which had it
+    TagRenamed {
+        /// The old name of the tag
+        from: String,
+        /// The new name of the tag
+        to: String,
+        /// The changed links before renaming
+        before: Vec<Link>,
+        /// The changed links after renaming
+        after: Vec<Link>,
+        /// The infos of the changed tags before renaming
+        #[serde(default)]
+        infos_before: Vec<TagInfo>,
+        /// The infos of the changed tags after renaming
+        #[serde(default)]
+        infos_after: Vec<TagInfo>,
+    },
+    /// A tag was replaced with another tag in every link which had it
+    TagsMerged {
+        /// The tag which was replaced
+        from: String,
+        /// The tag which replaced it
+        into: String,
+        /// The changed links before merging
+        before: Vec<Link>,
+        /// The changed links after merging
+        after: Vec<Link>,
+        /// The infos of the changed tags before merging
+        #[serde(default)]
+        infos_before: Vec<TagInfo>,
+        /// The infos of the changed tags after merging
+        #[serde(default)]
+        infos_after: Vec<TagInfo>,
+    },
+    /// A tag was removed from every link which had it
+    TagDeleted {
+        /// The removed tag
+        tag: String,
+        /// The changed links before removing the tag
+        before: Vec<Link>,
+        /// The changed links after removing the tag
+        after: Vec<Link>,
+        /// The infos of the changed tags before removing the tag
+        #[serde(default)]
+        infos_before: Vec<TagInfo>,
+        /// The infos of the changed tags after removing the tag
+        #[serde(default)]
+        infos_after: Vec<TagInfo>,
+    },
+}
+
+impl Change {
+    /// The change which reverts this change.
+    ///
+    /// A merged or deleted tag can't be expressed as another merge or delete, so their inverse is the same change with the links (and the infos of the tags) before and after swapped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// # use dsaver_core::journal::Change;
+    /// #
+    /// let link = Link::new("https://www.rust-lang.org");
+    ///
+    /// assert_eq!(Change::Created(link.clone()).inverse(), Change::Deleted(link.clone()));
+    /// assert_eq!(Change::Created(link.clone()).inverse().inverse(), Change::Created(link));
+    /// ```
+    pub fn inverse(&self) -> Self {
+        match self.clone() {
+            Self::Created(link) => Self::Deleted(link),
+            Self::Edited { before, after } => Self::Edited {
+                before: after,
+                after: before,
+            },
+            Self::Deleted(link) => Self::Created(link),
+            Self::Trashed(link) => Self::Restored(link),
+            Self::Restored(link) => Self::Trashed(link),
+            Self::TagRenamed {
+                from,
+                to,
+                before,
+                after,
+                infos_before,
+                infos_after,
+            } => Self::TagRenamed {
+                from: to,
+                to: from,
+                before: after,
+                after: before,
+                infos_before: infos_after,
+                infos_after: infos_before,
+            },
+            Self::TagsMerged {
+                from,
+                into,
+                before,
+                after,
+                infos_before,
+                infos_after,
+            } => Self::TagsMerged {
+                from,
+                into,
+                before: after,
+                after: before,
+                infos_before: infos_after,
+                infos_after: infos_before,
+            },
+            Self::TagDeleted {
+                tag,
+                before,
+                after,
+                infos_before,
+                infos_after,
+            } => Self::TagDeleted {
+                tag,
+                before: after,
+                after: before,
+                infos_before: infos_after,
+                infos_after: infos_before,
+            },
+        }
+    }
+
+    /// Make this change in the `storage`.
+    ///
+    /// The edited links keep the `last_opened_at`, `open_count`, `open_history` and `manual_order` which they have in the storage, because those are not changed by the user's edits. They also keep the saved `check` if their url is not changed.
+    ///
+    /// A change of a tag only changes the `tags` of the links, so the other edits made after it are kept. All the links of it are saved at once with [`Storage::update_tags`], and the links which don't exist anymore are skipped.
+    pub fn apply(&self, storage: &mut dyn Storage) -> Result<(), ErrorReporter> {
+        match self {
+            Self::Created(link) => {
+                storage.insert(link.clone())?;
+            }
+            Self::Edited { after, .. } => replace(storage, after)?,
+            Self::Deleted(link) => {
+                // a link without an id is never saved
+                if let Some(id) = link.id {
+                    storage.delete(id)?;
+                }
+            }
+            Self::Trashed(link) => {
+                if let Some(id) = link.id {
+                    storage.trash(id)?;
+                }
+            }
+            Self::Restored(link) => {
+                if let Some(id) = link.id {
+                    storage.restore(id)?;
+                }
+            }
+            Self::TagRenamed {
+                after,
+                infos_before,
+                infos_after,
+                ..
+            }
+            | Self::TagsMerged {
+                after,
+                infos_before,
+                infos_after,
+                ..
+            }
+            | Self::TagDeleted {
+                after,
+                infos_before,
+                infos_after,
+                ..
+            } => {
+                let tags: HashMap<Uuid, &Vec<String>> = after
+                    .iter()
+                    .filter_map(|link| Some((link.id?, &link.tags)))
+                    .collect();
+
+                let links = storage
+                    .links()?
+                    .into_iter()
+                    .filter_map(|mut link| {
+                        link.tags = tags.get(&link.id?)?.to_vec();
+                        Some(link)
+                    })
+                    .collect();
+
+                storage.update_tags(links, infos_before, infos_after)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The link of the change, if only one link is changed.
+    fn link(&self) -> Option<&Link> {
+        match self {
+            Self::Created(link)
+            | Self::Deleted(link)
+            | Self::Trashed(link)
+            | Self::Restored(link)
+            | Self::Edited { after: link, .. } => Some(link),
+            Self::TagRenamed { .. } | Self::TagsMerged { .. } | Self::TagDeleted { .. } => None,
+        }
+    }
+
+    /// A short text about the change, shown to the user.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// # use dsaver_core::journal::Change;
+    /// #
+    /// let link = Link::new("https://www.rust-lang.org").title("Rust");
+    /// assert_eq!(Change::Deleted(link).description(), "Deleted the link Rust");
+    /// ```
+    pub fn description(&self) -> String {
+        match self {
+            Self::Created(link) => format!("Added the link {}", name(link)),
+            Self::Edited { after, .. } => format!("Edited the link {}", name(after)),
+            Self::Deleted(link) => format!("Deleted the link {}", name(link)),
+            Self::Trashed(link) => format!("Moved the link {} to the trash", name(link)),
+            Self::Restored(link) => format!("Restored the link {}", name(link)),
+            Self::TagRenamed { from, to, .. } => format!("Renamed the tag {from} to {to}"),
+            Self::TagsMerged { from, into, .. } => format!("Merged the tag {from} into {into}"),
+            Self::TagDeleted { tag, .. } => format!("Deleted the tag {tag}"),
+        }
+    }
+}
+
+/// Replace the saved link with the `link`, keeping what is not edited by the user.
+fn replace(storage: &mut dyn Storage, link: &Link) -> Result<(), ErrorReporter> {
+    let mut link = link.clone();
+
+    if let Some(saved) = link.id.map(|id| storage.get(id)).transpose()?.flatten() {
+        link.last_opened_at = saved.last_opened_at;
+        link.open_count = saved.open_count;
+        link.open_history = saved.open_history;
+        link.manual_order = saved.manual_order;
+
+        if saved.url == link.url {
+            link.check = saved.check;
+        }
+    }
+
+    storage.update(link)?;
+
+    Ok(())
+}
+
+/// Create a report for a change which can't be undone or redone anymore
+fn report_dropped(when: &str) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: "None",
+        why_error: vec![
+            "The link of this change is deleted forever",
+            "A backup without the link is restored",
+        ],
+        how_to_fix: vec!["The change is removed from the activity, try again to continue with the change before it"],
+        error_title: "Change Not Available",
+        when_error: when,
+        error_type: ErrorType::LinkNotFound,
+    }
+    .build()
+}
+
+/// Title of the link, or its url if it doesn't have a title.
+fn name(link: &Link) -> &str {
+    match &link.title {
+        Some(title) if !title.is_empty() => title,
+        _ => &link.url,
+    }
+}
+
+/// What happened to a [`Change`] in a [`JournalEntry`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    /// The user made the change
+    Done,
+    /// The change was undone
+    Undone,
+    /// The change was done again after undoing it
+    Redone,
+    /// The change can't be undone or redone anymore, because its link was deleted forever
+    Dropped,
+}
+
+/// A single entry of the [`Journal`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JournalEntry {
+    /// Unique id of the entry
+    pub id: Uuid,
+    /// When it happened (in UTC)
+    pub at: DateTime<Utc>,
+    /// What happened to the `change`
+    pub kind: EntryKind,
+    /// The change made by the user. For [`EntryKind::Undone`] it is the change which was undone, not its inverse.
+    pub change: Change,
+}
+
+/// The journal of the changes made by the user.
+///
+/// Every entry is appended as a line of JSON to the file. The file is only rewritten when the oldest entries are dropped (see [`MAX_JOURNAL_ENTRIES`]), or when its last line is torn.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::journal::{Change, Journal};
+/// use dsaver_core::storage::{LinkStore, Storage};
+///
+/// let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+/// let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+/// let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+///
+/// // the user deletes a link
+/// let link = Storage::insert(&mut storage, Link::new("https://www.rust-lang.org")).unwrap();
+/// let deleted = Storage::delete(&mut storage, link.id.unwrap()).unwrap();
+/// journal.record(Change::Deleted(deleted)).unwrap();
+///
+/// // and changes their mind
+/// journal.undo(&mut storage).unwrap();
+/// assert_eq!(Storage::links(&storage).unwrap(), vec![link.clone()]);
+///
+/// journal.redo(&mut storage).unwrap();
+/// assert!(Storage::links(&storage).unwrap().is_empty());
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Journal {
+    /// Path of the file
+    path: PathBuf,
+    /// All entries, the oldest first
+    entries: Vec<JournalEntry>,
+    /// Indexes of the entries which can be undone, the next one last
+    undo: Vec<usize>,
+    /// Indexes of the entries which can be redone, the next one last
+    redo: Vec<usize>,
+}
+
+impl Journal {
+    /// Read the journal from the file of the given `path`.
+    ///
+    /// If the file doesn't exist, then the journal will be empty. It will be created by the first change.
+    ///
+    /// If the app stopped while writing the last line, then that line can't be read. It is dropped from the file, because the change it was recording is already saved.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ErrorReporter> {
+        let path = path.as_ref();
+        let mut journal = Self {
+            path: path.to_path_buf(),
+            entries: Vec::new(),
+            undo: Vec::new(),
+            redo: Vec::new(),
+        };
+
+        let data = match fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(journal),
+            Err(err) => return Err(report_io(err, "reading the file", path)),
+        };
+
+        let mut lines = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .peekable();
+        let mut torn = false;
+
+        while let Some(line) = lines.next() {
+            match serde_json::from_str(line) {
+                Ok(entry) => journal.push(entry),
+                Err(_) if lines.peek().is_none() => torn = true,
+                Err(err) => return Err(report_corrupted(err, path)),
+            }
+        }
+
+        // the next line would be appended to the torn line, so write the file again without it
+        if torn || (!data.ends_with('\n') && !data.trim().is_empty()) {
+            journal.rewrite()?;
+        }
+
+        Ok(journal)
+    }
+
+    /// All entries, the oldest first.
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Remember a change made by the user. It can be undone with [`undo`](Journal::undo).
+    ///
+    /// The changes which were undone before can't be redone anymore.
+    pub fn record(&mut self, change: Change) -> Result<(), ErrorReporter> {
+        self.append(EntryKind::Done, change)
+    }
+
+    /// The change which will be reverted by [`undo`](Journal::undo).
+    pub fn next_undo(&self) -> Option<&Change> {
+        self.undo.last().map(|index| &self.entries[*index].change)
+    }
+
+    /// The change which will be made again by [`redo`](Journal::redo).
+    pub fn next_redo(&self) -> Option<&Change> {
+        self.redo.last().map(|index| &self.entries[*index].change)
+    }
+
+    /// Revert the last change in the `storage`.
+    ///
+    /// It returns the reverted change, or `None` if there is nothing to undo.
+    ///
+    /// If the link of the change doesn't exist anymore, then the change is dropped and an error with [`ErrorType::LinkNotFound`] is returned. So the next undo reverts the change before it.
+    pub fn undo(&mut self, storage: &mut dyn Storage) -> Result<Option<Change>, ErrorReporter> {
+        let change = match self.next_undo() {
+            Some(change) => change.clone(),
+            None => return Ok(None),
+        };
+
+        self.try_apply(&change.inverse(), &change, storage, "undoing the change")?;
+        self.append(EntryKind::Undone, change.clone())?;
+
+        Ok(Some(change))
+    }
+
+    /// Make the last undone change again in the `storage`.
+    ///
+    /// It returns the change, or `None` if there is nothing to redo.
+    ///
+    /// Like [`undo`](Journal::undo), a change which can't be made anymore is dropped.
+    pub fn redo(&mut self, storage: &mut dyn Storage) -> Result<Option<Change>, ErrorReporter> {
+        let change = match self.next_redo() {
+            Some(change) => change.clone(),
+            None => return Ok(None),
+        };
+
+        self.try_apply(&change, &change, storage, "redoing the change")?;
+        self.append(EntryKind::Redone, change.clone())?;
+
+        Ok(Some(change))
+    }
+
+    /// Forget the changes of the links with the given `ids`, so they can't be undone or redone anymore.
+    ///
+    /// Call it after deleting the links forever, e.g. when the trash is emptied.
+    pub fn forget(&mut self, ids: &[Uuid]) -> Result<(), ErrorReporter> {
+        let forgotten: Vec<Change> = self
+            .undo
+            .iter()
+            .chain(&self.redo)
+            .map(|index| &self.entries[*index].change)
+            .filter(|change| {
+                matches!(change.link().and_then(|link| link.id), Some(id) if ids.contains(&id))
+            })
+            .cloned()
+            .collect();
+
+        for change in forgotten {
+            self.append(EntryKind::Dropped, change)?;
+        }
+
+        Ok(())
+    }
+
+    /// Make the `change` in the `storage`. If its link doesn't exist anymore, then the `entry` is dropped.
+    fn try_apply(
+        &mut self,
+        change: &Change,
+        entry: &Change,
+        storage: &mut dyn Storage,
+        when: &str,
+    ) -> Result<(), ErrorReporter> {
+        match change.apply(storage) {
+            Err(err) if err.error_type() == &ErrorType::LinkNotFound => {
+                self.append(EntryKind::Dropped, entry.clone())?;
+                Err(report_dropped(when))
+            }
+            result => result,
+        }
+    }
+
+    /// Add the `entry` to the entries, and update the undo and redo stacks with it.
+    ///
+    /// An undone or redone entry only moves the next change if it is the same change. So the stacks stay right after the oldest entries are dropped.
+    fn push(&mut self, entry: JournalEntry) {
+        let index = self.entries.len();
+        let (from, to) = match entry.kind {
+            EntryKind::Done => {
+                self.undo.push(index);
+                self.redo.clear();
+                self.entries.push(entry);
+                return;
+            }
+            EntryKind::Undone => (&mut self.undo, &mut self.redo),
+            EntryKind::Redone => (&mut self.redo, &mut self.undo),
+            EntryKind::Dropped => {
+                let entries = &self.entries;
+                for stack in [&mut self.undo, &mut self.redo] {
+                    stack.retain(|index| entries[*index].change != entry.change);
+                }
+                self.entries.push(entry);
+                return;
+            }
+        };
+
+        if let Some(next) = from.last() {
+            if self.entries[*next].change == entry.change {
+                to.push(*next);
+                from.pop();
+            }
+        }
+
+        self.entries.push(entry);
+    }
+
+    /// Add a new entry at the end of the file.
+    fn append(&mut self, kind: EntryKind, change: Change) -> Result<(), ErrorReporter> {
+        let entry = JournalEntry {
+            id: Uuid::new_v4(),
+            at: Utc::now(),
+            kind,
+            change,
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| report_io(err, "creating the directory", parent))?;
+        }
+
+        // Serializing `JournalEntry` never fails
+        let line = serde_json::to_string(&entry).unwrap();
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| {
+                writeln!(file, "{line}")?;
+                file.sync_data()
+            })
+            .map_err(|err| report_io(err, "writing the file", &self.path))?;
+
+        self.push(entry);
+
+        if self.entries.len() > MAX_JOURNAL_ENTRIES {
+            let entries = self
+                .entries
+                .split_off(self.entries.len() - MAX_JOURNAL_ENTRIES / 2);
+            self.entries.clear();
+            self.undo.clear();
+            self.redo.clear();
+
+            for entry in entries {
+                self.push(entry);
+            }
+            self.rewrite()?;
+        }
+
+        Ok(())
+    }
+
+    /// Write all the entries into the file, replacing it.
+    fn rewrite(&self) -> Result<(), ErrorReporter> {
+        // Serializing `JournalEntry` never fails
+        let data: String = self
+            .entries
+            .iter()
+            .map(|entry| serde_json::to_string(entry).unwrap() + "\n")
+            .collect();
+
+        write_atomic(&self.path, data).map_err(|err| report_io(err, "writing the file", &self.path))
+    }
+}
@@ -1,7 +1,20 @@
 //! Common structs and enums for the application.
 //!
 //! Both backend and frontend will use these types for sharing data between them.
+
 pub mod browser;
+pub mod check;
 pub mod error_reporter;
+pub mod filter;
+pub mod journal;
 pub mod link;
+pub mod metadata;
 pub mod prelude;
+pub mod query;
+pub mod refresh;
+pub mod saved_search;
+pub mod search;
+pub mod settings;
+pub mod sort;
+pub mod storage;
+pub mod tag;
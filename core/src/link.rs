@@ -1,38 +1,37 @@
 use crate::browser::Browser;
+use crate::check::LinkCheck;
+use crate::metadata::PageMetadata;
+use crate::tag::{is_within, normalize_tag};
+use chrono::{DateTime, Utc};
 use itertools::Itertools;
-#[cfg(feature = "wasm")]
-use js_sys::Date;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use uuid::Uuid;
 
+/// How many openings are kept in the [`Link::open_history`]. The older openings are only counted in the [`Link::open_count`].
+pub const MAX_OPEN_HISTORY: usize = 100;
+
 /// A struct that represents a webpage link
 ///
 /// This is the main struct for storing the webpage link information.
 ///
-/// For storing a link from client side, you can use [`writeFile`] and [`createDir`] functions from tauri api.
+/// For storing links in the filesystem, use [`LinkStore`](crate::storage::LinkStore). From client side, you can call the tauri commands (such as `add_link`) which use it.
 ///
 /// Before saving the data, you should validate the link/url and then add it to the filesystem
 ///
 /// # Example
 ///
-/// Create a javascript file to call tauri apis
+/// Create a javascript file to call the tauri command
 ///
 /// *file: assets/scripts/write.js*
 /// ```js
-/// const ROOT_DIR = ".Dsaver";
-///
-/// export async function writeFile(data) {
+/// export async function addLink(link) {
 ///     // importing tauri apis
-///     const { writeTextFile, createDir, BaseDirectory } = window.__TAURI__.fs;
+///     const invoke = window.__TAURI__.invoke;
 ///
 ///     try {
-///         // create the directory
-///         await createDir(ROOT_DIR, { dir: BaseDirectory.Home, recursive: true });
-///         // write the data into the file
-///         await writeTextFile(`${ROOT_DIR}/test.json`, data, { dir: BaseDirectory.Home, recursive: true });
-///
-///         return null;
+///         // validate and save the link
+///         return JSON.stringify(await invoke("add_link", { link }));
 ///     } catch (error) {
 ///         console.log("ERROR: ", error);
 ///         return JSON.stringify(error);
@@ -44,8 +43,8 @@ use uuid::Uuid;
 /// ```ignore
 /// #[wasm_bindgen(module = "/assets/scripts/write.js")]
 /// extern "C" {
-///     #[wasm_bindgen(js_name = writeFile, catch)]
-///     pub async fn write_file(data: String) -> Result<JsValue, JsValue>;
+///     #[wasm_bindgen(js_name = addLink, catch)]
+///     pub async fn add_link(link: String) -> Result<JsValue, JsValue>;
 /// }
 /// ```
 ///
@@ -57,22 +56,19 @@ use uuid::Uuid;
 /// use dsaver_project_types::Link;
 ///
 /// let link =
-///     Link::new_with_date("https://www.youtube.com/watch?v=ygL_xcavzQ4".to_string())
+///     Link::new("https://www.youtube.com/watch?v=ygL_xcavzQ4".to_string())
 ///         .title("Rust tutorial - Youtube")
 ///         .priority('C')
 ///         .tags("Videos Tutorial RustTutorial");
 ///
 /// // save to the home directory
 /// spawn_local(async move {
-///     write_file(serde_json::to_string(&link).unwrap())
+///     add_link(serde_json::to_string(&link).unwrap())
 ///         .await
 ///         .unwrap();
 /// });
 ///
 /// ```
-///
-/// [`writeFile`]: https://www.tauri.app/v1/api/js/fs#writefile
-/// [`createDir`]: https://www.tauri.app/v1/api/js/fs#createdir
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Hash, Eq)]
 pub struct Link {
     /// Unique identifier for this link
@@ -113,6 +109,11 @@ pub struct Link {
     ///
     /// And if the value is Some("some title"), it means that the title has already been fetched and the website has a title.
     pub title: Option<String>,
+    /// Whether the user has written the `title` instead of fetching it from the webpage
+    ///
+    /// Such a title is never replaced when the link is refreshed (see [`LinkRefresh`](crate::refresh::LinkRefresh)).
+    #[serde(default)]
+    pub custom_title: bool,
     /// Description of the webapage
     ///
     /// This tag can be automatically fetched from the website by the link.
@@ -169,16 +170,44 @@ pub struct Link {
     ///
     /// *FUTURE* You can also filter the links by using this property.
     pub complete: bool,
-    /// Link's creation date
-    ///
-    /// The date will be created when the link is created.
-    ///
-    /// The format of the date is: `{date} {month} {year}`
-    ///
-    /// You can use [`new_with_date`](#method.new_with_date) to automatically create a link object with the local date.
-    ///
-    /// *FUTURE* You can sort links by oldest links or latest links
-    pub date: String,
+    /// When the link was created (in UTC)
+    ///
+    /// It is set by [`new`](#method.new), and the backend sets it again when the link is saved for the first time.
+    ///
+    /// Convert it into the user's timezone before showing it.
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+    /// When the link was edited last time (in UTC)
+    ///
+    /// It is the same as `created_at` if the link is never edited. The backend updates it whenever the user edits the link.
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+    /// When the link was opened in the browser last time (in UTC)
+    ///
+    /// It is `None` if the link is never opened.
+    #[serde(default)]
+    pub last_opened_at: Option<DateTime<Utc>>,
+    /// How many times the link was opened in the browser
+    #[serde(default)]
+    pub open_count: u32,
+    /// The last [`MAX_OPEN_HISTORY`] times the link was opened in the browser, the oldest first. See [`opened`](#method.opened).
+    #[serde(default)]
+    pub open_history: Vec<OpenRecord>,
+    /// Position of the link when the user sorts the links by hand (see [`SortOrder::Manual`](crate::sort::SortOrder::Manual)). The smallest position is first.
+    ///
+    /// It is `None` if the user has never moved the link. Those links are shown after the moved links.
+    #[serde(default)]
+    pub manual_order: Option<u32>,
+    /// The image, site name, icon and other information fetched from the webpage when the link was validated
+    ///
+    /// It is empty if the link was never validated.
+    #[serde(default)]
+    pub metadata: PageMetadata,
+    /// The result of the last time the link was checked (see [`check`](crate::check))
+    ///
+    /// It is `None` if the link is never checked.
+    #[serde(default)]
+    pub check: Option<LinkCheck>,
 }
 
 impl Link {
@@ -202,65 +231,32 @@ impl Link {
     /// assert_eq!(link.browser, Browser::SysDefault);
     /// assert_eq!(link.priority, 'A');
     /// assert_eq!(link.complete, false);
-    /// assert_eq!(link.date, String::new());    
+    /// assert_eq!(link.created_at, link.updated_at);
+    /// assert_eq!(link.last_opened_at, None);
+    /// assert_eq!(link.open_count, 0);
+    /// assert!(link.open_history.is_empty());
     /// ```
     pub fn new<T: AsRef<str> + Display>(url: T) -> Self {
-        Link {
-            id: Some(Uuid::new_v4()),
-            url: url.to_string(),
-            title: None,
-            description: None,
-            tags: vec![String::from("GeneralTag")],
-            priority: 'A',
-            browser: Browser::default(),
-            complete: false,
-            date: String::from(""),
-        }
-    }
-
-    /// Initialize a new [`Link`] with the given `url` and with a date (automatically)
-    ///
-    /// The format of the date is: `{date} {month} {year}`
-    ///
-    /// This function is intended to be used inside wasm environment because it calls the javascript functions for getting the date
-    ///
-    /// *You need to enable `wasm` feature to use this method*
-    #[cfg(feature = "wasm")]
-    pub fn new_with_date<T: AsRef<str> + Display>(url: T) -> Self {
-        let months = [
-            "January",
-            "February",
-            "March",
-            "April",
-            "May",
-            "June",
-            "July",
-            "August",
-            "September",
-            "October",
-            "November",
-            "December",
-        ];
-
-        let today = Date::new_0();
-
-        let date = format!(
-            "{date} {month} {year}",
-            date = today.get_date(),
-            month = months[today.get_month() as usize],
-            year = today.get_full_year()
-        );
+        let now = Utc::now();
 
         Link {
             id: Some(Uuid::new_v4()),
             url: url.to_string(),
             title: None,
+            custom_title: false,
             description: None,
             tags: vec![String::from("GeneralTag")],
             priority: 'A',
-            browser: Browser::SysDefault,
+            browser: Browser::default(),
             complete: false,
-            date,
+            created_at: now,
+            updated_at: now,
+            last_opened_at: None,
+            open_count: 0,
+            open_history: Vec::new(),
+            manual_order: None,
+            metadata: PageMetadata::default(),
+            check: None,
         }
     }
 
@@ -318,6 +314,8 @@ impl Link {
     ///
     /// If you use this function to create tags, then it will automatically make the tags unique.
     ///
+    /// Nested tags are written like paths, e.g. `dev/rust/async`. Their separators are normalized (see [`normalize_tag`]), so `dev\\rust//async/` is the same tag.
+    ///
     /// # Example
     ///
     /// ```
@@ -338,9 +336,20 @@ impl Link {
     ///     String::from("Rust"),
     ///     String::from("Python"),
     /// ]);
+    ///
+    /// // nested tags
+    /// let link = link.tags("dev/rust/async dev\\rust\\async /Video/");
+    /// assert_eq!(link.tags, vec![String::from("dev/rust/async"), String::from("Video")]);
     /// ```
     pub fn tags<T: AsRef<str> + Display>(self, tags: T) -> Self {
-        self.tags_vec(tags.to_string().split_whitespace().unique().collect())
+        self.tags_vec(
+            tags.to_string()
+                .split_whitespace()
+                .map(normalize_tag)
+                .filter(|tag| !tag.is_empty())
+                .unique()
+                .collect(),
+        )
     }
 
     /// Change the `tags` field of the link using Vec of strings
@@ -385,6 +394,29 @@ impl Link {
         self
     }
 
+    /// Normalize the tags of the link like [`tags`](#method.tags) does: the separators of nested tags are fixed, and empty or repeated tags are removed.
+    ///
+    /// The storages call it before saving a link, so every saved tag is valid. A link without any tag (e.g. after its only tag is deleted) doesn't get the default tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// #
+    /// let link = Link::new("http://example.com")
+    ///     .tags_vec(vec!["/dev\\rust/", "dev/rust", "Video "])
+    ///     .normalize_tags();
+    /// assert_eq!(link.tags, vec![String::from("dev/rust"), String::from("Video")]);
+    /// ```
+    pub fn normalize_tags(self) -> Self {
+        if self.tags.is_empty() {
+            return self;
+        }
+
+        let tags = self.tags.join(" ");
+        self.tags(tags)
+    }
+
     /// Change the `priority` field of the link
     ///
     /// # Example
@@ -450,27 +482,192 @@ impl Link {
         self
     }
 
-    /// change the`date` field of the link
+    /// change the `created_at` field of the link
     ///
-    /// The default value is "" (empty string)
+    /// It changes the `updated_at` field too, because a link can't be edited before it is created.
+    ///
+    /// The default value is the current time.
     ///
     /// # Example
     ///
     /// ```
-    /// # use dsaver_project_types::Link;
+    /// # use dsaver_core::prelude::*;
+    /// # use chrono::{TimeZone, Utc};
     /// #
-    /// let link = Link::new("http://example.com");
-    ///
-    /// // before changing the date
-    /// assert_eq!(link.date, String::new());
+    /// let date = Utc.with_ymd_and_hms(2022, 10, 11, 0, 0, 0).unwrap();
     ///
     /// // change
-    /// let link = link.date("11 October 2022");
+    /// let link = Link::new("http://example.com").created_at(date);
     ///
     /// // after changing the date
-    /// assert_eq!(link.date, String::from("11 October 2022"));
-    pub fn date<T: AsRef<str> + Display>(mut self, date: T) -> Self {
-        self.date = date.to_string();
+    /// assert_eq!(link.created_at, date);
+    /// assert_eq!(link.updated_at, date);
+    pub fn created_at(mut self, created_at: DateTime<Utc>) -> Self {
+        self.created_at = created_at;
+        self.updated_at = created_at;
         self
     }
+
+    /// Remember that the link was opened in the `browser` just now
+    ///
+    /// It sets the `last_opened_at`, increases the `open_count` and adds the opening to the `open_history`. Only the last [`MAX_OPEN_HISTORY`] openings are kept in the `open_history`. The `updated_at` is not changed, because opening a link doesn't edit it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// #
+    /// let mut link = Link::new("http://example.com");
+    ///
+    /// link.opened(Browser::Firefox);
+    /// link.opened(Browser::Chrome);
+    ///
+    /// assert_eq!(link.open_count, 2);
+    /// assert_eq!(link.open_history.len(), 2);
+    /// assert_eq!(link.open_history[1].browser, Browser::Chrome);
+    /// assert_eq!(link.last_opened_at, Some(link.open_history[1].opened_at));
+    ///
+    /// // the oldest openings are removed from the history, but they are still counted
+    /// for _ in 0..MAX_OPEN_HISTORY {
+    ///     link.opened(Browser::Firefox);
+    /// }
+    /// assert_eq!(link.open_count, MAX_OPEN_HISTORY as u32 + 2);
+    /// assert_eq!(link.open_history.len(), MAX_OPEN_HISTORY);
+    /// assert!(link.open_history.iter().all(|record| record.browser == Browser::Firefox));
+    /// ```
+    pub fn opened(&mut self, browser: Browser) {
+        let now = Utc::now();
+
+        self.last_opened_at = Some(now);
+        self.open_count = self.open_count.saturating_add(1);
+        self.open_history.push(OpenRecord {
+            opened_at: now,
+            browser,
+        });
+
+        let removed = self.open_history.len().saturating_sub(MAX_OPEN_HISTORY);
+        self.open_history.drain(..removed);
+    }
+
+    /// Get the domain name of the link's `url` in lowercase, without `www.`
+    ///
+    /// It returns `None` if the url doesn't have a domain name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// #
+    /// let link = Link::new("https://www.GitHub.com/KRShanto/DSaver?tab=readme");
+    /// assert_eq!(link.domain(), Some(String::from("github.com")));
+    ///
+    /// let link = Link::new("docs.rs:443/chrono");
+    /// assert_eq!(link.domain(), Some(String::from("docs.rs")));
+    ///
+    /// let link = Link::new("");
+    /// assert_eq!(link.domain(), None);
+    /// ```
+    pub fn domain(&self) -> Option<String> {
+        // remove the scheme (`https://`)
+        let url = match self.url.split_once("://") {
+            Some((_, rest)) => rest,
+            None => &self.url,
+        };
+
+        // remove the path, query and fragment
+        let host = url.split(['/', '?', '#']).next().unwrap_or_default();
+        // remove the user (`user@`) and the port (`:443`)
+        let host = host.rsplit('@').next().unwrap_or_default();
+        let host = host.split(':').next().unwrap_or_default().to_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host);
+
+        if host.is_empty() {
+            None
+        } else {
+            Some(host.to_string())
+        }
+    }
+
+    /// Whether the link was dead the last time it was checked, see [`LinkCheck::is_broken`]
+    ///
+    /// A link which is never checked is not broken.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// # use dsaver_core::check::LinkCheck;
+    /// #
+    /// let mut link = Link::new("https://example.com/old");
+    /// assert!(!link.is_broken());
+    ///
+    /// link.check = Some(LinkCheck::new(&link.url).status(404));
+    /// assert!(link.is_broken());
+    /// ```
+    pub fn is_broken(&self) -> bool {
+        matches!(&self.check, Some(check) if check.is_broken())
+    }
+
+    /// Whether the link has the `tag` or one of its child tags (see [`is_within`]), ignoring the case of the ASCII letters.
+    ///
+    /// The [`Query`](crate::query::Query) and the [`LinkFilter`](crate::filter::LinkFilter) find the links of a tag with it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// #
+    /// let link = Link::new("https://tokio.rs").tags("dev/Rust/async");
+    ///
+    /// assert!(link.has_tag("dev/rust"));
+    /// assert!(link.has_tag("Dev"));
+    /// assert!(!link.has_tag("dev/rustlang"));
+    /// ```
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.to_ascii_lowercase();
+
+        self.tags
+            .iter()
+            .any(|link_tag| is_within(&link_tag.to_ascii_lowercase(), &tag))
+    }
+
+    /// Save the `check` of the link. It returns whether the check is saved.
+    ///
+    /// The check is not saved if the url of the link is changed since the check. An [inconclusive](LinkCheck::is_inconclusive) check doesn't replace the previous check of the same url, so a link doesn't look working or broken just because the user was offline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// # use dsaver_core::check::LinkCheck;
+    /// #
+    /// let mut link = Link::new("https://example.com/old");
+    ///
+    /// assert!(link.set_check(LinkCheck::new(&link.url).status(404)));
+    /// assert!(!link.set_check(LinkCheck::new(&link.url).error("Timeout was reached")));
+    /// assert!(!link.set_check(LinkCheck::new("https://example.com/new").status(200)));
+    /// assert!(link.is_broken());
+    /// ```
+    pub fn set_check(&mut self, check: LinkCheck) -> bool {
+        if check.url != self.url {
+            return false;
+        }
+
+        let has_check = matches!(&self.check, Some(previous) if previous.url == self.url);
+        if check.is_inconclusive() && has_check {
+            return false;
+        }
+
+        self.check = Some(check);
+        true
+    }
+}
+
+/// A single opening of a [`Link`] in the browser, see [`Link::open_history`]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Hash, Eq)]
+pub struct OpenRecord {
+    /// When the link was opened (in UTC)
+    pub opened_at: DateTime<Utc>,
+    /// The browser in which the link was opened
+    pub browser: Browser,
 }
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+/// Information about a webpage, other than its title and description
+///
+/// It is fetched from the webpage when the link is validated, and stored in [`Link::metadata`](crate::link::Link::metadata). Every field is `None` if the webpage doesn't have it.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::metadata::PageMetadata;
+///
+/// let metadata = PageMetadata {
+///     site_name: Some("GitHub".into()),
+///     favicon: Some("https://github.com/favicon.ico".into()),
+///     ..Default::default()
+/// };
+///
+/// assert!(!metadata.is_empty());
+/// assert!(PageMetadata::default().is_empty());
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Hash, Eq)]
+pub struct PageMetadata {
+    /// The Open Graph or Twitter card image of the webpage, shown as the thumbnail of the link
+    pub image: Option<String>,
+    /// Name of the website, like `YouTube`
+    pub site_name: Option<String>,
+    /// The canonical url of the webpage. It can be different from the url of the link.
+    pub canonical_url: Option<String>,
+    /// Url of the icon of the website
+    pub favicon: Option<String>,
+    /// Language of the webpage, like `en`
+    pub language: Option<String>,
+    /// Content type of the webpage without the charset, like `text/html`
+    pub content_type: Option<String>,
+}
+
+impl PageMetadata {
+    /// Whether the webpage has none of the metadata
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Find the icon of a webpage from its `html`.
+///
+/// It returns the `href` of the first `<link>` whose `rel` has `icon` (like `rel="shortcut icon"`). The `href` can be relative, use [`resolve_url`] to make it absolute.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::metadata::find_favicon;
+///
+/// let html = r#"<head><link rel="stylesheet" href="/main.css"><LINK REL="Shortcut Icon" HREF='/static/icon.png'></head>"#;
+/// assert_eq!(find_favicon(html), Some(String::from("/static/icon.png")));
+///
+/// assert_eq!(find_favicon("<head></head>"), None);
+/// ```
+pub fn find_favicon(html: &str) -> Option<String> {
+    // same byte positions as `html`
+    let lowercase = html.to_ascii_lowercase();
+    let mut start = 0;
+
+    while let Some(position) = lowercase[start..].find("<link") {
+        let tag_start = start + position + "<link".len();
+        let tag_end = lowercase[tag_start..]
+            .find('>')
+            .map_or(html.len(), |end| tag_start + end);
+        start = tag_end;
+
+        let attributes = attributes(&html[tag_start..tag_end]);
+        let value = |name: &str| {
+            attributes
+                .iter()
+                .find(|(attribute, _)| attribute == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        let is_icon = value("rel")
+            .into_iter()
+            .flat_map(str::split_whitespace)
+            .any(|rel| rel.eq_ignore_ascii_case("icon"));
+        match value("href") {
+            Some(href) if is_icon && !href.trim().is_empty() => {
+                return Some(href.trim().replace("&amp;", "&"))
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Make the `href` found inside the webpage at `base` an absolute url.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::metadata::resolve_url;
+///
+/// let base = "https://docs.rs/chrono/latest/index.html?search=date";
+///
+/// assert_eq!(resolve_url(base, "https://crates.io/icon.png"), "https://crates.io/icon.png");
+/// assert_eq!(resolve_url(base, "//static.docs.rs/icon.png"), "https://static.docs.rs/icon.png");
+/// assert_eq!(resolve_url(base, "/favicon.ico"), "https://docs.rs/favicon.ico");
+/// assert_eq!(resolve_url(base, "icon.png"), "https://docs.rs/chrono/latest/icon.png");
+/// ```
+pub fn resolve_url(base: &str, href: &str) -> String {
+    if href.contains("://") || href.starts_with("data:") {
+        return href.to_string();
+    }
+
+    let (scheme, rest) = base.split_once("://").unwrap_or(("https", base));
+    if let Some(href) = href.strip_prefix("//") {
+        return format!("{scheme}://{href}");
+    }
+
+    // remove the query and fragment
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = match rest.find('/') {
+        Some(slash) => rest.split_at(slash),
+        None => (rest, "/"),
+    };
+
+    if href.starts_with('/') {
+        format!("{scheme}://{host}{href}")
+    } else {
+        // relative to the directory of the webpage
+        let directory = &path[..=path.rfind('/').unwrap_or_default()];
+        format!("{scheme}://{host}{directory}{href}")
+    }
+}
+
+/// The attributes (names in lowercase) of a html tag, from the text after the tag name
+fn attributes(tag: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = tag;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        if name_end == 0 {
+            break;
+        }
+
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        // an attribute without a value, like `async`
+        let mut value = "";
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (found, remaining) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &after[1..];
+                    let end = inner.find(quote).unwrap_or(inner.len());
+                    (&inner[..end], inner.get(end + 1..).unwrap_or_default())
+                }
+                _ => after.split_at(after.find(char::is_whitespace).unwrap_or(after.len())),
+            };
+            value = found;
+            rest = remaining;
+        }
+
+        attributes.push((name, value.to_string()));
+    }
+
+    attributes
+}
@@ -1,3 +1,6 @@
 pub use crate::browser::*;
 pub use crate::error_reporter::*;
+pub use crate::filter::*;
 pub use crate::link::*;
+pub use crate::saved_search::*;
+pub use crate::sort::*;
@@ -0,0 +1,449 @@
+//! A small query language for filtering the links.
+//!
+//! The search bar of the app, a CLI and the saved searches use the same [`Query`], so a query always matches the same links everywhere.
+
+use crate::prelude::*;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::cmp::Ordering;
+
+/// All keys which can be used in a query
+pub const QUERY_KEYS: [&str; 8] = [
+    "tag", "priority", "browser", "domain", "is", "added", "updated", "opened",
+];
+
+/// How a value of a link is compared with the value of the query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// `key:value` or `key:=value`
+    Equal,
+    /// `key:<value`
+    Less,
+    /// `key:<=value`
+    LessOrEqual,
+    /// `key:>value`
+    Greater,
+    /// `key:>=value`
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /// Check whether `value` compares to `other` like this comparison.
+    pub fn compare<T: Ord>(&self, value: T, other: T) -> bool {
+        let ordering = value.cmp(&other);
+
+        match self {
+            Self::Equal => ordering == Ordering::Equal,
+            Self::Less => ordering == Ordering::Less,
+            Self::LessOrEqual => ordering != Ordering::Greater,
+            Self::Greater => ordering == Ordering::Greater,
+            Self::GreaterOrEqual => ordering != Ordering::Less,
+        }
+    }
+
+    /// Split the comparison operator from the beginning of the `value`.
+    fn split(value: &str) -> (Self, &str) {
+        for (operator, comparison) in [
+            ("<=", Self::LessOrEqual),
+            (">=", Self::GreaterOrEqual),
+            ("<", Self::Less),
+            (">", Self::Greater),
+            ("=", Self::Equal),
+        ] {
+            if let Some(value) = value.strip_prefix(operator) {
+                return (comparison, value);
+            }
+        }
+
+        (Self::Equal, value)
+    }
+}
+
+/// The state of a link, used by `is:`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkState {
+    /// `is:complete`
+    Complete,
+    /// `is:incomplete`
+    Incomplete,
+    /// `is:opened` - the link was opened at least once
+    Opened,
+    /// `is:unopened` - the link was never opened
+    Unopened,
+    /// `is:broken` - the link was dead the last time it was checked, see [`Link::is_broken`]
+    Broken,
+}
+
+/// A single condition of a [`Query`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Condition {
+    /// A word without a key. The `title`, `url`, `description` or a tag of the link must contain it (ignoring the case).
+    Text(String),
+    /// `tag:rust` - the link must have the tag or one of its child tags, like `rust/async` (ignoring the case), see [`Link::has_tag`].
+    Tag(String),
+    /// `priority:<=C` - the priority of the link is compared by the alphabet, so `A` is less than `C`.
+    Priority(Comparison, char),
+    /// `browser:firefox` - the link must be opened with the browser.
+    Browser(Browser),
+    /// `domain:github.com` - the domain of the link must be the domain or its subdomain.
+    Domain(String),
+    /// `is:incomplete` - the link must be in the state.
+    Is(LinkState),
+    /// `added:>2026-01-01` - the day (in UTC) when the link was created.
+    Added(Comparison, NaiveDate),
+    /// `updated:2026-01-01` - the day (in UTC) when the link was edited last time.
+    Updated(Comparison, NaiveDate),
+    /// `opened:<2026-01-01` - the day (in UTC) when the link was opened last time. A link which is never opened doesn't match.
+    Opened(Comparison, NaiveDate),
+}
+
+impl Condition {
+    /// Check whether the `link` matches this condition.
+    pub fn matches(&self, link: &Link) -> bool {
+        match self {
+            Self::Text(text) => {
+                let text = text.to_lowercase();
+                let contains = |field: &str| field.to_lowercase().contains(&text);
+
+                contains(&link.url)
+                    || matches!(&link.title, Some(title) if contains(title))
+                    || matches!(&link.description, Some(description) if contains(description))
+                    || link.tags.iter().any(|tag| contains(tag))
+            }
+            Self::Tag(tag) => link.has_tag(tag),
+            Self::Priority(comparison, priority) => comparison.compare(link.priority, *priority),
+            Self::Browser(browser) => &link.browser == browser,
+            Self::Domain(domain) => match link.domain() {
+                Some(link_domain) => {
+                    link_domain == *domain || link_domain.ends_with(&format!(".{domain}"))
+                }
+                None => false,
+            },
+            Self::Is(state) => match state {
+                LinkState::Complete => link.complete,
+                LinkState::Incomplete => !link.complete,
+                LinkState::Opened => link.last_opened_at.is_some(),
+                LinkState::Unopened => link.last_opened_at.is_none(),
+                LinkState::Broken => link.is_broken(),
+            },
+            Self::Added(comparison, date) => compare_day(*comparison, link.created_at, *date),
+            Self::Updated(comparison, date) => compare_day(*comparison, link.updated_at, *date),
+            Self::Opened(comparison, date) => match link.last_opened_at {
+                Some(opened_at) => compare_day(*comparison, opened_at, *date),
+                None => false,
+            },
+        }
+    }
+}
+
+/// A [`Condition`] of a [`Query`] which may be negated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Term {
+    /// `-tag:video` - the link must *not* match the condition.
+    pub negated: bool,
+    /// The condition
+    pub condition: Condition,
+}
+
+impl Term {
+    /// Check whether the `link` matches this term.
+    pub fn matches(&self, link: &Link) -> bool {
+        self.condition.matches(link) != self.negated
+    }
+}
+
+/// A parsed search query, like `tag:rust priority:<=C -tag:video tutorial`.
+///
+/// A query is a list of terms separated by spaces. A link matches the query if it matches all the terms.
+///
+/// - `key:value` compares a field of the link. See [`QUERY_KEYS`] and [`Condition`] for all keys.
+/// - `key:<value`, `key:<=value`, `key:>value`, `key:>=value` compare the `priority` and the dates (`YYYY-MM-DD`).
+/// - A word without a key must be contained in the link's `title`, `url`, `description` or tags.
+/// - `-` before a term negates it.
+/// - Use quotes for spaces: `tag:"machine learning"` or `"hello world"`.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::query::Query;
+///
+/// let query = Query::parse("tag:rust priority:<=C browser:firefox domain:github.com is:incomplete -tag:video").unwrap();
+///
+/// let link = Link::new("https://github.com/rust-lang/rust")
+///     .tags("Rust Code")
+///     .priority('B')
+///     .browser(Browser::Firefox);
+///
+/// assert!(query.matches(&link));
+/// assert!(!query.matches(&link.clone().tags("Rust Video")));
+/// assert!(!query.matches(&link.clone().priority('D')));
+///
+/// // Errors tell the user how to fix them
+/// let error = Query::parse("tga:rust").unwrap_err();
+/// assert_eq!(error.error_type(), &ErrorType::InvalidQuery);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+impl Query {
+    /// Parse the `query`.
+    ///
+    /// It will return an error with [`ErrorType::InvalidQuery`] if the query is not valid. An empty query matches every link.
+    pub fn parse(query: &str) -> Result<Self, ErrorReporter> {
+        let terms = split_terms(query)?
+            .iter()
+            .map(|term| parse_term(term))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { terms })
+    }
+
+    /// All terms of the query
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    /// Whether the query doesn't have any term.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Check whether the `link` matches all the terms.
+    pub fn matches(&self, link: &Link) -> bool {
+        self.terms.iter().all(|term| term.matches(link))
+    }
+
+    /// The words without a key (which are not negated), separated by spaces.
+    ///
+    /// Give them to the [`SearchIndex`](crate::search::SearchIndex) to rank the links.
+    pub fn text(&self) -> String {
+        self.terms
+            .iter()
+            .filter_map(|term| match &term.condition {
+                Condition::Text(text) if !term.negated => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The query without the words of [`text`](Query::text).
+    ///
+    /// Use it with the search index, so that the words are only matched by the index.
+    pub fn without_text(&self) -> Self {
+        Self {
+            terms: self
+                .terms
+                .iter()
+                .filter(|term| term.negated || !matches!(term.condition, Condition::Text(_)))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Compare the day of the `time` with the `date`
+fn compare_day(comparison: Comparison, time: DateTime<Utc>, date: NaiveDate) -> bool {
+    comparison.compare(time.date_naive(), date)
+}
+
+/// Split the query by spaces, except the spaces inside quotes. The quotes are kept.
+fn split_terms(query: &str) -> Result<Vec<String>, ErrorReporter> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut in_quotes = false;
+
+    for c in query.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+            term.push(c);
+        } else if c.is_whitespace() && !in_quotes {
+            if !term.is_empty() {
+                terms.push(std::mem::take(&mut term));
+            }
+        } else {
+            term.push(c);
+        }
+    }
+
+    if in_quotes {
+        return Err(report_invalid(
+            &format!("unclosed quote in `{term}`"),
+            vec!["A quote (\") is opened but never closed"],
+            vec!["Add a quote at the end of the text, like \"machine learning\""],
+        ));
+    }
+
+    if !term.is_empty() {
+        terms.push(term);
+    }
+
+    Ok(terms)
+}
+
+/// Parse a single term, like `-tag:video`
+fn parse_term(term: &str) -> Result<Term, ErrorReporter> {
+    let (negated, term) = match term.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, term),
+    };
+
+    let condition = match term.split_once(':') {
+        // `"a:b"` and urls like `https://github.com` are text
+        Some((key, value))
+            if !key.starts_with('"')
+                && !value.starts_with("//")
+                && !key.is_empty()
+                && key.chars().all(char::is_alphabetic) =>
+        {
+            parse_condition(&key.to_lowercase(), value)?
+        }
+        _ => Condition::Text(unquote(term).to_string()),
+    };
+
+    Ok(Term { negated, condition })
+}
+
+/// Parse the `value` of the `key`
+fn parse_condition(key: &str, value: &str) -> Result<Condition, ErrorReporter> {
+    let (comparison, value) = Comparison::split(value);
+    let value = unquote(value);
+
+    if value.is_empty() {
+        return Err(report_invalid(
+            &format!("no value for `{key}:`"),
+            vec!["A key must be followed by a value"],
+            vec![&format!(
+                "Write a value after the key, like {}",
+                example(key)
+            )],
+        ));
+    }
+
+    if comparison != Comparison::Equal && !["priority", "added", "updated", "opened"].contains(&key)
+    {
+        return Err(report_invalid(
+            &format!("`{key}:` can't be compared with `<` or `>`"),
+            vec!["Only `priority`, `added`, `updated` and `opened` can be compared"],
+            vec![&format!("Remove the comparison, like {}", example(key))],
+        ));
+    }
+
+    let condition = match key {
+        "tag" => Condition::Tag(value.to_string()),
+        "priority" => {
+            let mut chars = value.chars();
+
+            match (chars.next(), chars.next()) {
+                (Some(priority), None) if priority.is_ascii_alphabetic() => {
+                    Condition::Priority(comparison, priority.to_ascii_uppercase())
+                }
+                _ => {
+                    return Err(report_invalid(
+                        &format!("invalid priority `{value}`"),
+                        vec!["A priority is a single letter from A to Z"],
+                        vec![&format!("Use a letter, like {}", example(key))],
+                    ))
+                }
+            }
+        }
+        "browser" => match value.to_lowercase().as_str() {
+            "firefox" => Condition::Browser(Browser::Firefox),
+            "chrome" => Condition::Browser(Browser::Chrome),
+            "brave" => Condition::Browser(Browser::Brave),
+            "default" | "sysdefault" => Condition::Browser(Browser::SysDefault),
+            _ => {
+                return Err(report_invalid(
+                    &format!("unknown browser `{value}`"),
+                    vec!["The browser is not supported by the app"],
+                    vec!["Use one of the browsers: firefox, chrome, brave, default"],
+                ))
+            }
+        },
+        "domain" => Condition::Domain(value.to_lowercase().trim_start_matches("www.").to_string()),
+        "is" => match value.to_lowercase().as_str() {
+            "complete" => Condition::Is(LinkState::Complete),
+            "incomplete" => Condition::Is(LinkState::Incomplete),
+            "opened" => Condition::Is(LinkState::Opened),
+            "unopened" => Condition::Is(LinkState::Unopened),
+            "broken" => Condition::Is(LinkState::Broken),
+            _ => {
+                return Err(report_invalid(
+                    &format!("unknown state `{value}`"),
+                    vec!["`is:` only knows a few states of a link"],
+                    vec!["Use one of the states: complete, incomplete, opened, unopened, broken"],
+                ))
+            }
+        },
+        "added" | "updated" | "opened" => {
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|err| {
+                report_invalid(
+                    &format!("invalid date `{value}`: {err}"),
+                    vec!["A date must be written as year-month-day"],
+                    vec![&format!("Write the date like {}", example(key))],
+                )
+            })?;
+
+            match key {
+                "added" => Condition::Added(comparison, date),
+                "updated" => Condition::Updated(comparison, date),
+                _ => Condition::Opened(comparison, date),
+            }
+        }
+        _ => {
+            return Err(report_invalid(
+                &format!("unknown key `{key}:`"),
+                vec![&format!("`{key}` is not a field of the links")],
+                vec![
+                    &format!("Use one of the keys: {}", QUERY_KEYS.join(", ")),
+                    &format!(
+                        "Put the term inside quotes to search it as text, like \"{key}:{value}\""
+                    ),
+                ],
+            ))
+        }
+    };
+
+    Ok(condition)
+}
+
+/// Remove the quotes around the `value`
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// An example of the `key`, used in the errors
+fn example(key: &str) -> &'static str {
+    match key {
+        "tag" => "tag:rust",
+        "priority" => "priority:<=C",
+        "browser" => "browser:firefox",
+        "domain" => "domain:github.com",
+        "is" => "is:incomplete",
+        "added" => "added:>2026-01-01",
+        "updated" => "updated:2026-01-01",
+        _ => "opened:<2026-01-01",
+    }
+}
+
+/// Create a report for an invalid query
+fn report_invalid(
+    actual_error: &str,
+    why_error: Vec<&str>,
+    how_to_fix: Vec<&str>,
+) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error,
+        why_error,
+        how_to_fix,
+        error_title: "Invalid Search Query",
+        when_error: "parsing the search query",
+        error_type: ErrorType::InvalidQuery,
+    }
+    .build()
+}
@@ -0,0 +1,85 @@
+//! Refreshing the saved links.
+//!
+//! The title, description and metadata of a link are fetched from its webpage only when the link is saved. The backend can fetch them again and return a [`LinkRefresh`], so the user can see what is changed before applying it.
+
+use crate::metadata::PageMetadata;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The changes of a link's webpage since the link was saved
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::refresh::LinkRefresh;
+///
+/// let saved = Link::new("https://example.com")
+///     .title("Old title")
+///     .description("Same description");
+/// let fetched = Link::new("https://example.com")
+///     .title("New title")
+///     .description("Same description");
+///
+/// let refresh = LinkRefresh::new(saved.clone(), fetched);
+/// assert_eq!(refresh.title.as_deref(), Some("New title"));
+/// assert_eq!(refresh.description, None);
+///
+/// let refreshed = refresh.apply(saved);
+/// assert_eq!(refreshed.title.as_deref(), Some("New title"));
+/// assert_eq!(refreshed.description.as_deref(), Some("Same description"));
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct LinkRefresh {
+    /// The saved link
+    pub link: Link,
+    /// The new title of the webpage. It is `None` if it is not changed, the webpage doesn't have a title, or the user has written the title (see [`Link::custom_title`]).
+    pub title: Option<String>,
+    /// The new description of the webpage. It is `None` if it is not changed or the webpage doesn't have a description.
+    pub description: Option<String>,
+    /// The new metadata of the webpage, including the thumbnail
+    pub metadata: PageMetadata,
+}
+
+impl LinkRefresh {
+    /// Compare the saved `link` with the `fetched` link, which is validated again.
+    pub fn new(link: Link, fetched: Link) -> Self {
+        let changed = |old: &Option<String>, new: Option<String>| {
+            new.filter(|new| !new.trim().is_empty() && old.as_ref() != Some(new))
+        };
+
+        Self {
+            title: if link.custom_title {
+                None
+            } else {
+                changed(&link.title, fetched.title)
+            },
+            description: changed(&link.description, fetched.description),
+            metadata: fetched.metadata,
+            link,
+        }
+    }
+
+    /// Whether the thumbnail of the webpage is changed
+    pub fn image_changed(&self) -> bool {
+        self.metadata.image != self.link.metadata.image
+    }
+
+    /// Whether nothing is changed
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.description.is_none() && self.metadata == self.link.metadata
+    }
+
+    /// Put the changes into the `link`. It can be newer than the saved link of the refresh, so the other edits are kept.
+    pub fn apply(&self, mut link: Link) -> Link {
+        if let Some(title) = &self.title {
+            link.title = Some(title.clone());
+        }
+        if let Some(description) = &self.description {
+            link.description = Some(description.clone());
+        }
+        link.metadata = self.metadata.clone();
+
+        link
+    }
+}
@@ -0,0 +1,88 @@
+use crate::prelude::*;
+use crate::query::Query;
+use crate::search::{SearchHit, SearchIndex};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A named search saved by the user, like "Unread Rust videos".
+///
+/// It is shown as a *smart collection* in the sidebar. It combines a [`Query`] (what the user typed in the search bar) and a [`LinkFilter`] (the tags and browsers selected in the sidebar).
+///
+/// It is stored with the links, see [`Storage::saved_searches`](crate::storage::Storage::saved_searches).
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::search::SearchIndex;
+///
+/// let search = SavedSearch::new("Unread Rust videos", "is:unopened tag:video")
+///     .filter(LinkFilter::default().tags(vec!["Rust"]));
+///
+/// let links = vec![
+///     Link::new("https://www.youtube.com/watch?v=ygL_xcavzQ4").tags("Rust Video"),
+///     Link::new("https://www.youtube.com/watch?v=rQ_J9WH6CGk").tags("Python Video"),
+///     Link::new("https://www.rust-lang.org").tags("Rust"),
+/// ];
+///
+/// let index = SearchIndex::new(&links);
+/// assert_eq!(search.count(&links, &index), 1);
+///
+/// // the words can have typos, like in the search bar
+/// let search = SavedSearch::new("Rust", "rsut");
+/// assert_eq!(search.count(&links, &index), 2);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedSearch {
+    /// Unique id of the search
+    pub id: Uuid,
+    /// Name of the search, shown in the sidebar
+    pub name: String,
+    /// The [`Query`] typed by the user. It can be empty.
+    pub query: String,
+    /// The tags and browsers selected by the user
+    pub filter: LinkFilter,
+}
+
+impl SavedSearch {
+    /// Create a new search with a new `id` and without any filter.
+    pub fn new<N: ToString, Q: ToString>(name: N, query: Q) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            query: query.to_string(),
+            filter: LinkFilter::default(),
+        }
+    }
+
+    /// change the `filter` of the search
+    pub fn filter(mut self, filter: LinkFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Parse the `query` of the search.
+    ///
+    /// It will return an error with [`ErrorType::InvalidQuery`] if the query is not valid. Check it before saving the search.
+    pub fn parse_query(&self) -> Result<Query, ErrorReporter> {
+        Query::parse(&self.query)
+    }
+
+    /// Search the `links` which match both the query and the filter, the best match first.
+    ///
+    /// The words of the query are searched in the `index` of the links (see [`SearchIndex::search_query`]), so the same links are found as in the search bar. If the query is not valid, then no link matches.
+    pub fn search(&self, links: &[Link], index: &SearchIndex) -> Vec<SearchHit> {
+        match self.parse_query() {
+            Ok(query) => index.search_query(
+                &query,
+                links.iter().filter(|link| self.filter.matches(link)),
+            ),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Count the `links` which match the search, see [`search`](SavedSearch::search).
+    pub fn count(&self, links: &[Link], index: &SearchIndex) -> usize {
+        self.search(links, index).len()
+    }
+}
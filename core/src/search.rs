@@ -0,0 +1,415 @@
+//! Full-text search over the [`Link`]s.
+//!
+//! The [`SearchIndex`] keeps an inverted index of the words of every link, so a search doesn't need to read all the links.
+
+use crate::prelude::*;
+use crate::query::Query;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
+use uuid::Uuid;
+
+/// A field of a [`Link`] which is searched.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SearchField {
+    /// The `title` of the link
+    Title,
+    /// The `url` of the link
+    Url,
+    /// The `description` of the link
+    Description,
+    /// The tag at the given index of the link's `tags`
+    Tag(usize),
+}
+
+impl SearchField {
+    /// How important a match inside this field is.
+    fn weight(&self) -> f32 {
+        match self {
+            Self::Title => 4.0,
+            Self::Tag(_) => 3.0,
+            Self::Url => 2.0,
+            Self::Description => 1.0,
+        }
+    }
+}
+
+/// A matched part of a field. Show it highlighted to the user.
+///
+/// `start` and `end` are byte indexes of the field's text, so `&text[start..end]` is the matched word.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Highlight {
+    /// The field where the word is
+    pub field: SearchField,
+    /// Byte index where the word starts
+    pub start: usize,
+    /// Byte index where the word ends
+    pub end: usize,
+}
+
+/// A link which matches the search.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchHit {
+    /// `id` of the matched link
+    pub id: Uuid,
+    /// How well the link matches. A bigger score is a better match.
+    pub score: f32,
+    /// All matched words of the link
+    pub highlights: Vec<Highlight>,
+}
+
+/// An occurrence of a word inside a link.
+#[derive(Debug, Clone, Copy)]
+struct Posting {
+    id: Uuid,
+    field: SearchField,
+    start: usize,
+    end: usize,
+}
+
+/// An in-memory inverted index of the [`Link`]s.
+///
+/// Every word of the `title`, `url`, `description` and `tags` of a link is indexed. Build it once with [`new`](SearchIndex::new) and then keep it up to date with [`insert`](SearchIndex::insert) and [`remove`](SearchIndex::remove) whenever a link changes.
+///
+/// A link matches a search if every word of the query matches a word of the link. A word matches if it is the same, if it starts with the query's word, or if it has a few typos (1 typo for words of 4 letters, 2 typos for words of 8 letters). The first letter can't be a typo, so only the words with the same first letter are compared.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::search::{SearchField, SearchIndex};
+///
+/// let rust = Link::new("https://www.rust-lang.org").title("Rust Programming Language");
+/// let python = Link::new("https://www.python.org").title("Welcome to Python.org");
+///
+/// let index = SearchIndex::new(&[rust.clone(), python]);
+///
+/// // "progamming" has a typo
+/// let hits = index.search("rust progamming");
+///
+/// assert_eq!(hits.len(), 1);
+/// assert_eq!(hits[0].id, rust.id.unwrap());
+///
+/// // the word "Rust" of the title is matched
+/// let highlight = hits[0].highlights.iter().find(|h| h.field == SearchField::Title).unwrap();
+/// assert_eq!(&rust.title.unwrap()[highlight.start..highlight.end], "Rust");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// Every word and where it occurs, sorted so the words with the same beginning can be found
+    words: BTreeMap<String, Vec<Posting>>,
+    /// The words of every link, so a link can be removed without searching all the words
+    links: HashMap<Uuid, Vec<String>>,
+}
+
+impl SearchIndex {
+    /// Build the index of the given `links`.
+    ///
+    /// Links without an `id` are not indexed.
+    pub fn new(links: &[Link]) -> Self {
+        let mut index = Self::default();
+
+        for link in links {
+            index.insert(link);
+        }
+
+        index
+    }
+
+    /// How many links are indexed.
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Whether no link is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Add the `link` into the index. If the link is already indexed, then it will be indexed again with its new content.
+    ///
+    /// Links without an `id` are not indexed.
+    pub fn insert(&mut self, link: &Link) {
+        let id = match link.id {
+            Some(id) => id,
+            None => return,
+        };
+
+        self.remove(id);
+
+        let mut fields = vec![(SearchField::Url, link.url.as_str())];
+
+        if let Some(title) = &link.title {
+            fields.push((SearchField::Title, title));
+        }
+        if let Some(description) = &link.description {
+            fields.push((SearchField::Description, description));
+        }
+        for (i, tag) in link.tags.iter().enumerate() {
+            fields.push((SearchField::Tag(i), tag));
+        }
+
+        let mut link_words = Vec::new();
+
+        for (field, text) in fields {
+            for (word, start, end) in tokenize(text) {
+                let posting = Posting {
+                    id,
+                    field,
+                    start,
+                    end,
+                };
+
+                self.words.entry(word.clone()).or_default().push(posting);
+                link_words.push(word);
+            }
+        }
+
+        link_words.sort();
+        link_words.dedup();
+
+        self.links.insert(id, link_words);
+    }
+
+    /// Remove the link `id` from the index. Nothing happens if the link is not indexed.
+    pub fn remove(&mut self, id: Uuid) {
+        let link_words = match self.links.remove(&id) {
+            Some(link_words) => link_words,
+            None => return,
+        };
+
+        for word in link_words {
+            if let Some(postings) = self.words.get_mut(&word) {
+                postings.retain(|posting| posting.id != id);
+
+                if postings.is_empty() {
+                    self.words.remove(&word);
+                }
+            }
+        }
+    }
+
+    /// Search the `links` with a parsed [`Query`]: the terms with a key are checked with [`Query::matches`], and the words without a key are searched in this index.
+    ///
+    /// Use it everywhere a query is searched, so the same links are found. If the query doesn't have any word, then every link which matches the terms is returned in its order with a score of `0`.
+    pub fn search_query<'a, I>(&self, query: &Query, links: I) -> Vec<SearchHit>
+    where
+        I: IntoIterator<Item = &'a Link>,
+    {
+        let conditions = query.without_text();
+        let matched = links
+            .into_iter()
+            .filter(|link| conditions.matches(link))
+            .filter_map(|link| link.id);
+
+        let text = query.text();
+
+        if text.is_empty() {
+            return matched
+                .map(|id| SearchHit {
+                    id,
+                    score: 0.0,
+                    highlights: Vec::new(),
+                })
+                .collect();
+        }
+
+        let matched: HashSet<Uuid> = matched.collect();
+
+        self.search(&text)
+            .into_iter()
+            .filter(|hit| matched.contains(&hit.id))
+            .collect()
+    }
+
+    /// Search the links which match every word of the `query`.
+    ///
+    /// The best match will be first. It returns an empty list if the query doesn't have any word.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms: Vec<String> = tokenize(query).into_iter().map(|(word, ..)| word).collect();
+
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut hits: HashMap<Uuid, SearchHit> = HashMap::new();
+
+        for (i, term) in terms.iter().enumerate() {
+            // the best score of every field of every link for this term
+            let mut scores: HashMap<(Uuid, SearchField), f32> = HashMap::new();
+            let mut highlights: HashMap<Uuid, Vec<Highlight>> = HashMap::new();
+
+            for (word, postings) in self.candidates(term) {
+                let quality = match match_quality(term, word) {
+                    Some(quality) => quality,
+                    None => continue,
+                };
+
+                for posting in postings {
+                    // after the first term, only the links which matched all previous terms are kept
+                    if i > 0 && !hits.contains_key(&posting.id) {
+                        continue;
+                    }
+
+                    let score = scores.entry((posting.id, posting.field)).or_default();
+                    *score = score.max(quality * posting.field.weight());
+
+                    highlights.entry(posting.id).or_default().push(Highlight {
+                        field: posting.field,
+                        start: posting.start,
+                        end: posting.end,
+                    });
+                }
+            }
+
+            let mut term_scores: HashMap<Uuid, f32> = HashMap::new();
+            for ((id, _), score) in scores {
+                *term_scores.entry(id).or_default() += score;
+            }
+
+            if i == 0 {
+                for (id, score) in term_scores {
+                    hits.insert(
+                        id,
+                        SearchHit {
+                            id,
+                            score,
+                            highlights: highlights.remove(&id).unwrap_or_default(),
+                        },
+                    );
+                }
+            } else {
+                hits.retain(|id, _| term_scores.contains_key(id));
+
+                for (id, hit) in hits.iter_mut() {
+                    hit.score += term_scores[id];
+                    hit.highlights
+                        .extend(highlights.remove(id).unwrap_or_default());
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = hits
+            .into_values()
+            .map(|mut hit| {
+                hit.highlights
+                    .sort_by_key(|highlight| (field_order(highlight.field), highlight.start));
+                hit.highlights.dedup();
+                hit
+            })
+            .collect();
+
+        // the best score first. Links with the same score are kept in a stable order
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.id.cmp(&b.id)));
+
+        hits
+    }
+
+    /// The words which may match the `term`: the words which start with the `term`, or with its first letter if the `term` is long enough to have typos.
+    fn candidates<'a>(
+        &'a self,
+        term: &'a str,
+    ) -> impl Iterator<Item = (&'a String, &'a Vec<Posting>)> + 'a {
+        let prefix = match term.chars().next() {
+            Some(first) if term.chars().count() >= MIN_TYPO_LENGTH => &term[..first.len_utf8()],
+            _ => term,
+        };
+
+        self.words
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |(word, _)| word.starts_with(prefix))
+    }
+}
+
+/// Split the `text` into lowercase words. It returns the words with their byte indexes inside the `text`.
+fn tokenize(text: &str) -> Vec<(String, usize, usize)> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            start.get_or_insert(i);
+        } else if let Some(s) = start.take() {
+            words.push((text[s..i].to_lowercase(), s, i));
+        }
+    }
+
+    if let Some(s) = start {
+        words.push((text[s..].to_lowercase(), s, text.len()));
+    }
+
+    words
+}
+
+/// Words shorter than this must not have typos
+const MIN_TYPO_LENGTH: usize = 4;
+
+/// How well the `word` of a link matches the `term` of the query. `None` means it doesn't match.
+fn match_quality(term: &str, word: &str) -> Option<f32> {
+    if word == term {
+        return Some(1.0);
+    }
+    if word.starts_with(term) {
+        return Some(0.8);
+    }
+
+    let term_len = term.chars().count();
+    if term_len < MIN_TYPO_LENGTH {
+        return None;
+    }
+    let max_typos = if term_len < 8 { 1 } else { 2 };
+
+    if term_len.abs_diff(word.chars().count()) > max_typos {
+        return None;
+    }
+
+    match typos(term, word) {
+        1 => Some(0.6),
+        2 if max_typos == 2 => Some(0.4),
+        _ => None,
+    }
+}
+
+/// Count the typos between two words: inserted, removed, replaced or swapped letters.
+fn typos(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances[i][j] is the number of typos between `a[..i]` and `b[..j]`
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// Order of the fields inside the highlights of a [`SearchHit`]
+fn field_order(field: SearchField) -> (usize, usize) {
+    match field {
+        SearchField::Title => (0, 0),
+        SearchField::Url => (1, 0),
+        SearchField::Description => (2, 0),
+        SearchField::Tag(i) => (3, i),
+    }
+}
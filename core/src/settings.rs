@@ -0,0 +1,129 @@
+//! Settings of the application chosen by the user.
+
+use crate::prelude::*;
+use crate::storage::{report_corrupted, report_io, root_dir, write_atomic, StorageBackend};
+use crate::storage::{MAX_TRASH_RETENTION_DAYS, TRASH_RETENTION_DAYS};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Name of the file where the settings are stored (inside [`ROOT_DIR`](crate::storage::ROOT_DIR)).
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Get the path of the file where the settings are stored.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # use dsaver_core::settings::settings_file;
+/// #
+/// assert_eq!(settings_file("/home/user"), Path::new("/home/user/.DSaver/settings.json"));
+/// ```
+pub fn settings_file<P: AsRef<Path>>(home_dir: P) -> PathBuf {
+    root_dir(home_dir).join(SETTINGS_FILE)
+}
+
+/// Settings of the application.
+///
+/// Every field has a default value. So a missing field (e.g. from an older version of the app) will get its default value.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::settings::Settings;
+/// use dsaver_core::storage::StorageBackend;
+///
+/// let path = std::env::temp_dir().join(format!("dsaver-{}.json", uuid::Uuid::new_v4()));
+///
+/// // The file doesn't exist yet, so the settings are default
+/// let mut settings = Settings::load(&path).unwrap();
+/// assert_eq!(settings, Settings::default());
+///
+/// settings.storage = StorageBackend::Sqlite;
+/// settings.save(&path).unwrap();
+///
+/// assert_eq!(Settings::load(&path).unwrap().storage, StorageBackend::Sqlite);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Settings {
+    /// Where the links are stored
+    pub storage: StorageBackend,
+    /// How the links are sorted
+    pub sort_order: SortOrder,
+    /// Hide the complete links from the list. They are only shown in the archive.
+    pub archive_completed: bool,
+    /// Mark a link as complete when it is opened in the browser
+    pub complete_on_open: bool,
+    /// How many days the deleted links stay in the trash. If it is `0`, they stay until the user deletes them.
+    ///
+    /// It is at most [`MAX_TRASH_RETENTION_DAYS`](crate::storage::MAX_TRASH_RETENTION_DAYS), a larger value is lowered when the settings are loaded or saved.
+    pub trash_retention_days: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            storage: StorageBackend::default(),
+            sort_order: SortOrder::default(),
+            archive_completed: false,
+            complete_on_open: false,
+            trash_retention_days: TRASH_RETENTION_DAYS,
+        }
+    }
+}
+
+impl Settings {
+    /// Read the settings from the file of the given `path`.
+    ///
+    /// If the file doesn't exist, then it will return the default settings.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ErrorReporter> {
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data)
+                .map(Self::validated)
+                .map_err(|err| report_corrupted(err, path)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(report_io(err, "reading the file", path)),
+        }
+    }
+
+    /// Lower the values which are out of their bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dsaver_core::settings::Settings;
+    /// use dsaver_core::storage::MAX_TRASH_RETENTION_DAYS;
+    ///
+    /// let settings = Settings {
+    ///     trash_retention_days: 100_000_000,
+    ///     ..Settings::default()
+    /// };
+    ///
+    /// assert_eq!(settings.validated().trash_retention_days, MAX_TRASH_RETENTION_DAYS);
+    /// ```
+    pub fn validated(mut self) -> Self {
+        self.trash_retention_days = self.trash_retention_days.min(MAX_TRASH_RETENTION_DAYS);
+        self
+    }
+
+    /// Write the settings into the file of the given `path`. The values are validated first, see [`Settings::validated`].
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ErrorReporter> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| report_io(err, "creating the directory", parent))?;
+        }
+
+        // Serializing `Settings` never fails
+        let data = serde_json::to_string_pretty(&self.clone().validated()).unwrap();
+
+        write_atomic(path, data).map_err(|err| report_io(err, "writing the file", path))
+    }
+}
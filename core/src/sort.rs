@@ -0,0 +1,100 @@
+//! Orders in which the [`Link`]s can be shown.
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::fmt::{self, Display};
+
+/// How the links are sorted. The user chooses it and it is stored in the [`Settings`](crate::settings::Settings).
+///
+/// The sort is stable, so the links which are equal keep the order they were added.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::sort::SortOrder;
+///
+/// let mut links = vec![
+///     Link::new("https://www.rust-lang.org").title("Rust"),
+///     Link::new("https://www.python.org").title("Python"),
+/// ];
+///
+/// SortOrder::Title.sort(&mut links);
+///
+/// assert_eq!(links[0].title, Some(String::from("Python")));
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    /// The order chosen by the user, see [`Link::manual_order`]. The links which are never moved are shown in the order they were added.
+    ///
+    /// This is the default value of this enum.
+    #[default]
+    Manual,
+    /// By `title` from A to Z, ignoring the case. The `url` is used if the link doesn't have a title.
+    Title,
+    /// By [`domain`](Link::domain) from A to Z. The links without a domain are last.
+    Domain,
+    /// The newest link first
+    Created,
+    /// The link which was opened last time first. The links which are never opened are last.
+    LastOpened,
+    /// The most opened link first
+    OpenCount,
+}
+
+impl SortOrder {
+    /// All the sort orders, in the order they are shown to the user
+    pub fn get_vec() -> Vec<Self> {
+        vec![
+            Self::Manual,
+            Self::Title,
+            Self::Domain,
+            Self::Created,
+            Self::LastOpened,
+            Self::OpenCount,
+        ]
+    }
+
+    /// Sort the `links` in this order.
+    ///
+    /// The links can be borrowed (e.g. `Vec<&Link>`), so that a large list can be sorted without cloning every link.
+    pub fn sort<L: Borrow<Link>>(&self, links: &mut [L]) {
+        match self {
+            Self::Manual => links.sort_by_key(|link| match link.borrow().manual_order {
+                Some(order) => (0, order),
+                None => (1, 0),
+            }),
+            Self::Title => links.sort_by_cached_key(|link| {
+                let link = link.borrow();
+                link.title.as_ref().unwrap_or(&link.url).to_lowercase()
+            }),
+            Self::Domain => links.sort_by_cached_key(|link| match link.borrow().domain() {
+                Some(domain) => (0, domain),
+                None => (1, String::new()),
+            }),
+            Self::Created => links.sort_by_key(|link| Reverse(link.borrow().created_at)),
+            Self::LastOpened => links.sort_by_key(|link| {
+                let last_opened_at = link.borrow().last_opened_at;
+                (last_opened_at.is_none(), Reverse(last_opened_at))
+            }),
+            Self::OpenCount => links.sort_by_key(|link| Reverse(link.borrow().open_count)),
+        }
+    }
+}
+
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Manual => "Manual",
+            Self::Title => "Title",
+            Self::Domain => "Domain",
+            Self::Created => "Created",
+            Self::LastOpened => "Last opened",
+            Self::OpenCount => "Most opened",
+        };
+
+        write!(f, "{name}")
+    }
+}
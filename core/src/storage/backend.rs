@@ -0,0 +1,506 @@
+use super::{links_db, links_file, Backup, LinkStore, LinksFile, TrashedLink};
+use crate::check::LinkCheck;
+use crate::prelude::*;
+use crate::tag::TagInfo;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::path::Path;
+use uuid::Uuid;
+
+/// A place where the user's [`Link`]s are stored.
+///
+/// Every change is written immediately, so the caller never needs to save anything.
+///
+/// Use [`StorageBackend::open`] to open the storage chosen by the user.
+pub trait Storage: Send {
+    /// All the links in the order they were added.
+    fn links(&self) -> Result<Vec<Link>, ErrorReporter>;
+
+    /// Get a link by its `id`.
+    fn get(&self, id: Uuid) -> Result<Option<Link>, ErrorReporter>;
+
+    /// Add a new link. If the link's `id` is `None`, then a new id will be created. The tags are normalized with [`Link::normalize_tags`].
+    ///
+    /// It returns the link that was added. It will return an error with [`ErrorType::DuplicateLink`] if a link with the same `id` already exists.
+    fn insert(&mut self, link: Link) -> Result<Link, ErrorReporter>;
+
+    /// Replace the link which has the same `id` as the given `link`. The tags are normalized with [`Link::normalize_tags`].
+    ///
+    /// It returns the updated link. It will return an error with [`ErrorType::LinkNotFound`] if no link has the same `id`.
+    fn update(&mut self, link: Link) -> Result<Link, ErrorReporter>;
+
+    /// Remove the link with the given `id`.
+    ///
+    /// It returns the removed link. It will return an error with [`ErrorType::LinkNotFound`] if no link has the `id`.
+    fn delete(&mut self, id: Uuid) -> Result<Link, ErrorReporter>;
+
+    /// Remove all links and store the given `links` instead.
+    fn replace_all(&mut self, links: Vec<Link>) -> Result<(), ErrorReporter>;
+
+    /// Set the [`manual_order`](Link::manual_order) of the links, so they are shown in the order of `ids` with [`SortOrder::Manual`].
+    ///
+    /// The links which are not in `ids` will be shown after them. It returns all the links.
+    ///
+    /// The default implementation [`update`](Storage::update)s every changed link. Storages which can do better should override it.
+    fn reorder(&mut self, ids: &[Uuid]) -> Result<Vec<Link>, ErrorReporter> {
+        let orders = manual_orders(ids);
+
+        for mut link in self.links()? {
+            let order = link.id.and_then(|id| orders.get(&id).copied());
+
+            if link.manual_order != order {
+                link.manual_order = order;
+                self.update(link)?;
+            }
+        }
+
+        self.links()
+    }
+
+    /// Save the result of checking the links (see [`Link::check`]), by the `id` of every link.
+    ///
+    /// A check is skipped if the link is deleted or its url is changed after it was checked, or if it is inconclusive and the link already has a check (see [`Link::set_check`]). It returns the updated links.
+    ///
+    /// Checking is not a change of the user, so storages with backups should not back up the links for it.
+    ///
+    /// The default implementation [`update`](Storage::update)s every link. Storages which can do better should override it.
+    fn save_checks(&mut self, checks: Vec<(Uuid, LinkCheck)>) -> Result<Vec<Link>, ErrorReporter> {
+        let mut updated = Vec::new();
+
+        for (id, check) in checks {
+            if let Some(mut link) = self.get(id)? {
+                if link.set_check(check) {
+                    updated.push(self.update(link)?);
+                }
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// Replace the links which have the same `id` as the given `links`, and replace the infos `infos_before` of the tags with `infos_after`. Use it to change the tags of many links at once.
+    ///
+    /// The links which don't exist anymore are skipped. The tags are normalized with [`Link::normalize_tags`]. It returns the updated links.
+    ///
+    /// The default implementation [`update`](Storage::update)s every link and saves every info. Storages which can do better (e.g. in a single write) should override it.
+    fn update_tags(
+        &mut self,
+        links: Vec<Link>,
+        infos_before: &[TagInfo],
+        infos_after: &[TagInfo],
+    ) -> Result<Vec<Link>, ErrorReporter> {
+        let mut updated = Vec::new();
+
+        for link in links {
+            if link
+                .id
+                .map(|id| self.get(id))
+                .transpose()?
+                .flatten()
+                .is_some()
+            {
+                updated.push(self.update(link)?);
+            }
+        }
+
+        let saved = self.tag_infos()?;
+        for info in infos_before {
+            if saved.iter().any(|saved| saved.tag == info.tag) {
+                self.delete_tag_info(&info.tag)?;
+            }
+        }
+        for info in infos_after {
+            self.save_tag_info(info.clone())?;
+        }
+
+        Ok(updated)
+    }
+
+    /// Move the link with the given `id` into the trash.
+    ///
+    /// It returns the trashed link. It will return an error with [`ErrorType::LinkNotFound`] if no link has the `id`.
+    fn trash(&mut self, id: Uuid) -> Result<TrashedLink, ErrorReporter>;
+
+    /// All the links inside the trash. The last deleted link will be first.
+    fn trashed(&self) -> Result<Vec<TrashedLink>, ErrorReporter>;
+
+    /// Move the link with the given `id` out of the trash. It is added after all the other links.
+    ///
+    /// It returns the restored link. It will return an error with [`ErrorType::LinkNotFound`] if the trash doesn't have the link and [`ErrorType::DuplicateLink`] if a link with the same `id` is already saved.
+    fn restore(&mut self, id: Uuid) -> Result<Link, ErrorReporter>;
+
+    /// Delete forever the link with the given `id` from the trash.
+    ///
+    /// It returns the removed link. It will return an error with [`ErrorType::LinkNotFound`] if the trash doesn't have the link.
+    fn purge(&mut self, id: Uuid) -> Result<TrashedLink, ErrorReporter>;
+
+    /// Remove all links from the trash and put the given `trash` instead.
+    fn replace_trash(&mut self, trash: Vec<TrashedLink>) -> Result<(), ErrorReporter>;
+
+    /// Delete forever the links which were moved into the trash before the given time.
+    ///
+    /// It returns the removed links.
+    fn purge_before(&mut self, before: DateTime<Utc>) -> Result<Vec<TrashedLink>, ErrorReporter> {
+        let (purged, kept): (Vec<_>, Vec<_>) = self
+            .trashed()?
+            .into_iter()
+            .partition(|trashed| trashed.deleted_at < before);
+
+        if !purged.is_empty() {
+            self.replace_trash(kept)?;
+        }
+
+        Ok(purged)
+    }
+
+    /// All the searches saved by the user, in the order they were added.
+    fn saved_searches(&self) -> Result<Vec<SavedSearch>, ErrorReporter>;
+
+    /// Add the `search`, or replace the saved search which has the same `id`.
+    ///
+    /// It returns the saved search. The query of the search is not checked, so check it with [`SavedSearch::parse_query`] first.
+    fn save_search(&mut self, search: SavedSearch) -> Result<SavedSearch, ErrorReporter>;
+
+    /// Remove the saved search with the given `id`.
+    ///
+    /// It returns the removed search. It will return an error with [`ErrorType::SavedSearchNotFound`] if no saved search has the `id`.
+    fn delete_search(&mut self, id: Uuid) -> Result<SavedSearch, ErrorReporter>;
+
+    /// The colors, icons and descriptions of the tags, sorted by the tag.
+    fn tag_infos(&self) -> Result<Vec<TagInfo>, ErrorReporter>;
+
+    /// Add the `info`, or replace the info of the same tag.
+    ///
+    /// It returns the saved info. The info is not checked, so check it with [`TagInfo::validate`] first.
+    fn save_tag_info(&mut self, info: TagInfo) -> Result<TagInfo, ErrorReporter>;
+
+    /// Remove the info of the `tag`.
+    ///
+    /// It returns the removed info. It will return an error with [`ErrorType::TagNotFound`] if the tag has no info.
+    fn delete_tag_info(&mut self, tag: &str) -> Result<TagInfo, ErrorReporter>;
+
+    /// Replace the links, the trash, the saved searches and the tag infos with the ones of the `file`.
+    ///
+    /// The default implementation replaces them one by one. Storages should override it to write everything at once.
+    fn replace_file(&mut self, file: LinksFile) -> Result<(), ErrorReporter> {
+        self.replace_all(file.links)?;
+        self.replace_trash(file.trash)?;
+
+        for search in self.saved_searches()? {
+            self.delete_search(search.id)?;
+        }
+        for search in file.saved_searches {
+            self.save_search(search)?;
+        }
+
+        for info in self.tag_infos()? {
+            self.delete_tag_info(&info.tag)?;
+        }
+        for info in file.tag_infos {
+            self.save_tag_info(info)?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the links which match the `filter`, in the order they were added.
+    ///
+    /// The default implementation filters all the [`links`](Storage::links). Storages which can do better (e.g. with an index) should override it.
+    fn query(&self, filter: &LinkFilter) -> Result<Vec<Link>, ErrorReporter> {
+        Ok(self
+            .links()?
+            .into_iter()
+            .filter(|link| filter.matches(link))
+            .collect())
+    }
+
+    /// All backups of the storage. The newest backup will be first.
+    ///
+    /// Storages without backups return an empty list.
+    fn backups(&self) -> Result<Vec<Backup>, ErrorReporter> {
+        Ok(Vec::new())
+    }
+
+    /// Replace all links with the links of the backup `name`.
+    fn restore_backup(&mut self, name: &str) -> Result<(), ErrorReporter> {
+        Err(ErrorReporterBuilder {
+            actual_error: "None",
+            why_error: vec!["This storage doesn't keep backups"],
+            how_to_fix: vec!["Switch to the JSON storage in the settings to use backups"],
+            error_title: "Backup Not Found",
+            when_error: &format!("restoring the backup {name}"),
+            error_type: ErrorType::FileNotFound,
+        }
+        .build())
+    }
+}
+
+impl Storage for LinkStore {
+    fn links(&self) -> Result<Vec<Link>, ErrorReporter> {
+        Ok(LinkStore::links(self).clone())
+    }
+
+    fn get(&self, id: Uuid) -> Result<Option<Link>, ErrorReporter> {
+        Ok(LinkStore::get(self, id).cloned())
+    }
+
+    fn insert(&mut self, link: Link) -> Result<Link, ErrorReporter> {
+        self.write_change(true, |store| LinkStore::insert(store, link))
+    }
+
+    fn update(&mut self, link: Link) -> Result<Link, ErrorReporter> {
+        self.write_change(true, |store| LinkStore::update(store, link))
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<Link, ErrorReporter> {
+        self.write_change(true, |store| LinkStore::delete(store, id))
+    }
+
+    fn replace_all(&mut self, links: Vec<Link>) -> Result<(), ErrorReporter> {
+        self.write_change(true, |store| {
+            store.set_links(links);
+            Ok(())
+        })
+    }
+
+    fn reorder(&mut self, ids: &[Uuid]) -> Result<Vec<Link>, ErrorReporter> {
+        let orders = manual_orders(ids);
+        let mut links = LinkStore::links(self).clone();
+
+        for link in &mut links {
+            link.manual_order = link.id.and_then(|id| orders.get(&id).copied());
+        }
+
+        self.write_change(true, |store| {
+            store.set_links(links.clone());
+            Ok(links)
+        })
+    }
+
+    fn save_checks(&mut self, checks: Vec<(Uuid, LinkCheck)>) -> Result<Vec<Link>, ErrorReporter> {
+        let checks = checks.into_iter().collect::<HashMap<_, _>>();
+        let mut links = LinkStore::links(self).clone();
+        let mut updated = Vec::new();
+
+        for link in &mut links {
+            if let Some(check) = link.id.and_then(|id| checks.get(&id)) {
+                if link.set_check(check.clone()) {
+                    updated.push(link.clone());
+                }
+            }
+        }
+
+        // checking is not a change of the user, so the backups are kept for the user's changes
+        if !updated.is_empty() {
+            self.write_change(false, |store| {
+                store.set_links(links);
+                Ok(())
+            })?;
+        }
+
+        Ok(updated)
+    }
+
+    fn update_tags(
+        &mut self,
+        links: Vec<Link>,
+        infos_before: &[TagInfo],
+        infos_after: &[TagInfo],
+    ) -> Result<Vec<Link>, ErrorReporter> {
+        let mut changed: HashMap<Uuid, Link> = links
+            .into_iter()
+            .filter_map(|link| Some((link.id?, link)))
+            .collect();
+        let mut links = LinkStore::links(self).clone();
+        let mut updated = Vec::new();
+
+        for link in &mut links {
+            if let Some(new) = link.id.and_then(|id| changed.remove(&id)) {
+                *link = new.normalize_tags();
+                updated.push(link.clone());
+            }
+        }
+
+        self.write_change(true, |store| {
+            store.set_links(links);
+
+            for info in infos_before {
+                // the info may be already deleted
+                let _ = LinkStore::delete_tag_info(store, &info.tag);
+            }
+            for info in infos_after {
+                LinkStore::save_tag_info(store, info.clone());
+            }
+
+            Ok(updated)
+        })
+    }
+
+    fn trash(&mut self, id: Uuid) -> Result<TrashedLink, ErrorReporter> {
+        self.write_change(true, |store| LinkStore::trash(store, id))
+    }
+
+    fn trashed(&self) -> Result<Vec<TrashedLink>, ErrorReporter> {
+        Ok(LinkStore::trashed(self).clone())
+    }
+
+    fn restore(&mut self, id: Uuid) -> Result<Link, ErrorReporter> {
+        self.write_change(true, |store| LinkStore::restore(store, id))
+    }
+
+    fn purge(&mut self, id: Uuid) -> Result<TrashedLink, ErrorReporter> {
+        self.write_change(true, |store| LinkStore::purge(store, id))
+    }
+
+    fn replace_trash(&mut self, trash: Vec<TrashedLink>) -> Result<(), ErrorReporter> {
+        self.write_change(true, |store| {
+            store.set_trash(trash);
+            Ok(())
+        })
+    }
+
+    fn saved_searches(&self) -> Result<Vec<SavedSearch>, ErrorReporter> {
+        Ok(LinkStore::saved_searches(self).clone())
+    }
+
+    fn save_search(&mut self, search: SavedSearch) -> Result<SavedSearch, ErrorReporter> {
+        self.write_change(true, |store| Ok(LinkStore::save_search(store, search)))
+    }
+
+    fn delete_search(&mut self, id: Uuid) -> Result<SavedSearch, ErrorReporter> {
+        self.write_change(true, |store| LinkStore::delete_search(store, id))
+    }
+
+    fn tag_infos(&self) -> Result<Vec<TagInfo>, ErrorReporter> {
+        Ok(LinkStore::tag_infos(self).clone())
+    }
+
+    fn save_tag_info(&mut self, info: TagInfo) -> Result<TagInfo, ErrorReporter> {
+        self.write_change(true, |store| Ok(LinkStore::save_tag_info(store, info)))
+    }
+
+    fn delete_tag_info(&mut self, tag: &str) -> Result<TagInfo, ErrorReporter> {
+        self.write_change(true, |store| LinkStore::delete_tag_info(store, tag))
+    }
+
+    fn replace_file(&mut self, file: LinksFile) -> Result<(), ErrorReporter> {
+        self.write_change(true, |store| {
+            store.set_file(file);
+            Ok(())
+        })
+    }
+
+    fn backups(&self) -> Result<Vec<Backup>, ErrorReporter> {
+        LinkStore::backups(self)
+    }
+
+    fn restore_backup(&mut self, name: &str) -> Result<(), ErrorReporter> {
+        LinkStore::restore_backup(self, name)
+    }
+}
+
+/// The [`manual_order`](Link::manual_order) of every link in `ids`: its position inside `ids`.
+pub(crate) fn manual_orders(ids: &[Uuid]) -> HashMap<Uuid, u32> {
+    ids.iter()
+        .enumerate()
+        .map(|(position, id)| (*id, position as u32))
+        .collect()
+}
+
+/// The kinds of [`Storage`] the user can choose in the settings.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum StorageBackend {
+    /// A JSON file (`~/.DSaver/links.json`). See [`LinkStore`].
+    ///
+    /// The whole file is written on every change, but it can be read and fixed by hand.
+    #[default]
+    Json,
+    /// An SQLite database (`~/.DSaver/links.db`). See `SqliteStorage`.
+    ///
+    /// Only the changed link is written, so it is faster for thousands of links.
+    ///
+    /// *You need to enable `sqlite` feature to open this storage*
+    Sqlite,
+}
+
+impl StorageBackend {
+    /// Get all the backends
+    pub fn get_vec() -> Vec<Self> {
+        vec![Self::Json, Self::Sqlite]
+    }
+
+    /// Open the storage of this backend inside the home directory `home_dir`.
+    ///
+    /// The JSON storage is opened with [`LinkStore::load`]. So if the file is corrupted, you may want to call [`LinkStore::recover`] yourself.
+    pub fn open<P: AsRef<Path>>(&self, home_dir: P) -> Result<Box<dyn Storage>, ErrorReporter> {
+        match self {
+            Self::Json => Ok(Box::new(LinkStore::load(links_file(home_dir))?)),
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite => Ok(Box::new(super::SqliteStorage::open(links_db(home_dir))?)),
+            #[cfg(not(feature = "sqlite"))]
+            Self::Sqlite => Err(ErrorReporterBuilder {
+                actual_error: "None",
+                why_error: vec!["This version of the app is built without SQLite"],
+                how_to_fix: vec![
+                    "Switch to the JSON storage in the settings",
+                    &format!(
+                        "Build the app with the `sqlite` feature to use {}",
+                        links_db(home_dir).display()
+                    ),
+                ],
+                error_title: "Storage Not Available",
+                when_error: "opening the SQLite storage",
+                error_type: ErrorType::Database,
+            }
+            .build()),
+        }
+    }
+}
+
+impl Display for StorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => f.write_str("JSON"),
+            Self::Sqlite => f.write_str("SQLite"),
+        }
+    }
+}
+
+/// Copy all links, saved searches, tag infos and the trash from the storage `from` into the storage `to`.
+///
+/// The links, saved searches, tag infos and trash already inside `to` will be removed with [`Storage::replace_file`]. The storage `from` is not changed, so nothing is lost if anything goes wrong.
+///
+/// It returns how many links are copied.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::storage::{migrate_storage, LinkStore, Storage};
+///
+/// let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+/// let mut from = LinkStore::load(dir.join("old.json")).unwrap();
+/// let mut to = LinkStore::load(dir.join("new.json")).unwrap();
+///
+/// Storage::insert(&mut from, Link::new("https://www.rust-lang.org")).unwrap();
+///
+/// assert_eq!(migrate_storage(&from, &mut to).unwrap(), 1);
+/// assert_eq!(Storage::links(&to).unwrap(), Storage::links(&from).unwrap());
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn migrate_storage(from: &dyn Storage, to: &mut dyn Storage) -> Result<usize, ErrorReporter> {
+    let file = LinksFile {
+        links: from.links()?,
+        saved_searches: from.saved_searches()?,
+        trash: from.trashed()?,
+        tag_infos: from.tag_infos()?,
+        ..LinksFile::default()
+    };
+    let count = file.links.len();
+
+    to.replace_file(file)?;
+
+    Ok(count)
+}
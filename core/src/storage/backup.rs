@@ -0,0 +1,159 @@
+use chrono::{DateTime, NaiveDateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the directory (next to the links file) where the backups are stored.
+pub const BACKUPS_DIR: &str = "backups";
+
+/// How many backups will be kept by default.
+///
+/// When a new backup is created, the oldest backups will be removed so that only this many backups remain.
+pub const MAX_BACKUPS: usize = 10;
+
+/// Format of the backup file names. For example `links-20221014T153020.512Z.json`.
+pub(crate) const BACKUP_NAME_FORMAT: &str = "links-%Y%m%dT%H%M%S%.3fZ.json";
+
+/// A backup of the links file.
+///
+/// Every time the links are saved, the old file is copied into the backups directory. So that the user can restore it if anything goes wrong.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Backup {
+    /// Name of the backup file
+    ///
+    /// Use this name to restore the backup.
+    pub name: String,
+    /// When the backup was created
+    pub created_at: DateTime<Utc>,
+    /// Size of the backup file in bytes
+    pub size: u64,
+}
+
+/// Get the backups directory for the given links file.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # use dsaver_core::storage::backups_dir;
+/// #
+/// assert_eq!(
+///     backups_dir("/home/user/.DSaver/links.json"),
+///     Path::new("/home/user/.DSaver/backups")
+/// );
+/// ```
+pub fn backups_dir<P: AsRef<Path>>(links_file: P) -> PathBuf {
+    links_file
+        .as_ref()
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(BACKUPS_DIR)
+}
+
+/// Copy the `file` into the backups directory `dir` with the current time in its name.
+///
+/// If the `file` doesn't exist, then nothing will be copied and it will return `None`.
+pub fn create_backup<P: AsRef<Path>, D: AsRef<Path>>(
+    file: P,
+    dir: D,
+) -> io::Result<Option<Backup>> {
+    let (file, dir) = (file.as_ref(), dir.as_ref());
+
+    if !file.exists() {
+        return Ok(None);
+    }
+
+    fs::create_dir_all(dir)?;
+
+    // the name only keeps milliseconds
+    let created_at = Utc::now().trunc_subsecs(3);
+    let name = created_at.format(BACKUP_NAME_FORMAT).to_string();
+    let size = fs::copy(file, dir.join(&name))?;
+
+    Ok(Some(Backup {
+        name,
+        created_at,
+        size,
+    }))
+}
+
+/// Get all backups inside the directory `dir`. The newest backup will be first.
+///
+/// Files whose names are not created by [`create_backup`] will be ignored.
+///
+/// If the directory doesn't exist, then it will return an empty list.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::storage::{create_backup, list_backups};
+///
+/// let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+/// let file = dir.join("links.json");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// std::fs::write(&file, "[]").unwrap();
+///
+/// assert!(list_backups(dir.join("backups")).unwrap().is_empty());
+///
+/// let backup = create_backup(&file, dir.join("backups")).unwrap().unwrap();
+///
+/// assert_eq!(list_backups(dir.join("backups")).unwrap(), vec![backup]);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn list_backups<D: AsRef<Path>>(dir: D) -> io::Result<Vec<Backup>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut backups = Vec::new();
+
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // ignore unknown files
+        if let Ok(created_at) = NaiveDateTime::parse_from_str(&name, BACKUP_NAME_FORMAT) {
+            backups.push(Backup {
+                name,
+                created_at: DateTime::from_naive_utc_and_offset(created_at, Utc),
+                size: entry.metadata()?.len(),
+            });
+        }
+    }
+
+    // newest first
+    backups.sort_by_key(|backup| Reverse(backup.created_at));
+
+    Ok(backups)
+}
+
+/// Remove the oldest backups from the directory `dir` so that only `keep` backups remain.
+pub fn remove_old_backups<D: AsRef<Path>>(dir: D, keep: usize) -> io::Result<()> {
+    let dir = dir.as_ref();
+
+    for backup in list_backups(dir)?.into_iter().skip(keep) {
+        fs::remove_file(dir.join(backup.name))?;
+    }
+
+    Ok(())
+}
+
+/// Check whether the `name` is a name of a backup file.
+///
+/// Use it before joining a name given by the user with the backups directory, so that no other files can be accessed.
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::storage::is_backup_name;
+/// #
+/// assert!(is_backup_name("links-20221014T153020.512Z.json"));
+/// assert!(!is_backup_name("../links.json"));
+/// ```
+pub fn is_backup_name(name: &str) -> bool {
+    NaiveDateTime::parse_from_str(name, BACKUP_NAME_FORMAT).is_ok()
+}
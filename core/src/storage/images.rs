@@ -0,0 +1,250 @@
+use super::{report_io, root_dir, write_atomic};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Name of the directory (inside [`ROOT_DIR`](super::ROOT_DIR)) where the favicons and thumbnails of the links are cached.
+pub const IMAGES_DIR: &str = "images";
+
+/// Name of the file (inside [`IMAGES_DIR`]) which knows the cached file of every image url.
+pub const IMAGES_INDEX: &str = "index.json";
+
+/// Get the path of the directory where the images are cached.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # use dsaver_core::storage::images_dir;
+/// #
+/// assert_eq!(images_dir("/home/user"), Path::new("/home/user/.DSaver/images"));
+/// ```
+pub fn images_dir<P: AsRef<Path>>(home_dir: P) -> PathBuf {
+    root_dir(home_dir).join(IMAGES_DIR)
+}
+
+/// The key of an image url inside the [`ImageCache`]
+///
+/// The frontend asks for the cached image with this key, so it never loads the image from the website.
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::storage::image_key;
+/// #
+/// let key = image_key("https://github.com/favicon.ico");
+///
+/// assert_eq!(key.len(), 16);
+/// assert_eq!(key, image_key("https://github.com/favicon.ico"));
+/// assert_ne!(key, image_key("https://docs.rs/favicon.ico"));
+/// ```
+pub fn image_key(url: &str) -> String {
+    format!("{:016x}", fnv_hash(url.as_bytes()))
+}
+
+/// The urls of the favicon and the thumbnail of the `link`
+pub fn image_urls(link: &Link) -> impl Iterator<Item = &str> {
+    let metadata = &link.metadata;
+
+    metadata
+        .favicon
+        .iter()
+        .chain(&metadata.image)
+        .map(String::as_str)
+}
+
+/// An image inside the [`ImageCache`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CachedImage {
+    /// Where the image was downloaded from
+    pub url: String,
+    /// Name of the file inside the cache directory. It is the hash of the image, so the same image is stored only once.
+    pub file: String,
+    /// Content type of the image, like `image/png`
+    pub content_type: String,
+}
+
+/// The favicons and thumbnails of the links, downloaded once and stored in a directory
+///
+/// Every image is stored in a file named after the hash of its content. The index file ([`IMAGES_INDEX`]) maps the [`image_key`] of every url to its file.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::storage::{image_key, ImageCache};
+///
+/// let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+/// let mut cache = ImageCache::open(&dir).unwrap();
+///
+/// let url = "https://example.com/favicon.ico";
+/// cache.insert(url, "image/x-icon", b"icon").unwrap();
+///
+/// let (image, data) = cache.read(&image_key(url)).unwrap().unwrap();
+/// assert_eq!(image.content_type, "image/x-icon");
+/// assert_eq!(data, b"icon");
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImageCache {
+    /// The cache directory
+    dir: PathBuf,
+    /// Every cached image by its [`image_key`]
+    index: BTreeMap<String, CachedImage>,
+}
+
+impl ImageCache {
+    /// Open the cache inside the directory `dir`.
+    ///
+    /// The directory is created when the first image is inserted. If the index file is corrupted, then the cache is empty (the images can always be downloaded again), and the old files are removed by [`collect_garbage`](Self::collect_garbage).
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, ErrorReporter> {
+        let dir = dir.as_ref().to_path_buf();
+        let path = dir.join(IMAGES_INDEX);
+
+        let index = match fs::read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+            Err(err) if err.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(report_io(err, "reading the file", &path)),
+        };
+
+        Ok(Self { dir, index })
+    }
+
+    /// Whether the image of the `url` is cached
+    pub fn contains(&self, url: &str) -> bool {
+        self.index.contains_key(&image_key(url))
+    }
+
+    /// Every cached image, sorted by the key
+    pub fn images(&self) -> impl Iterator<Item = &CachedImage> {
+        self.index.values()
+    }
+
+    /// Read the image of the `key` (see [`image_key`]).
+    ///
+    /// It returns `None` if the image is not cached or its file is removed.
+    pub fn read(&self, key: &str) -> Result<Option<(CachedImage, Vec<u8>)>, ErrorReporter> {
+        let image = match self.index.get(key) {
+            Some(image) => image,
+            None => return Ok(None),
+        };
+        let path = self.dir.join(&image.file);
+
+        match fs::read(&path) {
+            Ok(data) => Ok(Some((image.clone(), data))),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(report_io(err, "reading the file", &path)),
+        }
+    }
+
+    /// Store the image `data` downloaded from the `url`.
+    ///
+    /// It returns the key of the image.
+    pub fn insert(
+        &mut self,
+        url: &str,
+        content_type: &str,
+        data: &[u8],
+    ) -> Result<String, ErrorReporter> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|err| report_io(err, "creating the directory", &self.dir))?;
+
+        let file = format!("{:016x}.{}", fnv_hash(data), extension(content_type));
+        let path = self.dir.join(&file);
+
+        // the same image is already stored for another url
+        if !path.exists() {
+            write_atomic(&path, data).map_err(|err| report_io(err, "writing the file", &path))?;
+        }
+
+        let key = image_key(url);
+        self.index.insert(
+            key.clone(),
+            CachedImage {
+                url: url.to_string(),
+                file,
+                content_type: content_type.to_string(),
+            },
+        );
+        self.save()?;
+
+        Ok(key)
+    }
+
+    /// Remove the images which are not used by any of the `links`, and the files which are not used by any image.
+    ///
+    /// Pass the links inside the trash too, so that their images are kept until they are deleted forever.
+    ///
+    /// It returns how many files are removed.
+    pub fn collect_garbage(&mut self, links: &[Link]) -> Result<usize, ErrorReporter> {
+        let used = links
+            .iter()
+            .flat_map(image_urls)
+            .map(image_key)
+            .collect::<HashSet<_>>();
+
+        let count = self.index.len();
+        self.index.retain(|key, _| used.contains(key));
+        if self.index.len() != count {
+            self.save()?;
+        }
+
+        let files = self
+            .index
+            .values()
+            .map(|image| image.file.as_str())
+            .collect::<HashSet<_>>();
+
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(report_io(err, "reading the directory", &self.dir)),
+        };
+
+        let mut removed = 0;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if name != IMAGES_INDEX && !files.contains(name.as_str()) {
+                let path = entry.path();
+                fs::remove_file(&path).map_err(|err| report_io(err, "removing the file", &path))?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Write the index into the cache directory
+    fn save(&self) -> Result<(), ErrorReporter> {
+        let path = self.dir.join(IMAGES_INDEX);
+
+        // Serializing the index never fails
+        let data = serde_json::to_string_pretty(&self.index).unwrap();
+
+        write_atomic(&path, data).map_err(|err| report_io(err, "writing the file", &path))
+    }
+}
+
+/// The 64 bit FNV-1a hash of the `data`. Unlike the hasher of the standard library, it never changes between Rust versions.
+fn fnv_hash(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The file extension of an image with the `content_type`
+fn extension(content_type: &str) -> &'static str {
+    match content_type {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/avif" => "avif",
+        "image/svg+xml" => "svg",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        _ => "img",
+    }
+}
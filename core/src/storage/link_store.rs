@@ -0,0 +1,634 @@
+use super::{backups_dir, create_backup, is_backup_name, list_backups, remove_old_backups};
+use super::{migrate, write_atomic, Backup, LinksFile, MigrationError, CURRENT_VERSION};
+use super::{quarantine, quarantine_dir, recover_links, Recovery, TrashedLink, MAX_BACKUPS};
+use crate::prelude::*;
+use crate::tag::TagInfo;
+use std::fmt::Display;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// A collection of [`Link`]s stored in a JSON file.
+///
+/// The file contains a [`LinksFile`]. Files of older versions are upgraded with [`migrate`] while loading, and saved with the [`CURRENT_VERSION`].
+///
+/// This is the only place where the links are read from or written to the filesystem.
+///
+/// All the changes ([`insert`](#method.insert), [`update`](#method.update) and [`delete`](#method.delete)) happen in memory. Call [`save`](#method.save) to write them into the file.
+///
+/// Before saving, the old file is copied into the `backups` directory next to it. Only the last [`MAX_BACKUPS`] backups are kept. You can change it with [`max_backups`](#method.max_backups).
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::storage::LinkStore;
+///
+/// let path = std::env::temp_dir().join(format!("dsaver-{}.json", uuid::Uuid::new_v4()));
+///
+/// // The file doesn't exist yet, so the store is empty
+/// let mut store = LinkStore::load(&path).unwrap();
+/// assert!(store.links().is_empty());
+///
+/// // add a new link and save it
+/// let link = store
+///     .insert(Link::new("https://www.rust-lang.org").title("Rust").description(""))
+///     .unwrap();
+/// store.save().unwrap();
+///
+/// // load it again
+/// let mut store = LinkStore::load(&path).unwrap();
+/// assert_eq!(store.links(), &vec![link.clone()]);
+///
+/// // delete the link
+/// store.delete(link.id.unwrap()).unwrap();
+/// assert!(store.links().is_empty());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkStore {
+    /// Path of the JSON file
+    path: PathBuf,
+    /// Content of the file
+    file: LinksFile,
+    /// How many backups to keep
+    max_backups: usize,
+}
+
+impl LinkStore {
+    /// Create a new empty store for the given `path`.
+    ///
+    /// It won't read or create the file. If you want to read the links from the file, use [`load`](#method.load).
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            file: LinksFile::default(),
+            max_backups: MAX_BACKUPS,
+        }
+    }
+
+    /// Change how many backups will be kept.
+    ///
+    /// The default value is [`MAX_BACKUPS`]. If it is `0`, then no backups will be created.
+    pub fn max_backups(mut self, max_backups: usize) -> Self {
+        self.max_backups = max_backups;
+        self
+    }
+
+    /// Read the links from the file of the given `path`.
+    ///
+    /// If the file doesn't exist, then it will return an empty store. The file will be created when you [`save`](#method.save) it.
+    ///
+    /// If the file is written by an older version of the app, it will be migrated to the [`CURRENT_VERSION`]. The file itself is updated on the next [`save`](#method.save).
+    ///
+    /// It will return an error with [`ErrorType::FileSystem`] if the file can't be read, [`ErrorType::CorruptedData`] if the content of the file is not valid and [`ErrorType::UnsupportedVersion`] if the file is written by a newer version of the app.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ErrorReporter> {
+        let path = path.as_ref();
+
+        let data = match fs::read_to_string(path) {
+            Ok(data) => data,
+            // The user has not saved any links yet
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::new(path)),
+            Err(err) => return Err(report_io(err, "reading the file", path)),
+        };
+
+        Ok(Self {
+            file: parse(&data, path)?,
+            ..Self::new(path)
+        })
+    }
+
+    /// Read every valid link from a corrupted file. Use it when [`load`](#method.load) fails with [`ErrorType::CorruptedData`].
+    ///
+    /// The original file is copied into the `quarantine` directory next to it. The file itself is not changed until you [`save`](#method.save) the store.
+    ///
+    /// See [`recover_links`] for how the links are recovered. Use [`Recovery::report`] to tell the user what happened.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dsaver_core::storage::LinkStore;
+    ///
+    /// let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+    /// let path = dir.join("links.json");
+    /// std::fs::create_dir_all(&dir).unwrap();
+    /// std::fs::write(&path, r#"[{ "url": "https://www.rust-lang.org", "tit"#).unwrap();
+    ///
+    /// assert!(LinkStore::load(&path).is_err());
+    ///
+    /// let (store, recovery) = LinkStore::recover(&path).unwrap();
+    ///
+    /// assert!(store.links().is_empty());
+    /// assert_eq!(recovery.lost, 1);
+    /// assert!(recovery.quarantined.exists());
+    /// # std::fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    pub fn recover<P: AsRef<Path>>(path: P) -> Result<(Self, Recovery), ErrorReporter> {
+        let path = path.as_ref();
+
+        let data = fs::read(path).map_err(|err| report_io(err, "reading the file", path))?;
+        // the corruption can be anywhere, even inside a character
+        let data = String::from_utf8_lossy(&data);
+
+        let quarantined = quarantine(path, quarantine_dir(path))
+            .map_err(|err| report_io(err, "copying the file", path))?;
+
+        let recovered = recover_links(&data);
+        let recovery = Recovery {
+            recovered: recovered.links.len() + recovered.trash.len(),
+            lost: recovered.lost,
+            quarantined,
+        };
+
+        let mut store = Self::new(path);
+        store.file.links = recovered.links;
+        store.file.trash = recovered.trash;
+        store.file.saved_searches = recovered.saved_searches;
+        for info in recovered.tag_infos {
+            // keeps the infos sorted by the tag
+            store.save_tag_info(info);
+        }
+
+        Ok((store, recovery))
+    }
+
+    /// Path of the file where the links are stored.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All the links of the store.
+    pub fn links(&self) -> &Vec<Link> {
+        &self.file.links
+    }
+
+    /// Replace all the links of the store.
+    pub fn set_links(&mut self, links: Vec<Link>) {
+        self.file.links = links;
+    }
+
+    /// Replace the links, the trash, the saved searches and the tag infos of the store.
+    pub fn set_file(&mut self, file: LinksFile) {
+        self.file = LinksFile {
+            version: CURRENT_VERSION,
+            ..file
+        };
+    }
+
+    /// Get a link by its `id`.
+    pub fn get(&self, id: Uuid) -> Option<&Link> {
+        self.file.links.iter().find(|link| link.id == Some(id))
+    }
+
+    /// Add a new link to the store.
+    ///
+    /// If the link's `id` is `None`, then a new id will be created. The tags are normalized with [`Link::normalize_tags`].
+    ///
+    /// It returns the link that was added.
+    ///
+    /// It will return an error with [`ErrorType::DuplicateLink`] if a link with the same `id` already exists.
+    pub fn insert(&mut self, mut link: Link) -> Result<Link, ErrorReporter> {
+        let id = *link.id.get_or_insert_with(Uuid::new_v4);
+
+        if self.get(id).is_some() {
+            return Err(ErrorReporterBuilder {
+                actual_error: "None",
+                why_error: vec!["A link with the same id is already saved"],
+                how_to_fix: vec!["Edit the existing link instead of creating it again"],
+                error_title: "Link Already Exists",
+                when_error: "adding a new link",
+                error_type: ErrorType::DuplicateLink,
+            }
+            .build());
+        }
+
+        let link = link.normalize_tags();
+        self.file.links.push(link.clone());
+
+        Ok(link)
+    }
+
+    /// Replace the link which has the same `id` as the given `link`. The tags are normalized with [`Link::normalize_tags`].
+    ///
+    /// It returns the updated link.
+    ///
+    /// It will return an error with [`ErrorType::LinkNotFound`] if no link has the same `id`.
+    pub fn update(&mut self, link: Link) -> Result<Link, ErrorReporter> {
+        let position = link
+            .id
+            .and_then(|id| self.position(id))
+            .ok_or_else(|| report_notfound("updating the link"))?;
+
+        let link = link.normalize_tags();
+        self.file.links[position] = link.clone();
+
+        Ok(link)
+    }
+
+    /// Remove the link with the given `id`.
+    ///
+    /// It returns the removed link.
+    ///
+    /// It will return an error with [`ErrorType::LinkNotFound`] if no link has the `id`.
+    pub fn delete(&mut self, id: Uuid) -> Result<Link, ErrorReporter> {
+        let position = self
+            .position(id)
+            .ok_or_else(|| report_notfound("deleting the link"))?;
+
+        Ok(self.file.links.remove(position))
+    }
+
+    /// All the links inside the trash. The last deleted link will be first.
+    pub fn trashed(&self) -> &Vec<TrashedLink> {
+        &self.file.trash
+    }
+
+    /// Replace all the links inside the trash.
+    pub fn set_trash(&mut self, trash: Vec<TrashedLink>) {
+        self.file.trash = trash;
+    }
+
+    /// Move the link with the given `id` into the trash.
+    ///
+    /// It returns the trashed link.
+    ///
+    /// It will return an error with [`ErrorType::LinkNotFound`] if no link has the `id`.
+    pub fn trash(&mut self, id: Uuid) -> Result<TrashedLink, ErrorReporter> {
+        let position = self
+            .position(id)
+            .ok_or_else(|| report_notfound("deleting the link"))?;
+
+        let trashed = TrashedLink::new(self.file.links.remove(position));
+        self.file.trash.insert(0, trashed.clone());
+
+        Ok(trashed)
+    }
+
+    /// Move the link with the given `id` out of the trash. It is added after all the other links.
+    ///
+    /// It returns the restored link.
+    ///
+    /// It will return an error with [`ErrorType::LinkNotFound`] if the trash doesn't have the link and [`ErrorType::DuplicateLink`] if a link with the same `id` is already saved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dsaver_core::prelude::*;
+    /// use dsaver_core::storage::LinkStore;
+    ///
+    /// let path = std::env::temp_dir().join(format!("dsaver-{}.json", uuid::Uuid::new_v4()));
+    /// let mut store = LinkStore::new(&path);
+    ///
+    /// let link = store.insert(Link::new("https://www.rust-lang.org")).unwrap();
+    ///
+    /// store.trash(link.id.unwrap()).unwrap();
+    /// assert!(store.links().is_empty());
+    /// assert_eq!(store.trashed()[0].link, link);
+    ///
+    /// store.restore(link.id.unwrap()).unwrap();
+    /// assert_eq!(store.links(), &vec![link]);
+    /// assert!(store.trashed().is_empty());
+    /// ```
+    pub fn restore(&mut self, id: Uuid) -> Result<Link, ErrorReporter> {
+        let position = self
+            .trash_position(id)
+            .ok_or_else(|| report_notfound("restoring the link"))?;
+
+        let link = self.insert(self.file.trash[position].link.clone())?;
+        self.file.trash.remove(position);
+
+        Ok(link)
+    }
+
+    /// Delete forever the link with the given `id` from the trash.
+    ///
+    /// It returns the removed link.
+    ///
+    /// It will return an error with [`ErrorType::LinkNotFound`] if the trash doesn't have the link.
+    pub fn purge(&mut self, id: Uuid) -> Result<TrashedLink, ErrorReporter> {
+        let position = self
+            .trash_position(id)
+            .ok_or_else(|| report_notfound("deleting the link forever"))?;
+
+        Ok(self.file.trash.remove(position))
+    }
+
+    /// All the saved searches of the store, in the order they were added.
+    pub fn saved_searches(&self) -> &Vec<SavedSearch> {
+        &self.file.saved_searches
+    }
+
+    /// Add the `search`, or replace the saved search which has the same `id`.
+    ///
+    /// It returns the saved search.
+    pub fn save_search(&mut self, search: SavedSearch) -> SavedSearch {
+        match self
+            .file
+            .saved_searches
+            .iter_mut()
+            .find(|saved| saved.id == search.id)
+        {
+            Some(saved) => *saved = search.clone(),
+            None => self.file.saved_searches.push(search.clone()),
+        }
+
+        search
+    }
+
+    /// Remove the saved search with the given `id`.
+    ///
+    /// It returns the removed search.
+    ///
+    /// It will return an error with [`ErrorType::SavedSearchNotFound`] if no saved search has the `id`.
+    pub fn delete_search(&mut self, id: Uuid) -> Result<SavedSearch, ErrorReporter> {
+        let position = self
+            .file
+            .saved_searches
+            .iter()
+            .position(|search| search.id == id)
+            .ok_or_else(|| report_search_notfound("deleting the saved search"))?;
+
+        Ok(self.file.saved_searches.remove(position))
+    }
+
+    /// The colors, icons and descriptions of the tags, sorted by the tag.
+    pub fn tag_infos(&self) -> &Vec<TagInfo> {
+        &self.file.tag_infos
+    }
+
+    /// Add the `info`, or replace the info of the same tag.
+    ///
+    /// It returns the saved info.
+    pub fn save_tag_info(&mut self, info: TagInfo) -> TagInfo {
+        match self
+            .file
+            .tag_infos
+            .binary_search_by(|saved| saved.tag.cmp(&info.tag))
+        {
+            Ok(position) => self.file.tag_infos[position] = info.clone(),
+            Err(position) => self.file.tag_infos.insert(position, info.clone()),
+        }
+
+        info
+    }
+
+    /// Remove the info of the `tag`.
+    ///
+    /// It returns the removed info.
+    ///
+    /// It will return an error with [`ErrorType::TagNotFound`] if the tag has no info.
+    pub fn delete_tag_info(&mut self, tag: &str) -> Result<TagInfo, ErrorReporter> {
+        let position = self
+            .file
+            .tag_infos
+            .iter()
+            .position(|info| info.tag == tag)
+            .ok_or_else(|| report_tag_info_notfound("deleting the info of the tag"))?;
+
+        Ok(self.file.tag_infos.remove(position))
+    }
+
+    /// Write all the links into the file.
+    ///
+    /// The parent directory will be created if it doesn't exist.
+    ///
+    /// The old file is backed up first, and the new file is written with [`write_atomic`]. So a crash while saving will never lose the links.
+    ///
+    /// It will return an error with [`ErrorType::FileSystem`] if the file can't be written.
+    pub fn save(&self) -> Result<(), ErrorReporter> {
+        self.write(self.max_backups > 0)
+    }
+
+    /// Write all the links into the file like [`save`](#method.save), but without backing up the old file.
+    ///
+    /// Use it for the changes which are not made by the user (e.g. the results of checking the links), so that they don't replace the user's backups.
+    pub fn save_without_backup(&self) -> Result<(), ErrorReporter> {
+        self.write(false)
+    }
+
+    /// Write all the links into the file, and back up the old file first if `backup` is `true`.
+    fn write(&self, backup: bool) -> Result<(), ErrorReporter> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| report_io(err, "creating the directory", parent))?;
+        }
+
+        let file = LinksFile {
+            version: CURRENT_VERSION,
+            ..self.file.clone()
+        };
+
+        // Serializing `LinksFile` never fails
+        let data = serde_json::to_string_pretty(&file).unwrap();
+
+        // Make sure that the data can be read again before replacing the old file
+        if let Err(err) = serde_json::from_str::<LinksFile>(&data) {
+            return Err(report_corrupted(err, &self.path));
+        }
+
+        if backup {
+            let dir = backups_dir(&self.path);
+
+            create_backup(&self.path, &dir)
+                .and_then(|_| remove_old_backups(&dir, self.max_backups))
+                .map_err(|err| report_io(err, "backing up the file", &self.path))?;
+        }
+
+        write_atomic(&self.path, data).map_err(|err| report_io(err, "writing the file", &self.path))
+    }
+
+    /// Make the `change` and write the file, backing up the old file first if `backup` is `true`.
+    ///
+    /// If the change or the writing fails, then the store is changed back, so it still has the links of the file.
+    pub(crate) fn write_change<T, F>(&mut self, backup: bool, change: F) -> Result<T, ErrorReporter>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ErrorReporter>,
+    {
+        let before = self.file.clone();
+        let backup = backup && self.max_backups > 0;
+
+        let result = change(self).and_then(|value| {
+            self.write(backup)?;
+            Ok(value)
+        });
+
+        if result.is_err() {
+            self.file = before;
+        }
+
+        result
+    }
+
+    /// All backups of the file. The newest backup will be first.
+    pub fn backups(&self) -> Result<Vec<Backup>, ErrorReporter> {
+        let dir = backups_dir(&self.path);
+
+        list_backups(&dir).map_err(|err| report_io(err, "reading the directory", &dir))
+    }
+
+    /// Replace all links with the links of the backup `name` and save them.
+    ///
+    /// The current file will be backed up too. So restoring can be undone by restoring that backup.
+    ///
+    /// Backups of older versions are migrated like [`load`](#method.load).
+    ///
+    /// It will return an error with [`ErrorType::FileNotFound`] if the backup doesn't exist and [`ErrorType::CorruptedData`] if the backup is not valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dsaver_core::prelude::*;
+    /// use dsaver_core::storage::LinkStore;
+    ///
+    /// let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+    /// let mut store = LinkStore::load(dir.join("links.json")).unwrap();
+    ///
+    /// store.insert(Link::new("https://www.rust-lang.org")).unwrap();
+    /// store.save().unwrap();
+    ///
+    /// // oops, the user deleted everything
+    /// let links = store.links().clone();
+    /// store.delete(links[0].id.unwrap()).unwrap();
+    /// store.save().unwrap();
+    ///
+    /// // the newest backup has the old links
+    /// let backup = store.backups().unwrap().remove(0);
+    /// store.restore_backup(&backup.name).unwrap();
+    ///
+    /// assert_eq!(store.links(), &links);
+    /// # std::fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    pub fn restore_backup(&mut self, name: &str) -> Result<(), ErrorReporter> {
+        let path = backups_dir(&self.path).join(name);
+
+        if !is_backup_name(name) || !path.is_file() {
+            return Err(ErrorReporterBuilder {
+                actual_error: "None",
+                why_error: vec!["The backup is deleted"],
+                how_to_fix: vec!["Choose another backup"],
+                error_title: "Backup Not Found",
+                when_error: &format!("restoring the backup {name}"),
+                error_type: ErrorType::FileNotFound,
+            }
+            .build());
+        }
+
+        let data =
+            fs::read_to_string(&path).map_err(|err| report_io(err, "reading the file", &path))?;
+        let file = parse(&data, &path)?;
+
+        self.write_change(true, |store| {
+            store.file = file;
+            Ok(())
+        })
+    }
+
+    /// Index of the link with the given `id`
+    fn position(&self, id: Uuid) -> Option<usize> {
+        self.file.links.iter().position(|link| link.id == Some(id))
+    }
+
+    /// Index of the link with the given `id` inside the trash
+    fn trash_position(&self, id: Uuid) -> Option<usize> {
+        self.file
+            .trash
+            .iter()
+            .position(|trashed| trashed.link.id == Some(id))
+    }
+}
+
+/// Parse the content of the file at `path`, migrating it if it is old
+fn parse(data: &str, path: &Path) -> Result<LinksFile, ErrorReporter> {
+    let value = serde_json::from_str(data).map_err(|err| report_corrupted(err, path))?;
+
+    migrate(value).map_err(|err| match err {
+        MigrationError::NewerVersion(_) => ErrorReporterBuilder {
+            actual_error: &err.to_string(),
+            why_error: vec!["The file is saved by a newer version of DSaver"],
+            how_to_fix: vec!["Update DSaver to the latest version"],
+            error_title: "Unsupported Version",
+            when_error: &format!("reading the links from {}", path.display()),
+            error_type: ErrorType::UnsupportedVersion,
+        }
+        .build(),
+        _ => report_corrupted(err, path),
+    })
+}
+
+/// Create a report for failing to read or write the file
+pub(crate) fn report_io<E: Display>(actual_error: E, when: &str, path: &Path) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: &actual_error.to_string(),
+        why_error: vec![
+            "You don't have the permission to access the file",
+            "The disk is full or not available",
+        ],
+        how_to_fix: vec![
+            &format!("Check the permissions of {}", path.display()),
+            "Make sure you have enough space in your disk",
+        ],
+        error_title: "Cannot Access The File",
+        when_error: &format!("{when} {}", path.display()),
+        error_type: ErrorType::FileSystem,
+    }
+    .build()
+}
+
+/// Create a report for invalid content of the file
+pub(crate) fn report_corrupted<E: Display>(actual_error: E, path: &Path) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: &actual_error.to_string(),
+        why_error: vec![
+            "The file is edited by hand or by another program",
+            "The app was closed while it was saving the links",
+        ],
+        how_to_fix: vec![&format!(
+            "Fix the JSON of the file {} by hand",
+            path.display()
+        )],
+        error_title: "Corrupted Data",
+        when_error: &format!("reading the links from {}", path.display()),
+        error_type: ErrorType::CorruptedData,
+    }
+    .build()
+}
+
+/// Create a report for not finding a link
+pub(crate) fn report_notfound(when: &str) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: "None",
+        why_error: vec!["The link is already deleted"],
+        how_to_fix: vec!["Restart the app to see the latest links"],
+        error_title: "Link Not Found",
+        when_error: when,
+        error_type: ErrorType::LinkNotFound,
+    }
+    .build()
+}
+
+/// Create a report for not finding a saved search
+pub(crate) fn report_search_notfound(when: &str) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: "None",
+        why_error: vec!["The saved search is already deleted"],
+        how_to_fix: vec!["Restart the app to see the latest saved searches"],
+        error_title: "Saved Search Not Found",
+        when_error: when,
+        error_type: ErrorType::SavedSearchNotFound,
+    }
+    .build()
+}
+
+/// Create a report for not finding the info of a tag
+pub(crate) fn report_tag_info_notfound(when: &str) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: "None",
+        why_error: vec!["The tag has no color, icon or description"],
+        how_to_fix: vec!["Restart the app to see the latest tags"],
+        error_title: "Tag Not Found",
+        when_error: when,
+        error_type: ErrorType::TagNotFound,
+    }
+    .build()
+}
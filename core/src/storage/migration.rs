@@ -0,0 +1,224 @@
+use super::TrashedLink;
+use crate::prelude::*;
+use crate::tag::TagInfo;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt::{self, Display};
+
+/// Version of the links file written by this version of the app.
+///
+/// Increase it whenever the shape of the file (or of [`Link`]) changes, and add a step to [`MIGRATIONS`] which upgrades the previous version.
+pub const CURRENT_VERSION: u32 = 6;
+
+/// A step which upgrades the JSON of version `n` to version `n + 1`.
+type Migration = fn(Value) -> Value;
+
+/// All migrations in order. The first one upgrades version `1` to version `2`, the second one upgrades `2` to `3` and so on.
+///
+/// Never change or remove a step once it is released. Old files on the users' disks still need them.
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize - 1] =
+    [v1_to_v2, v2_to_v3, v3_to_v4, v4_to_v5, v5_to_v6];
+
+/// The content of the links file.
+///
+/// This is the top level envelope of `~/.DSaver/links.json`:
+///
+/// ```json
+/// {
+///     "version": 6,
+///     "links": [],
+///     "saved_searches": [],
+///     "trash": [],
+///     "tag_infos": []
+/// }
+/// ```
+///
+/// Use [`migrate`] to read a file of any older version.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LinksFile {
+    /// Version of the schema. See [`CURRENT_VERSION`].
+    pub version: u32,
+    /// All links of the file
+    pub links: Vec<Link>,
+    /// The searches saved by the user
+    ///
+    /// It is optional in the JSON, so that a list of links can be read without them (e.g. while recovering a corrupted file).
+    #[serde(default)]
+    pub saved_searches: Vec<SavedSearch>,
+    /// The deleted links which can still be restored, the newest first
+    ///
+    /// A migration which changes the links must change the links inside the trash too.
+    #[serde(default)]
+    pub trash: Vec<TrashedLink>,
+    /// The colors, icons and descriptions of the tags, sorted by the tag
+    #[serde(default)]
+    pub tag_infos: Vec<TagInfo>,
+}
+
+impl Default for LinksFile {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            links: Vec::new(),
+            saved_searches: Vec::new(),
+            trash: Vec::new(),
+            tag_infos: Vec::new(),
+        }
+    }
+}
+
+/// Errors which can occur while migrating the links file.
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The JSON is neither a list of links nor an object with a `version` number.
+    UnknownShape,
+    /// The file is written by a newer version of the app.
+    NewerVersion(u32),
+    /// The file has a known version but its content doesn't match that version.
+    Invalid(serde_json::Error),
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownShape => write!(f, "unknown format of the links file"),
+            Self::NewerVersion(version) => write!(
+                f,
+                "the version of the links file is {version} but the latest supported version is {CURRENT_VERSION}"
+            ),
+            Self::Invalid(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// Get the schema version of the JSON of a links file.
+///
+/// The first version of the app stored the links as a bare list without any version. So a list is version `1`.
+///
+/// It will return `None` if the version can't be detected.
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::storage::detect_version;
+/// # use serde_json::json;
+/// #
+/// assert_eq!(detect_version(&json!([])), Some(1));
+/// assert_eq!(detect_version(&json!({ "version": 2, "links": [] })), Some(2));
+/// assert_eq!(detect_version(&json!("links")), None);
+/// ```
+pub fn detect_version(value: &Value) -> Option<u32> {
+    match value {
+        Value::Array(_) => Some(1),
+        Value::Object(object) => object
+            .get("version")
+            .and_then(Value::as_u64)
+            .and_then(|version| u32::try_from(version).ok()),
+        _ => None,
+    }
+}
+
+/// Upgrade the JSON of a links file of any version to the [`CURRENT_VERSION`] and parse it.
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::storage::{migrate, CURRENT_VERSION};
+/// # use serde_json::json;
+/// #
+/// // a file of the first version
+/// let old = json!([{
+///     "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+///     "url": "https://www.rust-lang.org",
+///     "title": "Rust",
+///     "description": "",
+///     "tags": ["Rust"],
+///     "priority": "A",
+///     "browser": "Firefox",
+///     "complete": false,
+///     "date": "14 January 2022"
+/// }]);
+///
+/// let file = migrate(old).unwrap();
+///
+/// assert_eq!(file.version, CURRENT_VERSION);
+/// assert_eq!(file.links[0].url, "https://www.rust-lang.org");
+/// ```
+pub fn migrate(mut value: Value) -> Result<LinksFile, MigrationError> {
+    let version = detect_version(&value).ok_or(MigrationError::UnknownShape)?;
+
+    if version == 0 {
+        return Err(MigrationError::UnknownShape);
+    }
+    if version > CURRENT_VERSION {
+        return Err(MigrationError::NewerVersion(version));
+    }
+
+    for migration in &MIGRATIONS[version as usize - 1..] {
+        value = migration(value);
+    }
+
+    serde_json::from_value(value).map_err(MigrationError::Invalid)
+}
+
+/// Version 2 wraps the list of links into an object with a `version`.
+fn v1_to_v2(value: Value) -> Value {
+    json!({
+        "version": 2,
+        "links": value,
+    })
+}
+
+/// Version 3 replaces the `date` string of every link with the `created_at`, `updated_at` and `last_opened_at` timestamps.
+///
+/// If the date can't be parsed, then the time of the migration is used.
+fn v2_to_v3(mut value: Value) -> Value {
+    if let Some(links) = value.get_mut("links").and_then(Value::as_array_mut) {
+        for link in links.iter_mut().filter_map(Value::as_object_mut) {
+            let created_at = link
+                .remove("date")
+                .and_then(|date| date.as_str().and_then(parse_date))
+                .unwrap_or_else(Utc::now);
+
+            link.insert("created_at".into(), json!(created_at));
+            link.insert("updated_at".into(), json!(created_at));
+            link.insert("last_opened_at".into(), Value::Null);
+        }
+    }
+
+    value["version"] = json!(3);
+    value
+}
+
+/// Parse the `date` of the links of version 2. It was created by the frontend, like "14 January 2022" or "September 15, 2022".
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    ["%d %B %Y", "%B %d, %Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(date.trim(), format).ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| DateTime::from_naive_utc_and_offset(date, Utc))
+}
+
+/// Version 4 adds the `saved_searches` of the user.
+fn v3_to_v4(mut value: Value) -> Value {
+    value["saved_searches"] = json!([]);
+    value["version"] = json!(4);
+    value
+}
+
+/// Version 5 adds the `trash` of the deleted links.
+fn v4_to_v5(mut value: Value) -> Value {
+    value["trash"] = json!([]);
+    value["version"] = json!(5);
+    value
+}
+
+/// Version 6 adds the `tag_infos` with the colors, icons and descriptions of the tags.
+fn v5_to_v6(mut value: Value) -> Value {
+    value["tag_infos"] = json!([]);
+    value["version"] = json!(6);
+    value
+}
@@ -0,0 +1,127 @@
+//! Storing the user's [`Link`](crate::link::Link)s in the filesystem.
+//!
+//! Every part of the application (the desktop app, tests and any future tools) should use this module for reading and writing the links. So that all of them share the exact same storage code.
+
+mod backend;
+mod backup;
+mod images;
+mod link_store;
+mod migration;
+mod recovery;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod trash;
+
+pub use backend::*;
+pub use backup::*;
+pub use images::*;
+pub use link_store::*;
+pub use migration::*;
+pub use recovery::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite::*;
+pub use trash::*;
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The directory (inside the user's home directory) where the app stores its data.
+pub const ROOT_DIR: &str = ".DSaver";
+
+/// Name of the file where the links are stored (inside [`ROOT_DIR`]).
+pub const LINKS_FILE: &str = "links.json";
+
+/// Name of the database where the links are stored (inside [`ROOT_DIR`]) if the user chooses [`StorageBackend::Sqlite`].
+pub const LINKS_DB: &str = "links.db";
+
+/// Get the path of the application's data directory.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # use dsaver_core::storage::root_dir;
+/// #
+/// assert_eq!(root_dir("/home/user"), Path::new("/home/user/.DSaver"));
+/// ```
+pub fn root_dir<P: AsRef<Path>>(home_dir: P) -> PathBuf {
+    home_dir.as_ref().join(ROOT_DIR)
+}
+
+/// Get the path of the file where the links are stored.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # use dsaver_core::storage::links_file;
+/// #
+/// assert_eq!(links_file("/home/user"), Path::new("/home/user/.DSaver/links.json"));
+/// ```
+pub fn links_file<P: AsRef<Path>>(home_dir: P) -> PathBuf {
+    root_dir(home_dir).join(LINKS_FILE)
+}
+
+/// Get the path of the database where the links are stored.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # use dsaver_core::storage::links_db;
+/// #
+/// assert_eq!(links_db("/home/user"), Path::new("/home/user/.DSaver/links.db"));
+/// ```
+pub fn links_db<P: AsRef<Path>>(home_dir: P) -> PathBuf {
+    root_dir(home_dir).join(LINKS_DB)
+}
+
+/// Write the `data` into the file of the given `path` without ever leaving a half written file.
+///
+/// First the data is written into a temporary file next to the `path` and flushed to the disk. Then the temporary file is renamed to the `path`.
+///
+/// So if the app crashes in the middle, the old file will remain as it was.
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::storage::write_atomic;
+/// #
+/// let path = std::env::temp_dir().join(format!("dsaver-{}.json", uuid::Uuid::new_v4()));
+///
+/// write_atomic(&path, "[]").unwrap();
+///
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), "[]");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn write_atomic<P: AsRef<Path>, D: AsRef<[u8]>>(path: P, data: D) -> io::Result<()> {
+    let path = path.as_ref();
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = (|| {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(data.as_ref())?;
+        file.sync_all()?;
+
+        fs::rename(&temp_path, path)?;
+
+        // flush the directory too, so that the rename itself is on the disk
+        #[cfg(unix)]
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            File::open(parent)?.sync_all()?;
+        }
+
+        Ok(())
+    })();
+
+    if result.is_err() {
+        // don't leave the temporary file behind
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result
+}
@@ -0,0 +1,377 @@
+use super::{detect_version, migrate, LinksFile, TrashedLink, BACKUP_NAME_FORMAT};
+use crate::prelude::*;
+use crate::saved_search::SavedSearch;
+use crate::tag::TagInfo;
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Name of the directory (next to the links file) where the corrupted files are copied.
+pub const QUARANTINE_DIR: &str = "quarantine";
+
+/// Links salvaged from a corrupted file by [`recover_links`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovered {
+    /// Every link which could be read
+    pub links: Vec<Link>,
+    /// Every link inside the trash which could be read, with the time it was deleted
+    pub trash: Vec<TrashedLink>,
+    /// Every saved search which could be read
+    pub saved_searches: Vec<SavedSearch>,
+    /// Every info of the tags which could be read
+    pub tag_infos: Vec<TagInfo>,
+    /// How many links could not be read
+    pub lost: usize,
+}
+
+/// Result of [`LinkStore::recover`](super::LinkStore::recover).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recovery {
+    /// How many links are recovered
+    pub recovered: usize,
+    /// How many links are lost
+    pub lost: usize,
+    /// Where the corrupted file is copied
+    pub quarantined: PathBuf,
+}
+
+impl Recovery {
+    /// Create a report with [`ErrorType::DataRecovered`] to tell the user what happened.
+    pub fn report(&self) -> ErrorReporter {
+        ErrorReporterBuilder {
+            actual_error: &format!(
+                "Recovered {} links and lost {} links",
+                self.recovered, self.lost
+            ),
+            why_error: vec![
+                "The file is edited by hand or by another program",
+                "The app was closed while it was saving the links",
+            ],
+            how_to_fix: vec![
+                "Restore the links from a backup",
+                "Keep the recovered links",
+                &format!(
+                    "Fix the original file by hand. A copy of it is saved in {}",
+                    self.quarantined.display()
+                ),
+            ],
+            error_title: "Corrupted Data",
+            when_error: "reading the links",
+            error_type: ErrorType::DataRecovered {
+                recovered: self.recovered,
+                lost: self.lost,
+            },
+        }
+        .build()
+    }
+}
+
+/// Get the quarantine directory for the given links file.
+pub fn quarantine_dir<P: AsRef<Path>>(links_file: P) -> PathBuf {
+    links_file
+        .as_ref()
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(QUARANTINE_DIR)
+}
+
+/// Copy the corrupted `file` into the directory `dir` with the current time in its name, so that it is never overwritten.
+///
+/// It returns the path of the copy.
+pub fn quarantine<P: AsRef<Path>, D: AsRef<Path>>(file: P, dir: D) -> io::Result<PathBuf> {
+    let dir = dir.as_ref();
+
+    fs::create_dir_all(dir)?;
+
+    let path = dir.join(Utc::now().format(BACKUP_NAME_FORMAT).to_string());
+    fs::copy(file, &path)?;
+
+    Ok(path)
+}
+
+/// A `{ ... }` object inside the text
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+/// The list of the file which contains an object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    /// `"links"`, or the whole file of version 1
+    Links,
+    /// `"trash"`
+    Trash,
+    /// `"saved_searches"`
+    SavedSearches,
+    /// `"tag_infos"`
+    TagInfos,
+    /// Any other key, its objects are skipped
+    Other,
+}
+
+/// Read every valid [`Link`] from the content of a corrupted links file.
+///
+/// It doesn't need the file to be a valid JSON. It searches every `{ ... }` object inside the text and tries to read it as a link of the version written in the file. So a missing bracket or a broken link only loses that link.
+///
+/// The objects inside the `"trash"` list are read as [`TrashedLink`]s, so the deleted links stay in the trash. The saved searches and the infos of the tags are read too.
+///
+/// An object is counted as lost if it has an `"url"` but it is not a valid link.
+///
+/// If two links (inside or outside the trash) have the same `id`, then the second one will get a new id.
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::storage::recover_links;
+/// #
+/// let data = r#"{ "version": 2, "links": [
+///     { "id": null, "url": "https://www.rust-lang.org", "title": "Rust", "description": "",
+///       "tags": ["Rust"], "priority": "A", "browser": "Firefox", "complete": false, "date": "" },
+///     { "id": null, "url": "https://www.github.com", "tit
+/// "#;
+///
+/// let recovered = recover_links(data);
+///
+/// assert_eq!(recovered.links.len(), 1);
+/// assert_eq!(recovered.links[0].url, "https://www.rust-lang.org");
+/// assert_eq!(recovered.lost, 1);
+/// ```
+pub fn recover_links(data: &str) -> Recovered {
+    let version = file_version(data);
+    let (spans, unclosed, keys) = objects(data);
+
+    let mut links = Vec::new();
+    let mut trash = Vec::new();
+    let mut saved_searches: Vec<SavedSearch> = Vec::new();
+    let mut tag_infos: Vec<TagInfo> = Vec::new();
+    let mut ids = HashSet::new();
+    let mut lost = 0;
+    // the objects which are read as links
+    let mut accepted: Vec<&Span> = Vec::new();
+
+    for span in &spans {
+        // an object inside a link (if any)
+        if accepted
+            .iter()
+            .any(|link| link.start <= span.start && span.end <= link.end)
+        {
+            continue;
+        }
+
+        let text = &data[span.start..span.end];
+        // a file of version 1 is a list of links without any key
+        let section = match version {
+            1 => Section::Links,
+            _ => section(&keys, span.start),
+        };
+
+        if section == Section::SavedSearches {
+            let search = parse_object(text, version, "saved_searches")
+                .and_then(|mut file| file.saved_searches.pop());
+
+            if let Some(mut search) = search {
+                if saved_searches.iter().any(|saved| saved.id == search.id) {
+                    search.id = Uuid::new_v4();
+                }
+
+                saved_searches.push(search);
+                accepted.push(span);
+            }
+            continue;
+        }
+
+        if section == Section::TagInfos {
+            let info =
+                parse_object(text, version, "tag_infos").and_then(|mut file| file.tag_infos.pop());
+
+            // only the first info of a tag is kept
+            if let Some(info) = info {
+                if !tag_infos.iter().any(|saved| saved.tag == info.tag) {
+                    tag_infos.push(info);
+                }
+                accepted.push(span);
+            }
+            continue;
+        }
+
+        // the link, and when it was deleted if it is inside the trash
+        let parsed = match section {
+            Section::Links => parse_object(text, version, "links")
+                .and_then(|mut file| file.links.pop())
+                .map(|link| (link, None)),
+            Section::Trash => parse_object(text, version, "trash")
+                .and_then(|mut file| file.trash.pop())
+                .map(|trashed| (trashed.link, Some(trashed.deleted_at))),
+            _ => continue,
+        };
+
+        match parsed {
+            Some((mut link, deleted_at)) => {
+                let unique = match link.id {
+                    Some(id) => ids.insert(id),
+                    None => false,
+                };
+
+                if !unique {
+                    let id = Uuid::new_v4();
+                    ids.insert(id);
+                    link.id = Some(id);
+                }
+
+                match deleted_at {
+                    Some(deleted_at) => trash.push(TrashedLink { link, deleted_at }),
+                    None => links.push(link),
+                }
+                accepted.push(span);
+            }
+            None => {
+                // Only the innermost object with an url is counted, not the objects around it
+                let is_innermost = !spans.iter().any(|inner| {
+                    span.start < inner.start
+                        && inner.end <= span.end
+                        && has_url(&data[inner.start..inner.end])
+                });
+
+                if has_url(text) && is_innermost {
+                    lost += 1;
+                }
+            }
+        }
+    }
+
+    // the file was cut in the middle of a link
+    if let Some(start) = unclosed {
+        // skip the closed objects inside it, they are already counted
+        let rest = spans
+            .iter()
+            .filter(|span| span.start > start)
+            .map(|span| span.end)
+            .max()
+            .unwrap_or(start);
+
+        if has_url(&data[rest..]) {
+            lost += 1;
+        }
+    }
+
+    Recovered {
+        links,
+        trash,
+        saved_searches,
+        tag_infos,
+        lost,
+    }
+}
+
+/// Find all balanced objects, ordered by their start. Also returns the start of the innermost object which is never closed, and the keys of the outermost object with their positions.
+fn objects(data: &str) -> (Vec<Span>, Option<usize>, Vec<(usize, String)>) {
+    let mut spans = Vec::new();
+    let mut keys = Vec::new();
+    let mut stack = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    // the start of the current string
+    let mut string_start = 0;
+
+    for (index, c) in data.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+
+                // a string of the outermost object followed by `:` is a key
+                if stack.len() == 1 && data[index + 1..].trim_start().starts_with(':') {
+                    keys.push((string_start, data[string_start + 1..index].to_string()));
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                string_start = index;
+            }
+            '{' => stack.push(index),
+            '}' => {
+                if let Some(start) = stack.pop() {
+                    spans.push(Span {
+                        start,
+                        end: index + 1,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    spans.sort_by_key(|span| span.start);
+
+    (spans, stack.pop(), keys)
+}
+
+/// The section of the object which starts at `start`: the last key of the outermost object before it
+fn section(keys: &[(usize, String)], start: usize) -> Section {
+    match keys.iter().rev().find(|(index, _)| *index < start) {
+        Some((_, key)) if key == "links" => Section::Links,
+        Some((_, key)) if key == "trash" => Section::Trash,
+        Some((_, key)) if key == "saved_searches" => Section::SavedSearches,
+        Some((_, key)) if key == "tag_infos" => Section::TagInfos,
+        _ => Section::Other,
+    }
+}
+
+/// Read one object of the list `key` of the given version of the file
+fn parse_object(text: &str, version: u32, key: &str) -> Option<LinksFile> {
+    let object: Value = serde_json::from_str(text).ok()?;
+
+    if !object.is_object() {
+        return None;
+    }
+
+    let file = match version {
+        1 if key == "links" => json!([object]),
+        // the first version only has links
+        1 => return None,
+        _ => json!({ "version": version, "links": [], key: [object] }),
+    };
+
+    migrate(file).ok()
+}
+
+/// Check whether the text contains an `"url"` key
+fn has_url(text: &str) -> bool {
+    text.contains("\"url\"")
+}
+
+/// Find the version of the file, even if the file is not a valid JSON
+fn file_version(data: &str) -> u32 {
+    if let Ok(value) = serde_json::from_str(data) {
+        if let Some(version) = detect_version(&value) {
+            return version;
+        }
+    }
+
+    // a file of version 1 starts with a list, other versions start with an object
+    if data.trim_start().starts_with('[') {
+        return 1;
+    }
+
+    data.find("\"version\"")
+        .and_then(|index| {
+            let rest = data[index + "\"version\"".len()..].trim_start();
+            let rest = rest.strip_prefix(':')?.trim_start();
+            let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+
+            digits.parse().ok()
+        })
+        .unwrap_or(1)
+}
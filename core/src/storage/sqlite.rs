@@ -0,0 +1,927 @@
+use super::report_tag_info_notfound;
+use super::CURRENT_VERSION;
+use super::{manual_orders, migrate, report_io, report_notfound, report_search_notfound};
+use super::{LinksFile, MigrationError, Storage, TrashedLink};
+use crate::check::LinkCheck;
+use crate::prelude::*;
+use crate::tag::{TagInfo, TAG_SEPARATOR};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Transaction};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Tables of the database.
+///
+/// Every link is stored as JSON in the `data` column, so that it can be migrated with [`migrate`] just like the JSON file. The other columns are copies of some fields of the link, only for querying.
+///
+/// The saved searches, the tag infos and the trashed links are stored as JSON too. The `deleted_at` column of the trash is in milliseconds since the Unix epoch.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS links (
+        id TEXT PRIMARY KEY NOT NULL,
+        position INTEGER NOT NULL,
+        priority TEXT NOT NULL,
+        browser TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS links_position ON links (position);
+    CREATE INDEX IF NOT EXISTS links_priority ON links (priority);
+    CREATE INDEX IF NOT EXISTS links_browser ON links (browser);
+
+    CREATE TABLE IF NOT EXISTS link_tags (
+        link_id TEXT NOT NULL REFERENCES links (id) ON DELETE CASCADE,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (link_id, tag)
+    );
+    CREATE INDEX IF NOT EXISTS link_tags_tag ON link_tags (tag);
+    CREATE INDEX IF NOT EXISTS link_tags_tag_nocase ON link_tags (tag COLLATE NOCASE);
+
+    CREATE TABLE IF NOT EXISTS saved_searches (
+        id TEXT PRIMARY KEY NOT NULL,
+        position INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS trash (
+        id TEXT PRIMARY KEY NOT NULL,
+        deleted_at INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS trash_deleted_at ON trash (deleted_at);
+
+    CREATE TABLE IF NOT EXISTS tag_infos (
+        tag TEXT PRIMARY KEY NOT NULL,
+        data TEXT NOT NULL
+    );
+";
+
+/// A [`Storage`] which stores the links in an SQLite database.
+///
+/// Unlike [`LinkStore`](super::LinkStore), only the changed link is written. And [`query`](Storage::query) is executed by SQLite with indexes on tags, browser and priority.
+///
+/// The version of the links (see [`CURRENT_VERSION`]) is stored in the `user_version` of the database. Older links are migrated when the database is opened.
+///
+/// *You need to enable `sqlite` feature to use this struct*
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::storage::{SqliteStorage, Storage};
+///
+/// let path = std::env::temp_dir().join(format!("dsaver-{}.db", uuid::Uuid::new_v4()));
+/// let mut storage = SqliteStorage::open(&path).unwrap();
+///
+/// storage.insert(Link::new("https://www.rust-lang.org").tags("Rust").priority('B')).unwrap();
+/// storage.insert(Link::new("https://www.python.org").tags("Python").priority('B')).unwrap();
+///
+/// let filter = LinkFilter::default().tags(vec!["Rust"]).priority('B');
+/// let links = storage.query(&filter).unwrap();
+///
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].url, "https://www.rust-lang.org");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct SqliteStorage {
+    /// Path of the database
+    path: PathBuf,
+    /// Connection to the database
+    connection: Connection,
+}
+
+impl SqliteStorage {
+    /// Open the database of the given `path`. It will be created if it doesn't exist.
+    ///
+    /// It will return an error with [`ErrorType::Database`] if the database can't be opened and [`ErrorType::UnsupportedVersion`] if it is written by a newer version of the app.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ErrorReporter> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| report_io(err, "creating the directory", parent))?;
+        }
+
+        let connection = Connection::open(path)
+            .map_err(|err| report_database(err, "opening the database", path))?;
+
+        let mut storage = Self {
+            path: path.to_path_buf(),
+            connection,
+        };
+
+        storage
+            .connection
+            .execute_batch("PRAGMA foreign_keys = ON;")
+            .and_then(|_| storage.connection.execute_batch(SCHEMA))
+            .map_err(|err| storage.report(err, "creating the tables"))?;
+
+        storage.migrate()?;
+
+        Ok(storage)
+    }
+
+    /// Path of the database
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Migrate the stored links to the [`CURRENT_VERSION`]
+    fn migrate(&mut self) -> Result<(), ErrorReporter> {
+        let version: u32 = self
+            .connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|err| self.report(err, "reading the version"))?;
+
+        // a new database
+        if version == 0 {
+            return self.set_version();
+        }
+
+        if version == CURRENT_VERSION {
+            return Ok(());
+        }
+
+        let links = self.query_sql("SELECT data FROM links ORDER BY position", Vec::new())?;
+        let trash = self.query_sql(
+            "SELECT data FROM trash ORDER BY deleted_at DESC, rowid DESC",
+            Vec::new(),
+        )?;
+
+        let file = migrate(json!({ "version": version, "links": links, "trash": trash })).map_err(
+            |err| match err {
+                MigrationError::NewerVersion(_) => ErrorReporterBuilder {
+                    actual_error: &err.to_string(),
+                    why_error: vec!["The database is saved by a newer version of DSaver"],
+                    how_to_fix: vec!["Update DSaver to the latest version"],
+                    error_title: "Unsupported Version",
+                    when_error: &format!("reading the links from {}", self.path.display()),
+                    error_type: ErrorType::UnsupportedVersion,
+                }
+                .build(),
+                _ => self.report(err, "migrating the links"),
+            },
+        )?;
+
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "migrating the links", &self.path))?;
+
+        write_links(&transaction, file.links)
+            .and_then(|_| write_trash(&transaction, &file.trash))
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "migrating the links", &self.path))?;
+
+        self.set_version()
+    }
+
+    /// Store the [`CURRENT_VERSION`] into the database
+    fn set_version(&self) -> Result<(), ErrorReporter> {
+        self.connection
+            .execute_batch(&format!("PRAGMA user_version = {CURRENT_VERSION}"))
+            .map_err(|err| self.report(err, "writing the version"))
+    }
+
+    /// Run a `SELECT data ...` query and parse the links
+    fn query_sql(
+        &self,
+        sql: &str,
+        params: Vec<String>,
+    ) -> Result<Vec<serde_json::Value>, ErrorReporter> {
+        let read = || -> rusqlite::Result<Vec<String>> {
+            let mut statement = self.connection.prepare(sql)?;
+            let rows = statement.query_map(params_from_iter(params.iter()), |row| row.get(0))?;
+
+            rows.collect()
+        };
+
+        read()
+            .map_err(|err| self.report(err, "reading the links"))?
+            .iter()
+            .map(|data| {
+                serde_json::from_str(data).map_err(|err| self.report(err, "reading the links"))
+            })
+            .collect()
+    }
+
+    /// Parse the links of the current version
+    fn parse(&self, links: Vec<serde_json::Value>) -> Result<Vec<Link>, ErrorReporter> {
+        serde_json::from_value::<LinksFile>(json!({ "version": CURRENT_VERSION, "links": links }))
+            .map(|file| file.links)
+            .map_err(|err| self.report(err, "reading the links"))
+    }
+
+    /// Run a `SELECT data FROM trash ...` query and parse the trashed links of the current version
+    fn query_trash(
+        &self,
+        sql: &str,
+        params: Vec<String>,
+    ) -> Result<Vec<TrashedLink>, ErrorReporter> {
+        let trash = self.query_sql(sql, params)?;
+
+        serde_json::from_value::<LinksFile>(
+            json!({ "version": CURRENT_VERSION, "links": [], "trash": trash }),
+        )
+        .map(|file| file.trash)
+        .map_err(|err| self.report(err, "reading the trash"))
+    }
+
+    /// Create a report for failing to use the database
+    fn report<E: std::fmt::Display>(&self, actual_error: E, when: &str) -> ErrorReporter {
+        report_database(actual_error, when, &self.path)
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn links(&self) -> Result<Vec<Link>, ErrorReporter> {
+        let links = self.query_sql("SELECT data FROM links ORDER BY position", Vec::new())?;
+
+        self.parse(links)
+    }
+
+    fn get(&self, id: Uuid) -> Result<Option<Link>, ErrorReporter> {
+        let links = self.query_sql("SELECT data FROM links WHERE id = ?1", vec![id.to_string()])?;
+
+        Ok(self.parse(links)?.pop())
+    }
+
+    fn insert(&mut self, mut link: Link) -> Result<Link, ErrorReporter> {
+        let id = *link.id.get_or_insert_with(Uuid::new_v4);
+
+        if self.get(id)?.is_some() {
+            return Err(ErrorReporterBuilder {
+                actual_error: "None",
+                why_error: vec!["A link with the same id is already saved"],
+                how_to_fix: vec!["Edit the existing link instead of creating it again"],
+                error_title: "Link Already Exists",
+                when_error: "adding a new link",
+                error_type: ErrorType::DuplicateLink,
+            }
+            .build());
+        }
+
+        let link = link.normalize_tags();
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "adding the link", &self.path))?;
+
+        write_link(&transaction, &link, None)
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "adding the link", &self.path))?;
+
+        Ok(link)
+    }
+
+    fn update(&mut self, link: Link) -> Result<Link, ErrorReporter> {
+        let position: Option<i64> = match link.id {
+            Some(id) => self
+                .connection
+                .query_row(
+                    "SELECT position FROM links WHERE id = ?1",
+                    params![id.to_string()],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|err| self.report(err, "updating the link"))?,
+            None => None,
+        };
+
+        let position = position.ok_or_else(|| report_notfound("updating the link"))?;
+
+        let link = link.normalize_tags();
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "updating the link", &self.path))?;
+
+        write_link(&transaction, &link, Some(position))
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "updating the link", &self.path))?;
+
+        Ok(link)
+    }
+
+    fn delete(&mut self, id: Uuid) -> Result<Link, ErrorReporter> {
+        let link = self
+            .get(id)?
+            .ok_or_else(|| report_notfound("deleting the link"))?;
+
+        // the tags are deleted by `ON DELETE CASCADE`
+        self.connection
+            .execute("DELETE FROM links WHERE id = ?1", params![id.to_string()])
+            .map_err(|err| self.report(err, "deleting the link"))?;
+
+        Ok(link)
+    }
+
+    fn replace_all(&mut self, links: Vec<Link>) -> Result<(), ErrorReporter> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "replacing the links", &self.path))?;
+
+        write_links(&transaction, links)
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "replacing the links", &self.path))?;
+
+        self.set_version()
+    }
+
+    fn reorder(&mut self, ids: &[Uuid]) -> Result<Vec<Link>, ErrorReporter> {
+        let orders = manual_orders(ids);
+        let mut links = self.links()?;
+
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "reordering the links", &self.path))?;
+
+        let write = |links: &mut Vec<Link>| -> rusqlite::Result<()> {
+            for link in links {
+                let order = link.id.and_then(|id| orders.get(&id).copied());
+
+                // only the `data` column has the order, the other columns are not changed
+                if link.manual_order != order {
+                    link.manual_order = order;
+                    transaction.execute(
+                        "UPDATE links SET data = ?2 WHERE id = ?1",
+                        // Serializing `Link` never fails
+                        params![
+                            link.id.unwrap_or_default().to_string(),
+                            serde_json::to_string(link).unwrap()
+                        ],
+                    )?;
+                }
+            }
+
+            Ok(())
+        };
+
+        write(&mut links)
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "reordering the links", &self.path))?;
+
+        Ok(links)
+    }
+
+    fn save_checks(&mut self, checks: Vec<(Uuid, LinkCheck)>) -> Result<Vec<Link>, ErrorReporter> {
+        let mut updated = Vec::new();
+        for (id, check) in checks {
+            if let Some(mut link) = self.get(id)? {
+                if link.set_check(check) {
+                    updated.push(link);
+                }
+            }
+        }
+
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "saving the checks of the links", &self.path))?;
+
+        let write = |links: &[Link]| -> rusqlite::Result<()> {
+            for link in links {
+                // only the `data` column has the check, the other columns are not changed
+                transaction.execute(
+                    "UPDATE links SET data = ?2 WHERE id = ?1",
+                    // Serializing `Link` never fails
+                    params![
+                        link.id.unwrap_or_default().to_string(),
+                        serde_json::to_string(link).unwrap()
+                    ],
+                )?;
+            }
+
+            Ok(())
+        };
+
+        write(&updated)
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "saving the checks of the links", &self.path))?;
+
+        Ok(updated)
+    }
+
+    fn update_tags(
+        &mut self,
+        links: Vec<Link>,
+        infos_before: &[TagInfo],
+        infos_after: &[TagInfo],
+    ) -> Result<Vec<Link>, ErrorReporter> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "changing the tags", &self.path))?;
+
+        let write = || -> rusqlite::Result<Vec<Link>> {
+            let mut updated = Vec::new();
+
+            for link in links {
+                let link = link.normalize_tags();
+                let position: Option<i64> = transaction
+                    .query_row(
+                        "SELECT position FROM links WHERE id = ?1",
+                        params![link.id.unwrap_or_default().to_string()],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+
+                if let Some(position) = position {
+                    write_link(&transaction, &link, Some(position))?;
+                    updated.push(link);
+                }
+            }
+
+            for info in infos_before {
+                transaction.execute("DELETE FROM tag_infos WHERE tag = ?1", params![info.tag])?;
+            }
+            for info in infos_after {
+                transaction.execute(
+                    "INSERT OR REPLACE INTO tag_infos (tag, data) VALUES (?1, ?2)",
+                    // Serializing `TagInfo` never fails
+                    params![info.tag, serde_json::to_string(info).unwrap()],
+                )?;
+            }
+
+            Ok(updated)
+        };
+
+        let updated = write()
+            .and_then(|updated| transaction.commit().map(|_| updated))
+            .map_err(|err| report_database(err, "changing the tags", &self.path))?;
+
+        Ok(updated)
+    }
+
+    fn trash(&mut self, id: Uuid) -> Result<TrashedLink, ErrorReporter> {
+        let link = self
+            .get(id)?
+            .ok_or_else(|| report_notfound("deleting the link"))?;
+        let trashed = TrashedLink::new(link);
+
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "deleting the link", &self.path))?;
+
+        // the tags are deleted by `ON DELETE CASCADE`
+        transaction
+            .execute("DELETE FROM links WHERE id = ?1", params![id.to_string()])
+            .and_then(|_| write_trashed(&transaction, &trashed))
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "deleting the link", &self.path))?;
+
+        Ok(trashed)
+    }
+
+    fn trashed(&self) -> Result<Vec<TrashedLink>, ErrorReporter> {
+        self.query_trash(
+            "SELECT data FROM trash ORDER BY deleted_at DESC, rowid DESC",
+            Vec::new(),
+        )
+    }
+
+    fn restore(&mut self, id: Uuid) -> Result<Link, ErrorReporter> {
+        let trashed = self
+            .query_trash("SELECT data FROM trash WHERE id = ?1", vec![id.to_string()])?
+            .pop()
+            .ok_or_else(|| report_notfound("restoring the link"))?;
+
+        if self.get(id)?.is_some() {
+            return Err(ErrorReporterBuilder {
+                actual_error: "None",
+                why_error: vec!["A link with the same id is already saved"],
+                how_to_fix: vec!["Delete the link forever from the trash"],
+                error_title: "Link Already Exists",
+                when_error: "restoring the link",
+                error_type: ErrorType::DuplicateLink,
+            }
+            .build());
+        }
+
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "restoring the link", &self.path))?;
+
+        let link = trashed.link.normalize_tags();
+
+        transaction
+            .execute("DELETE FROM trash WHERE id = ?1", params![id.to_string()])
+            .and_then(|_| write_link(&transaction, &link, None))
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "restoring the link", &self.path))?;
+
+        Ok(link)
+    }
+
+    fn purge(&mut self, id: Uuid) -> Result<TrashedLink, ErrorReporter> {
+        let trashed = self
+            .query_trash("SELECT data FROM trash WHERE id = ?1", vec![id.to_string()])?
+            .pop()
+            .ok_or_else(|| report_notfound("deleting the link forever"))?;
+
+        self.connection
+            .execute("DELETE FROM trash WHERE id = ?1", params![id.to_string()])
+            .map_err(|err| self.report(err, "deleting the link forever"))?;
+
+        Ok(trashed)
+    }
+
+    fn replace_trash(&mut self, trash: Vec<TrashedLink>) -> Result<(), ErrorReporter> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "replacing the trash", &self.path))?;
+
+        write_trash(&transaction, &trash)
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "replacing the trash", &self.path))
+    }
+
+    fn purge_before(&mut self, before: DateTime<Utc>) -> Result<Vec<TrashedLink>, ErrorReporter> {
+        let before = before.timestamp_millis().to_string();
+
+        let purged = self.query_trash(
+            "SELECT data FROM trash WHERE deleted_at < ?1 ORDER BY deleted_at DESC, rowid DESC",
+            vec![before.clone()],
+        )?;
+
+        if !purged.is_empty() {
+            self.connection
+                .execute("DELETE FROM trash WHERE deleted_at < ?1", params![before])
+                .map_err(|err| self.report(err, "emptying the trash"))?;
+        }
+
+        Ok(purged)
+    }
+
+    fn saved_searches(&self) -> Result<Vec<SavedSearch>, ErrorReporter> {
+        let searches = self.query_sql(
+            "SELECT data FROM saved_searches ORDER BY position",
+            Vec::new(),
+        )?;
+
+        searches
+            .into_iter()
+            .map(|search| {
+                serde_json::from_value(search)
+                    .map_err(|err| self.report(err, "reading the saved searches"))
+            })
+            .collect()
+    }
+
+    fn save_search(&mut self, search: SavedSearch) -> Result<SavedSearch, ErrorReporter> {
+        // an existing search keeps its position
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO saved_searches (id, position, data) VALUES (
+                    ?1,
+                    COALESCE(
+                        (SELECT position FROM saved_searches WHERE id = ?1),
+                        (SELECT COALESCE(MAX(position), 0) + 1 FROM saved_searches)
+                    ),
+                    ?2
+                )",
+                // Serializing `SavedSearch` never fails
+                params![
+                    search.id.to_string(),
+                    serde_json::to_string(&search).unwrap()
+                ],
+            )
+            .map_err(|err| self.report(err, "saving the search"))?;
+
+        Ok(search)
+    }
+
+    fn delete_search(&mut self, id: Uuid) -> Result<SavedSearch, ErrorReporter> {
+        let search = self
+            .saved_searches()?
+            .into_iter()
+            .find(|search| search.id == id)
+            .ok_or_else(|| report_search_notfound("deleting the saved search"))?;
+
+        self.connection
+            .execute(
+                "DELETE FROM saved_searches WHERE id = ?1",
+                params![id.to_string()],
+            )
+            .map_err(|err| self.report(err, "deleting the saved search"))?;
+
+        Ok(search)
+    }
+
+    fn tag_infos(&self) -> Result<Vec<TagInfo>, ErrorReporter> {
+        let infos = self.query_sql("SELECT data FROM tag_infos ORDER BY tag", Vec::new())?;
+
+        infos
+            .into_iter()
+            .map(|info| {
+                serde_json::from_value(info)
+                    .map_err(|err| self.report(err, "reading the infos of the tags"))
+            })
+            .collect()
+    }
+
+    fn save_tag_info(&mut self, info: TagInfo) -> Result<TagInfo, ErrorReporter> {
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO tag_infos (tag, data) VALUES (?1, ?2)",
+                // Serializing `TagInfo` never fails
+                params![info.tag, serde_json::to_string(&info).unwrap()],
+            )
+            .map_err(|err| self.report(err, "saving the info of the tag"))?;
+
+        Ok(info)
+    }
+
+    fn delete_tag_info(&mut self, tag: &str) -> Result<TagInfo, ErrorReporter> {
+        let info = self
+            .tag_infos()?
+            .into_iter()
+            .find(|info| info.tag == tag)
+            .ok_or_else(|| report_tag_info_notfound("deleting the info of the tag"))?;
+
+        self.connection
+            .execute("DELETE FROM tag_infos WHERE tag = ?1", params![tag])
+            .map_err(|err| self.report(err, "deleting the info of the tag"))?;
+
+        Ok(info)
+    }
+
+    fn replace_file(&mut self, file: LinksFile) -> Result<(), ErrorReporter> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| report_database(err, "replacing the links", &self.path))?;
+
+        let write = || -> rusqlite::Result<()> {
+            write_links(&transaction, file.links)?;
+            write_trash(&transaction, &file.trash)?;
+
+            transaction.execute("DELETE FROM saved_searches", [])?;
+            for (position, search) in file.saved_searches.iter().enumerate() {
+                transaction.execute(
+                    "INSERT OR REPLACE INTO saved_searches (id, position, data) VALUES (?1, ?2, ?3)",
+                    // Serializing `SavedSearch` never fails
+                    params![
+                        search.id.to_string(),
+                        position as i64 + 1,
+                        serde_json::to_string(search).unwrap()
+                    ],
+                )?;
+            }
+
+            transaction.execute("DELETE FROM tag_infos", [])?;
+            for info in &file.tag_infos {
+                transaction.execute(
+                    "INSERT OR REPLACE INTO tag_infos (tag, data) VALUES (?1, ?2)",
+                    // Serializing `TagInfo` never fails
+                    params![info.tag, serde_json::to_string(info).unwrap()],
+                )?;
+            }
+
+            Ok(())
+        };
+
+        write()
+            .and_then(|_| transaction.commit())
+            .map_err(|err| report_database(err, "replacing the links", &self.path))?;
+
+        self.set_version()
+    }
+
+    fn query(&self, filter: &LinkFilter) -> Result<Vec<Link>, ErrorReporter> {
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+
+        if let Some(tags) = &filter.tags {
+            let mut tags = tags.clone();
+            tags.sort();
+            tags.dedup();
+
+            let tag_conditions: Vec<String> = tags
+                .into_iter()
+                .map(|tag| tag_condition(&mut params, tag))
+                .collect();
+
+            // with `MatchMode::All` the link must have every tag, or one of its child tags
+            match filter.tags_mode {
+                // no tag is selected, so no link matches
+                MatchMode::Any if tag_conditions.is_empty() => conditions.push(String::from("0")),
+                MatchMode::Any => conditions.push(format!(
+                    "id IN (SELECT link_id FROM link_tags WHERE {})",
+                    tag_conditions.join(" OR ")
+                )),
+                MatchMode::All => conditions.extend(tag_conditions.into_iter().map(|condition| {
+                    format!("id IN (SELECT link_id FROM link_tags WHERE {condition})")
+                })),
+            }
+        }
+
+        if !filter.excluded_tags.is_empty() {
+            let tag_conditions: Vec<String> = filter
+                .excluded_tags
+                .iter()
+                .map(|tag| tag_condition(&mut params, tag.clone()))
+                .collect();
+
+            conditions.push(format!(
+                "id NOT IN (SELECT link_id FROM link_tags WHERE {})",
+                tag_conditions.join(" OR ")
+            ));
+        }
+
+        if let Some(browsers) = &filter.browsers {
+            let mut browsers: Vec<String> = browsers.iter().map(browser_column).collect();
+            browsers.sort();
+            browsers.dedup();
+
+            // a link has only one browser, so it can't match all of several browsers
+            if filter.browsers_mode == MatchMode::All && browsers.len() > 1 {
+                conditions.push(String::from("0"));
+            } else {
+                conditions.push(format!(
+                    "browser IN ({})",
+                    placeholders(params.len(), browsers.len())
+                ));
+                params.extend(browsers);
+            }
+        }
+
+        if !filter.excluded_browsers.is_empty() {
+            conditions.push(format!(
+                "browser NOT IN ({})",
+                placeholders(params.len(), filter.excluded_browsers.len())
+            ));
+            params.extend(filter.excluded_browsers.iter().map(browser_column));
+        }
+
+        if let Some(priority) = filter.priority {
+            conditions.push(format!("priority = ?{}", params.len() + 1));
+            params.push(priority.to_string());
+        }
+
+        if let Some((from, to)) = filter.priority_range {
+            conditions.push(format!(
+                "priority BETWEEN ?{} AND ?{}",
+                params.len() + 1,
+                params.len() + 2
+            ));
+            params.push(from.to_string());
+            params.push(to.to_string());
+        }
+
+        let sql = if conditions.is_empty() {
+            String::from("SELECT data FROM links ORDER BY position")
+        } else {
+            format!(
+                "SELECT data FROM links WHERE {} ORDER BY position",
+                conditions.join(" AND ")
+            )
+        };
+
+        let links = self.query_sql(&sql, params)?;
+        let mut links = self.parse(links)?;
+
+        // the domain, completion, creation and opening times don't have columns, so they are checked here
+        links.retain(|link| filter.matches(link));
+
+        Ok(links)
+    }
+}
+
+/// The SQL condition of the `link_tags` table which matches the `tag` and its child tags, ignoring the case like [`Link::has_tag`]. The `tag` is added to the `params`.
+fn tag_condition(params: &mut Vec<String>, tag: String) -> String {
+    params.push(tag);
+    let i = params.len();
+
+    // `NOCASE` only ignores the case of the ASCII letters, like `Link::has_tag`
+    format!(
+        "(tag = ?{i} COLLATE NOCASE \
+        OR substr(tag, 1, length(?{i}) + 1) = (?{i} || '{TAG_SEPARATOR}') COLLATE NOCASE)"
+    )
+}
+
+/// Insert or replace the `link` and its tags. If the `position` is `None`, then the link is added at the end.
+fn write_link(
+    transaction: &Transaction,
+    link: &Link,
+    position: Option<i64>,
+) -> rusqlite::Result<()> {
+    let id = link.id.unwrap_or_default().to_string();
+
+    let position = match position {
+        Some(position) => position,
+        None => transaction.query_row(
+            "SELECT COALESCE(MAX(position), 0) + 1 FROM links",
+            [],
+            |row| row.get(0),
+        )?,
+    };
+
+    transaction.execute(
+        "INSERT OR REPLACE INTO links (id, position, priority, browser, data) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            id,
+            position,
+            link.priority.to_string(),
+            browser_column(&link.browser),
+            // Serializing `Link` never fails
+            serde_json::to_string(link).unwrap(),
+        ],
+    )?;
+
+    transaction.execute("DELETE FROM link_tags WHERE link_id = ?1", params![id])?;
+
+    for tag in &link.tags {
+        transaction.execute(
+            "INSERT OR IGNORE INTO link_tags (link_id, tag) VALUES (?1, ?2)",
+            params![id, tag],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Remove all links and add the `links` instead. Links without an id get a new one.
+fn write_links(transaction: &Transaction, links: Vec<Link>) -> rusqlite::Result<()> {
+    transaction.execute("DELETE FROM links", [])?;
+
+    for mut link in links {
+        link.id.get_or_insert_with(Uuid::new_v4);
+        write_link(transaction, &link, None)?;
+    }
+
+    Ok(())
+}
+
+/// Remove all links from the trash and add the `trash` instead
+fn write_trash(transaction: &Transaction, trash: &[TrashedLink]) -> rusqlite::Result<()> {
+    transaction.execute("DELETE FROM trash", [])?;
+
+    // the oldest first, so that the links deleted at the same time keep their order
+    for trashed in trash.iter().rev() {
+        write_trashed(transaction, trashed)?;
+    }
+
+    Ok(())
+}
+
+/// Insert or replace the `trashed` link inside the trash
+fn write_trashed(transaction: &Transaction, trashed: &TrashedLink) -> rusqlite::Result<()> {
+    transaction.execute(
+        "INSERT OR REPLACE INTO trash (id, deleted_at, data) VALUES (?1, ?2, ?3)",
+        params![
+            trashed.link.id.unwrap_or_default().to_string(),
+            trashed.deleted_at.timestamp_millis(),
+            // Serializing `TrashedLink` never fails
+            serde_json::to_string(trashed).unwrap(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Value of the `browser` column, same as the JSON of the browser
+fn browser_column(browser: &Browser) -> String {
+    serde_json::to_value(browser)
+        .ok()
+        .and_then(|value| value.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+/// Numbered placeholders (`?3, ?4`) for `count` parameters after the first `skip` parameters
+fn placeholders(skip: usize, count: usize) -> String {
+    (skip + 1..=skip + count)
+        .map(|index| format!("?{index}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Create a report for failing to use the database
+fn report_database<E: std::fmt::Display>(
+    actual_error: E,
+    when: &str,
+    path: &Path,
+) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: &actual_error.to_string(),
+        why_error: vec![
+            "You don't have the permission to access the database",
+            "The database is used by another program",
+            "The database is corrupted",
+        ],
+        how_to_fix: vec![
+            &format!("Check the permissions of {}", path.display()),
+            "Close other programs who are using the database",
+            "Switch to the JSON storage in the settings",
+        ],
+        error_title: "Database Error",
+        when_error: &format!("{when} in {}", path.display()),
+        error_type: ErrorType::Database,
+    }
+    .build()
+}
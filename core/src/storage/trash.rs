@@ -0,0 +1,95 @@
+use super::Storage;
+use crate::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How many days a link stays in the trash by default, before it is deleted forever.
+pub const TRASH_RETENTION_DAYS: u32 = 30;
+
+/// The most days a link can stay in the trash, see [`Settings::trash_retention_days`](crate::settings::Settings::trash_retention_days). A longer retention is saved as this.
+pub const MAX_TRASH_RETENTION_DAYS: u32 = 36_500;
+
+/// A deleted link inside the trash.
+///
+/// Deleting a link only moves it into the trash with [`Storage::trash`]. It can be brought back with [`Storage::restore`] until it is deleted forever with [`Storage::purge`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TrashedLink {
+    /// The link as it was before deleting it
+    pub link: Link,
+    /// When the link was moved into the trash
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashedLink {
+    /// Put the `link` into the trash now.
+    pub fn new(link: Link) -> Self {
+        Self {
+            link,
+            deleted_at: Utc::now(),
+        }
+    }
+
+    /// When the link will be deleted forever if the links are kept in the trash for `retention_days`.
+    ///
+    /// It returns `None` if `retention_days` is `0`, which means the links are kept forever. It is also `None` if the date would be too far in the future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use dsaver_core::prelude::*;
+    /// # use dsaver_core::storage::TrashedLink;
+    /// # use chrono::Duration;
+    /// #
+    /// let trashed = TrashedLink::new(Link::new("https://www.rust-lang.org"));
+    ///
+    /// assert_eq!(trashed.expires_at(30), Some(trashed.deleted_at + Duration::days(30)));
+    /// assert_eq!(trashed.expires_at(0), None);
+    /// assert_eq!(trashed.expires_at(u32::MAX), None);
+    /// ```
+    pub fn expires_at(&self, retention_days: u32) -> Option<DateTime<Utc>> {
+        if retention_days == 0 {
+            return None;
+        }
+
+        self.deleted_at
+            .checked_add_signed(Duration::days(retention_days.into()))
+    }
+}
+
+/// Delete forever the links which were in the trash for more than `retention_days`.
+///
+/// If `retention_days` is `0`, or so long that no link can be that old, then nothing is deleted. It returns the deleted links.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::storage::{purge_expired, LinkStore, Storage};
+///
+/// let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+/// let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+///
+/// let link = Storage::insert(&mut storage, Link::new("https://www.rust-lang.org")).unwrap();
+/// Storage::trash(&mut storage, link.id.unwrap()).unwrap();
+///
+/// // it was deleted just now
+/// assert!(purge_expired(&mut storage, 30).unwrap().is_empty());
+/// assert_eq!(Storage::trashed(&storage).unwrap().len(), 1);
+///
+/// // it never expires
+/// assert!(purge_expired(&mut storage, u32::MAX).unwrap().is_empty());
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn purge_expired(
+    storage: &mut dyn Storage,
+    retention_days: u32,
+) -> Result<Vec<TrashedLink>, ErrorReporter> {
+    if retention_days == 0 {
+        return Ok(Vec::new());
+    }
+
+    match Utc::now().checked_sub_signed(Duration::days(retention_days.into())) {
+        Some(before) => storage.purge_before(before),
+        None => Ok(Vec::new()),
+    }
+}
@@ -0,0 +1,519 @@
+//! Managing the tags of all the links at once.
+//!
+//! Tags can be nested like paths, e.g. `dev/rust/async` is a child of `dev/rust`, which is a child of `dev`. A link which has a child tag also belongs to all its parent tags, see [`is_within`] and [`tag_tree`].
+//!
+//! The functions which change the tags the links in the [`Storage`] and returns the [`Change`] which was made, so that it can be recorded in the [`Journal`](crate::journal::Journal) and undone as a single change.
+
+use crate::journal::Change;
+use crate::prelude::*;
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// The separator between the parts of a nested tag, like `dev/rust/async`
+pub const TAG_SEPARATOR: char = '/';
+
+/// The color, icon and description of a tag, chosen by the user
+///
+/// It is stored with the links (see [`Storage::tag_infos`]), and it is moved with the tag when the tag is renamed or merged.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::tag::TagInfo;
+///
+/// let info = TagInfo::new("dev/rust")
+///     .color("#dea584")
+///     .icon("🦀")
+///     .description("Everything about the Rust language");
+///
+/// assert!(info.validate().is_ok());
+/// assert!(TagInfo::new("Rust").color("orange").validate().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagInfo {
+    /// The tag, e.g. `dev/rust`
+    pub tag: String,
+    /// A hex color like `#dea584`, used as the background of the tag
+    pub color: Option<String>,
+    /// An emoji (or a short text) shown before the tag
+    pub icon: Option<String>,
+    /// What the tag is used for, shown when hovering the tag
+    pub description: Option<String>,
+}
+
+impl TagInfo {
+    /// Create an info of the `tag` without any color, icon or description.
+    pub fn new<T: ToString>(tag: T) -> Self {
+        Self {
+            tag: tag.to_string(),
+            color: None,
+            icon: None,
+            description: None,
+        }
+    }
+
+    /// change the `color` of the tag
+    pub fn color<T: ToString>(mut self, color: T) -> Self {
+        self.color = Some(color.to_string());
+        self
+    }
+
+    /// change the `icon` of the tag
+    pub fn icon<T: ToString>(mut self, icon: T) -> Self {
+        self.icon = Some(icon.to_string());
+        self
+    }
+
+    /// change the `description` of the tag
+    pub fn description<T: ToString>(mut self, description: T) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Whether the tag has no color, icon or description, so that the info doesn't need to be stored
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.icon.is_none() && self.description.is_none()
+    }
+
+    /// Check the tag (see [`validate_tag`]) and the color. The color must be like `#dea584` or `#fa8`.
+    ///
+    /// It will return an error with [`ErrorType::InvalidTag`] if any of them is not valid.
+    pub fn validate(&self) -> Result<(), ErrorReporter> {
+        let when = format!("saving the info of the tag {}", self.tag);
+        validate_tag(&self.tag, &when)?;
+
+        if let Some(color) = &self.color {
+            let valid = match color.strip_prefix('#') {
+                Some(hex) => {
+                    matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+                }
+                None => false,
+            };
+
+            if !valid {
+                return Err(report_invalid(
+                    &when,
+                    "The color of a tag must be a hex color",
+                    "Use a color like `#dea584`",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A tag inside the tree of nested tags, see [`tag_tree`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagNode {
+    /// The last part of the tag, e.g. `async` for `dev/rust/async`
+    pub name: String,
+    /// The whole tag, e.g. `dev/rust/async`
+    pub path: String,
+    /// How many links have the tag or any of its child tags
+    pub count: usize,
+    /// How many of those links are complete
+    pub complete: usize,
+    /// The child tags, sorted by their name
+    pub children: Vec<TagNode>,
+}
+
+/// Rename the tag `from` to `to` in every link which has it. The child tags are moved too, e.g. `dev/rust` becomes `code/rust` when `dev` is renamed to `code`. So are their [`TagInfo`]s.
+///
+/// It will return an error with [`ErrorType::TagNotFound`] if no link has the tag `from`, and [`ErrorType::InvalidTag`] if `to` is not a valid tag (see [`validate_tag`]) or some links already have it. Use [`merge_tags`] to join two existing tags.
+///
+/// The `updated_at` of the links is not changed.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::storage::{LinkStore, Storage};
+/// use dsaver_core::tag::rename_tag;
+///
+/// let path = std::env::temp_dir().join(format!("dsaver-{}.json", uuid::Uuid::new_v4()));
+/// let mut storage = LinkStore::new(&path);
+/// Storage::insert(&mut storage, Link::new("https://www.rust-lang.org").tags("Rust Code")).unwrap();
+///
+/// rename_tag(&mut storage, "Code", "Programming").unwrap();
+///
+/// assert_eq!(storage.links()[0].tags, vec!["Rust", "Programming"]);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn rename_tag(
+    storage: &mut dyn Storage,
+    from: &str,
+    to: &str,
+) -> Result<Change, ErrorReporter> {
+    let when = format!("renaming the tag {from} to {to}");
+    validate_tag(to, &when)?;
+
+    let links = storage.links()?;
+    if has_tag(&links, to) {
+        return Err(report_invalid(
+            &when,
+            "Some links already have the new name of the tag",
+            "Merge the tags instead of renaming",
+        ));
+    }
+
+    let (before, after) = retag(links, from, Some(to), &when)?;
+    let (infos_before, infos_after) = move_infos(storage, from, Some(to))?;
+    let after = storage.update_tags(after, &infos_before, &infos_after)?;
+
+    Ok(Change::TagRenamed {
+        from: from.to_string(),
+        to: to.to_string(),
+        before,
+        after,
+        infos_before,
+        infos_after,
+    })
+}
+
+/// Replace the tag `from` with the tag `into` in every link which has it. A link which has both tags keeps only `into`. The child tags are moved too, e.g. `old/rust` becomes `dev/rust` when `old` is merged into `dev`. So are their [`TagInfo`]s, but a tag which already has an info keeps it.
+///
+/// It will return an error with [`ErrorType::TagNotFound`] if no link has one of the tags, and [`ErrorType::InvalidTag`] if `into` is the same tag or one of its child tags.
+///
+/// The `updated_at` of the links is not changed.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::storage::{LinkStore, Storage};
+/// use dsaver_core::tag::merge_tags;
+///
+/// let path = std::env::temp_dir().join(format!("dsaver-{}.json", uuid::Uuid::new_v4()));
+/// let mut storage = LinkStore::new(&path);
+/// Storage::insert(&mut storage, Link::new("https://www.rust-lang.org").tags("Rust RustLang")).unwrap();
+/// Storage::insert(&mut storage, Link::new("https://docs.rs").tags("RustLang Docs")).unwrap();
+///
+/// merge_tags(&mut storage, "RustLang", "Rust").unwrap();
+///
+/// assert_eq!(storage.links()[0].tags, vec!["Rust"]);
+/// assert_eq!(storage.links()[1].tags, vec!["Rust", "Docs"]);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn merge_tags(
+    storage: &mut dyn Storage,
+    from: &str,
+    into: &str,
+) -> Result<Change, ErrorReporter> {
+    let when = format!("merging the tag {from} into {into}");
+
+    if is_within(into, from) {
+        return Err(report_invalid(
+            &when,
+            "A tag can't be merged into itself or one of its child tags",
+            "Choose another tag",
+        ));
+    }
+
+    let links = storage.links()?;
+    if !has_tag(&links, into) {
+        return Err(report_tag_notfound(&when));
+    }
+
+    let (before, after) = retag(links, from, Some(into), &when)?;
+    let (infos_before, infos_after) = move_infos(storage, from, Some(into))?;
+    let after = storage.update_tags(after, &infos_before, &infos_after)?;
+
+    Ok(Change::TagsMerged {
+        from: from.to_string(),
+        into: into.to_string(),
+        before,
+        after,
+        infos_before,
+        infos_after,
+    })
+}
+
+/// Remove the `tag` and its child tags from every link which has them, and remove their [`TagInfo`]s. The links themselves are not deleted.
+///
+/// It will return an error with [`ErrorType::TagNotFound`] if no link has the tag.
+///
+/// The `updated_at` of the links is not changed.
+pub fn delete_tag(storage: &mut dyn Storage, tag: &str) -> Result<Change, ErrorReporter> {
+    let links = storage.links()?;
+    let (before, after) = retag(links, tag, None, &format!("deleting the tag {tag}"))?;
+    let (infos_before, infos_after) = move_infos(storage, tag, None)?;
+    let after = storage.update_tags(after, &infos_before, &infos_after)?;
+
+    Ok(Change::TagDeleted {
+        tag: tag.to_string(),
+        before,
+        after,
+        infos_before,
+        infos_after,
+    })
+}
+
+/// Check that the `tag` can be used as a tag. The tags are separated by spaces, so a tag can't be empty or contain spaces. It also must be normalized, see [`normalize_tag`].
+///
+/// It will return an error with [`ErrorType::InvalidTag`] if the tag is not valid. The `when` is shown inside the error.
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::tag::validate_tag;
+/// #
+/// assert!(validate_tag("Rust", "renaming the tag").is_ok());
+/// assert!(validate_tag("Rust Lang", "renaming the tag").is_err());
+/// assert!(validate_tag("", "renaming the tag").is_err());
+/// assert!(validate_tag("dev/rust", "renaming the tag").is_ok());
+/// assert!(validate_tag("dev//rust/", "renaming the tag").is_err());
+/// ```
+pub fn validate_tag(tag: &str, when: &str) -> Result<(), ErrorReporter> {
+    if tag.is_empty() || tag.contains(char::is_whitespace) {
+        return Err(report_invalid(
+            when,
+            "A tag can't be empty or contain spaces",
+            "Use another name, e.g. with `-` or `_` instead of spaces",
+        ));
+    }
+
+    if normalize_tag(tag) != tag {
+        return Err(report_invalid(
+            when,
+            "The parts of a tag must be separated by a single `/`",
+            "Remove the empty parts, e.g. use `dev/rust` instead of `dev//rust/`",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Normalize the separators of a nested tag: `\` is replaced with `/`, and the empty parts are removed.
+///
+/// It returns an empty string if the tag has no parts.
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::tag::normalize_tag;
+/// #
+/// assert_eq!(normalize_tag("dev/rust/async"), "dev/rust/async");
+/// assert_eq!(normalize_tag("/dev\\rust//async/"), "dev/rust/async");
+/// assert_eq!(normalize_tag("//"), "");
+/// ```
+pub fn normalize_tag(tag: &str) -> String {
+    tag.split([TAG_SEPARATOR, '\\'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(&TAG_SEPARATOR.to_string())
+}
+
+/// Whether the `tag` is the tag `parent` or one of its child tags
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::tag::is_within;
+/// #
+/// assert!(is_within("dev/rust/async", "dev/rust"));
+/// assert!(is_within("dev/rust", "dev/rust"));
+/// assert!(!is_within("dev/rustlang", "dev/rust"));
+/// assert!(!is_within("dev", "dev/rust"));
+/// ```
+pub fn is_within(tag: &str, parent: &str) -> bool {
+    match tag.strip_prefix(parent) {
+        Some(rest) => rest.is_empty() || rest.starts_with(TAG_SEPARATOR),
+        None => false,
+    }
+}
+
+/// The parent tag of the `tag`, e.g. `dev/rust` for `dev/rust/async`. It is `None` for a top level tag.
+pub fn parent_tag(tag: &str) -> Option<&str> {
+    tag.rsplit_once(TAG_SEPARATOR).map(|(parent, _)| parent)
+}
+
+/// All the parent tags of the `tag` from the top, followed by the `tag` itself
+///
+/// # Example
+///
+/// ```
+/// # use dsaver_core::tag::ancestors;
+/// #
+/// assert_eq!(ancestors("dev/rust/async"), vec!["dev", "dev/rust", "dev/rust/async"]);
+/// assert_eq!(ancestors("Rust"), vec!["Rust"]);
+/// ```
+pub fn ancestors(tag: &str) -> Vec<&str> {
+    tag.match_indices(TAG_SEPARATOR)
+        .map(|(i, _)| &tag[..i])
+        .chain(std::iter::once(tag))
+        .collect()
+}
+
+/// Build the tree of the nested tags of the `links`. The top level tags are sorted by their name.
+///
+/// The counts of a tag include the links of its child tags, but every link is counted only once.
+///
+/// # Example
+///
+/// ```
+/// use dsaver_core::prelude::*;
+/// use dsaver_core::tag::tag_tree;
+///
+/// let links = vec![
+///     Link::new("https://tokio.rs").tags("dev/rust/async"),
+///     Link::new("https://www.rust-lang.org").tags("dev/rust dev/rust/async"),
+///     Link::new("https://www.python.org").tags("dev/python"),
+/// ];
+///
+/// let tree = tag_tree(&links);
+///
+/// assert_eq!(tree.len(), 1);
+/// assert_eq!((tree[0].path.as_str(), tree[0].count), ("dev", 3));
+///
+/// let rust = &tree[0].children[1];
+/// assert_eq!((rust.name.as_str(), rust.count), ("rust", 2));
+/// assert_eq!(rust.children[0].path, "dev/rust/async");
+/// ```
+pub fn tag_tree(links: &[Link]) -> Vec<TagNode> {
+    // (count, complete) of every tag and parent tag
+    let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+
+    for link in links {
+        let paths: HashSet<&str> = link.tags.iter().flat_map(|tag| ancestors(tag)).collect();
+
+        for path in paths {
+            let (count, complete) = counts.entry(path).or_default();
+            *count += 1;
+            if link.complete {
+                *complete += 1;
+            }
+        }
+    }
+
+    tag_nodes(&counts, None)
+}
+
+/// The nodes of the tags whose parent tag is `parent`
+fn tag_nodes(counts: &BTreeMap<&str, (usize, usize)>, parent: Option<&str>) -> Vec<TagNode> {
+    counts
+        .iter()
+        .filter(|(path, _)| parent_tag(path) == parent)
+        .map(|(path, &(count, complete))| TagNode {
+            name: path
+                .rsplit(TAG_SEPARATOR)
+                .next()
+                .unwrap_or(path)
+                .to_string(),
+            path: path.to_string(),
+            count,
+            complete,
+            children: tag_nodes(counts, Some(path)),
+        })
+        .collect()
+}
+
+/// Replace the tag `from` with `to` (or remove it if `to` is `None`) in the `links` which have it or its child tags. The child tags are moved under `to`.
+///
+/// It returns the changed links before and after the change. Nothing is saved, save them with [`Storage::update_tags`].
+fn retag(
+    links: Vec<Link>,
+    from: &str,
+    to: Option<&str>,
+    when: &str,
+) -> Result<(Vec<Link>, Vec<Link>), ErrorReporter> {
+    let before: Vec<Link> = links
+        .into_iter()
+        .filter(|link| link.tags.iter().any(|tag| is_within(tag, from)))
+        .collect();
+
+    if before.is_empty() {
+        return Err(report_tag_notfound(when));
+    }
+
+    let after = before
+        .iter()
+        .map(|link| {
+            let mut seen = HashSet::new();
+            let mut link = link.clone();
+
+            link.tags = link
+                .tags
+                .iter()
+                .filter_map(|tag| match tag.strip_prefix(from) {
+                    // `from` itself or one of its child tags
+                    Some(rest) if is_within(tag, from) => to.map(|to| format!("{to}{rest}")),
+                    _ => Some(tag.clone()),
+                })
+                .filter(|tag| seen.insert(tag.clone()))
+                .collect();
+
+            link
+        })
+        .collect();
+
+    Ok((before, after))
+}
+
+/// Move the infos of the tag `from` and its child tags to the tag `to` (or remove them if `to` is `None`). The tags inside `to` keep their own infos.
+///
+/// It returns the infos of all the tags inside `from` and `to`, before and after moving. Nothing is saved, save them with [`Storage::update_tags`].
+fn move_infos(
+    storage: &dyn Storage,
+    from: &str,
+    to: Option<&str>,
+) -> Result<(Vec<TagInfo>, Vec<TagInfo>), ErrorReporter> {
+    let inside_to = |tag: &str| matches!(to, Some(to) if is_within(tag, to));
+
+    let before: Vec<TagInfo> = storage
+        .tag_infos()?
+        .into_iter()
+        .filter(|info| is_within(&info.tag, from) || inside_to(&info.tag))
+        .collect();
+
+    let (mut after, moved): (Vec<_>, Vec<_>) = before
+        .iter()
+        .cloned()
+        .partition(|info| inside_to(&info.tag));
+
+    if let Some(to) = to {
+        for info in moved {
+            let tag = format!("{to}{}", &info.tag[from.len()..]);
+
+            if !after.iter().any(|kept| kept.tag == tag) {
+                after.push(TagInfo { tag, ..info });
+            }
+        }
+    }
+
+    Ok((before, after))
+}
+
+/// Whether any of the `links` has the `tag` or one of its child tags
+fn has_tag(links: &[Link], tag: &str) -> bool {
+    links
+        .iter()
+        .any(|link| link.tags.iter().any(|t| is_within(t, tag)))
+}
+
+/// Create a report for an invalid tag
+fn report_invalid(when: &str, why: &str, how_to_fix: &str) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: "None",
+        why_error: vec![why],
+        how_to_fix: vec![how_to_fix],
+        error_title: "Invalid Tag",
+        when_error: when,
+        error_type: ErrorType::InvalidTag,
+    }
+    .build()
+}
+
+/// Create a report for not finding a tag
+fn report_tag_notfound(when: &str) -> ErrorReporter {
+    ErrorReporterBuilder {
+        actual_error: "None",
+        why_error: vec!["No link has the tag"],
+        how_to_fix: vec!["Restart the app to see the latest tags"],
+        error_title: "Tag Not Found",
+        when_error: when,
+        error_type: ErrorType::TagNotFound,
+    }
+    .build()
+}
@@ -0,0 +1,122 @@
+use dsaver_core::check::{CheckQueue, LinkCheck, Next};
+use dsaver_core::prelude::*;
+use std::time::{Duration, Instant};
+
+/// Check every link of the `queue` with a single worker, which takes `took` for every link.
+///
+/// It returns the urls in the order they are checked, and how long it took.
+fn run(mut queue: CheckQueue, took: Duration) -> (Vec<String>, Duration) {
+    let start = Instant::now();
+    let mut now = start;
+    let mut urls = Vec::new();
+
+    loop {
+        match queue.next(now) {
+            Next::Check(link) => {
+                now += took;
+                queue.finish(&link, now);
+                urls.push(link.url);
+            }
+            Next::Wait(wait) => {
+                assert!(wait > Duration::ZERO);
+                now += wait;
+            }
+            Next::Done => return (urls, now - start),
+        }
+    }
+}
+
+#[test]
+fn checks_one_link_of_a_domain_at_a_time() {
+    let links = vec![
+        Link::new("https://github.com/a"),
+        Link::new("https://www.github.com/b"),
+        Link::new("https://docs.rs/c"),
+        Link::new("https://github.com/d"),
+    ];
+    let mut queue = CheckQueue::new(links.clone(), Duration::from_secs(1));
+    assert_eq!(queue.len(), 4);
+
+    let now = Instant::now();
+    assert_eq!(queue.next(now), Next::Check(links[0].clone()));
+    assert_eq!(queue.next(now), Next::Check(links[2].clone()));
+    // `www.github.com` is the same domain as `github.com`
+    assert_eq!(queue.next(now), Next::Wait(Duration::from_secs(1)));
+
+    queue.finish(&links[0], now);
+    assert_eq!(
+        queue.next(now + Duration::from_millis(400)),
+        Next::Wait(Duration::from_millis(600))
+    );
+    assert_eq!(
+        queue.next(now + Duration::from_secs(1)),
+        Next::Check(links[1].clone())
+    );
+    assert_eq!(queue.len(), 1);
+    assert!(!queue.is_empty());
+}
+
+#[test]
+fn waits_between_the_links_of_a_domain() {
+    let links = vec![
+        Link::new("https://github.com/a"),
+        Link::new("https://github.com/b"),
+        Link::new("https://docs.rs/c"),
+        Link::new("https://github.com/d"),
+        Link::new("not a url"),
+    ];
+
+    let (urls, took) = run(
+        CheckQueue::new(links, Duration::from_secs(1)),
+        Duration::from_millis(100),
+    );
+    assert_eq!(
+        urls,
+        vec![
+            "https://github.com/a",
+            "https://docs.rs/c",
+            "not a url",
+            "https://github.com/b",
+            "https://github.com/d",
+        ]
+    );
+    // the github links are checked at 0s, 1.1s and 2.2s
+    assert_eq!(took, Duration::from_millis(2300));
+
+    let (urls, _) = run(
+        CheckQueue::new(Vec::new(), Duration::from_secs(1)),
+        Duration::ZERO,
+    );
+    assert!(urls.is_empty());
+}
+
+#[test]
+fn finds_broken_links() {
+    let check = |status: Option<u32>| {
+        let mut check = LinkCheck::new("https://example.com");
+        check.status = status;
+        check.is_broken()
+    };
+
+    assert!(!check(Some(200)));
+    assert!(!check(Some(304)));
+    assert!(check(Some(404)));
+    assert!(check(Some(503)));
+    // the website refuses the checker or asks it to slow down
+    assert!(!check(Some(403)));
+    assert!(!check(Some(405)));
+    assert!(!check(Some(429)));
+    // the user may be offline
+    assert!(!check(None));
+
+    let check = LinkCheck::new("https://example.com").error("Timeout was reached");
+    assert!(!check.is_broken());
+    assert!(check.is_inconclusive());
+
+    let check = LinkCheck::new("https://example.com").unreachable("Couldn't connect to server");
+    assert!(check.is_broken());
+    assert!(!check.is_inconclusive());
+
+    let check = LinkCheck::new("https://example.com").redirect("https://example.com");
+    assert_eq!(check.redirect, None);
+}
@@ -0,0 +1,9 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// A new directory inside the temporary directory
+pub fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
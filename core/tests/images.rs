@@ -0,0 +1,109 @@
+use dsaver_core::metadata::PageMetadata;
+use dsaver_core::prelude::*;
+use dsaver_core::storage::{image_key, ImageCache, IMAGES_INDEX};
+use std::fs;
+use std::path::PathBuf;
+
+mod common;
+use common::temp_dir;
+
+fn link_with_images(favicon: &str, image: Option<&str>) -> Link {
+    let mut link = Link::new("https://example.com");
+    link.metadata = PageMetadata {
+        favicon: Some(favicon.into()),
+        image: image.map(String::from),
+        ..Default::default()
+    };
+    link
+}
+
+/// Names of the files inside the `dir`, without the index
+fn files(dir: &PathBuf) -> Vec<String> {
+    let mut files = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name != IMAGES_INDEX)
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+#[test]
+fn stores_the_same_image_once() {
+    let dir = temp_dir();
+    let mut cache = ImageCache::open(&dir).unwrap();
+
+    cache
+        .insert("https://a.com/favicon.ico", "image/png", b"same")
+        .unwrap();
+    cache
+        .insert("https://b.com/favicon.ico", "image/png", b"same")
+        .unwrap();
+    cache
+        .insert("https://a.com/og.jpg", "image/jpeg", b"other")
+        .unwrap();
+    assert_eq!(files(&dir).len(), 2);
+
+    // the index is kept
+    let cache = ImageCache::open(&dir).unwrap();
+    assert!(cache.contains("https://b.com/favicon.ico"));
+    assert!(!cache.contains("https://c.com/favicon.ico"));
+    assert_eq!(cache.images().count(), 3);
+
+    let (image, data) = cache
+        .read(&image_key("https://a.com/og.jpg"))
+        .unwrap()
+        .unwrap();
+    assert!(files(&dir).contains(&image.file));
+    assert!(image.file.ends_with(".jpg"));
+    assert_eq!(data, b"other");
+    assert_eq!(cache.read(&image_key("https://c.com")).unwrap(), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn collects_the_images_of_deleted_links() {
+    let dir = temp_dir();
+    let mut cache = ImageCache::open(&dir).unwrap();
+
+    cache
+        .insert("https://a.com/favicon.ico", "image/x-icon", b"a")
+        .unwrap();
+    cache
+        .insert("https://a.com/og.png", "image/png", b"og")
+        .unwrap();
+    cache
+        .insert("https://b.com/favicon.ico", "image/x-icon", b"b")
+        .unwrap();
+    // left behind by a crash
+    fs::write(dir.join("links.json.tmp"), "").unwrap();
+
+    let links = vec![
+        link_with_images("https://a.com/favicon.ico", None),
+        link_with_images("https://b.com/favicon.ico", None),
+    ];
+    assert_eq!(cache.collect_garbage(&links).unwrap(), 2);
+    assert!(!cache.contains("https://a.com/og.png"));
+    assert_eq!(files(&dir).len(), 2);
+
+    // the removed images are not in the index anymore
+    let mut cache = ImageCache::open(&dir).unwrap();
+    assert_eq!(cache.images().count(), 2);
+
+    assert_eq!(cache.collect_garbage(&links[..1]).unwrap(), 1);
+    assert_eq!(cache.collect_garbage(&links[..1]).unwrap(), 0);
+    assert!(cache.contains("https://a.com/favicon.ico"));
+
+    // a corrupted index is a new cache
+    fs::write(dir.join(IMAGES_INDEX), "{").unwrap();
+    let mut cache = ImageCache::open(&dir).unwrap();
+    assert_eq!(cache.images().count(), 0);
+    assert_eq!(cache.collect_garbage(&links).unwrap(), 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+
+    // nothing is cached yet
+    let mut cache = ImageCache::open(&dir).unwrap();
+    assert_eq!(cache.collect_garbage(&links).unwrap(), 0);
+}
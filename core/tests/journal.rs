@@ -0,0 +1,313 @@
+use dsaver_core::check::LinkCheck;
+use dsaver_core::journal::{Change, EntryKind, Journal, JournalEntry, MAX_JOURNAL_ENTRIES};
+use dsaver_core::prelude::*;
+use dsaver_core::storage::{LinkStore, Storage};
+use std::fs;
+
+mod common;
+use common::temp_dir;
+
+fn titles(storage: &dyn Storage) -> Vec<String> {
+    storage
+        .links()
+        .unwrap()
+        .into_iter()
+        .map(|link| link.title.unwrap())
+        .collect()
+}
+
+#[test]
+fn undoes_and_redoes_changes() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+
+    assert_eq!(journal.undo(storage).unwrap(), None);
+
+    // create, edit and delete
+    let rust = storage
+        .insert(Link::new("https://www.rust-lang.org").title("Rust"))
+        .unwrap();
+    journal.record(Change::Created(rust.clone())).unwrap();
+
+    let python = storage
+        .insert(Link::new("https://www.python.org").title("Python"))
+        .unwrap();
+    journal.record(Change::Created(python.clone())).unwrap();
+
+    let edited = storage.update(rust.clone().title("Rust Lang")).unwrap();
+    journal
+        .record(Change::Edited {
+            before: rust.clone(),
+            after: edited.clone(),
+        })
+        .unwrap();
+
+    let deleted = storage.delete(python.id.unwrap()).unwrap();
+    journal.record(Change::Deleted(deleted)).unwrap();
+    assert_eq!(titles(storage), vec!["Rust Lang"]);
+
+    // opening and checking are not changes of the user, so undoing the edit keeps them
+    let mut opened = edited.clone();
+    opened.opened(Browser::Firefox);
+    opened.check = Some(LinkCheck::new(&opened.url).status(200));
+    storage.update(opened).unwrap();
+
+    // undo the delete and the edit
+    assert!(matches!(
+        journal.undo(storage).unwrap(),
+        Some(Change::Deleted(_))
+    ));
+    assert_eq!(titles(storage), vec!["Rust Lang", "Python"]);
+
+    assert!(matches!(
+        journal.undo(storage).unwrap(),
+        Some(Change::Edited { .. })
+    ));
+    assert_eq!(titles(storage), vec!["Rust", "Python"]);
+    let undone = storage.get(rust.id.unwrap()).unwrap().unwrap();
+    assert_eq!(undone.open_count, 1);
+    assert_eq!(undone.check.unwrap().status, Some(200));
+
+    // redo the edit
+    assert!(matches!(
+        journal.redo(storage).unwrap(),
+        Some(Change::Edited { .. })
+    ));
+    assert_eq!(titles(storage), vec!["Rust Lang", "Python"]);
+
+    // a new change can't be redone after undoing it, and the old undone changes can't be redone anymore
+    let docs = storage
+        .insert(Link::new("https://docs.rs").title("Docs"))
+        .unwrap();
+    journal.record(Change::Created(docs)).unwrap();
+    assert_eq!(journal.next_redo(), None);
+
+    // the journal is the same after opening it again
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+    assert_eq!(
+        journal
+            .entries()
+            .iter()
+            .map(|entry| entry.kind)
+            .collect::<Vec<_>>(),
+        vec![
+            EntryKind::Done,
+            EntryKind::Done,
+            EntryKind::Done,
+            EntryKind::Done,
+            EntryKind::Undone,
+            EntryKind::Undone,
+            EntryKind::Redone,
+            EntryKind::Done,
+        ]
+    );
+
+    // undo everything, the deleted link was already restored
+    while journal.undo(storage).unwrap().is_some() {}
+    assert!(titles(storage).is_empty());
+    assert!(matches!(journal.next_redo(), Some(Change::Created(link)) if link.url == rust.url));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn undoes_renamed_tags() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+
+    let before = vec![
+        storage
+            .insert(Link::new("https://www.rust-lang.org").tags("Rust Code"))
+            .unwrap(),
+        storage
+            .insert(Link::new("https://docs.rs").tags("Rust"))
+            .unwrap(),
+    ];
+    let after: Vec<Link> = before
+        .iter()
+        .map(|link| {
+            let tags = link.tags.join(" ").replace("Rust", "RustLang");
+            storage.update(link.clone().tags(tags)).unwrap()
+        })
+        .collect();
+
+    journal
+        .record(Change::TagRenamed {
+            from: "Rust".to_string(),
+            to: "RustLang".to_string(),
+            before: before.clone(),
+            after,
+            infos_before: Vec::new(),
+            infos_after: Vec::new(),
+        })
+        .unwrap();
+
+    journal.undo(storage).unwrap();
+    assert_eq!(storage.links().unwrap(), before);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn undoes_trashed_links() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+
+    let rust = storage
+        .insert(Link::new("https://www.rust-lang.org").title("Rust"))
+        .unwrap();
+    let trashed = storage.trash(rust.id.unwrap()).unwrap();
+    journal.record(Change::Trashed(trashed.link)).unwrap();
+
+    // undoing restores the link from the trash
+    journal.undo(storage).unwrap();
+    assert_eq!(titles(storage), vec!["Rust"]);
+    assert!(storage.trashed().unwrap().is_empty());
+
+    journal.redo(storage).unwrap();
+    assert!(titles(storage).is_empty());
+    assert_eq!(storage.trashed().unwrap()[0].link, rust);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn drops_torn_last_entry() {
+    let dir = temp_dir();
+    let path = dir.join("journal.jsonl");
+    let link = Link::new("https://www.rust-lang.org").title("Rust");
+
+    let mut journal = Journal::open(&path).unwrap();
+    journal.record(Change::Created(link.clone())).unwrap();
+
+    // the app stopped while writing the second entry
+    let written = fs::read_to_string(&path).unwrap();
+    fs::write(&path, format!("{written}{{\"id\":")).unwrap();
+
+    let mut journal = Journal::open(&path).unwrap();
+    assert_eq!(journal.entries().len(), 1);
+    assert_eq!(journal.next_undo(), Some(&Change::Created(link.clone())));
+
+    // the next entry is not appended to the torn line
+    journal.record(Change::Deleted(link)).unwrap();
+    assert_eq!(Journal::open(&path).unwrap().entries().len(), 2);
+
+    // a broken line in the middle is not torn, the journal is corrupted
+    let written = fs::read_to_string(&path).unwrap();
+    fs::write(&path, format!("{{\"id\":\n{written}")).unwrap();
+
+    let err = Journal::open(&path).unwrap_err();
+    assert_eq!(err.error_type(), &ErrorType::CorruptedData);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn drops_oldest_entries() {
+    let dir = temp_dir();
+    let path = dir.join("journal.jsonl");
+    let mut journal = Journal::open(&path).unwrap();
+
+    let links: Vec<Link> = (0..=MAX_JOURNAL_ENTRIES)
+        .map(|i| Link::new(format!("https://example.com/{i}")))
+        .collect();
+    for link in &links {
+        journal.record(Change::Created(link.clone())).unwrap();
+    }
+
+    assert_eq!(journal.entries().len(), MAX_JOURNAL_ENTRIES / 2);
+    assert_eq!(
+        journal.next_undo(),
+        Some(&Change::Created(links.last().unwrap().clone()))
+    );
+
+    // the file is rewritten with the kept entries
+    let journal = Journal::open(&path).unwrap();
+    assert_eq!(journal.entries().len(), MAX_JOURNAL_ENTRIES / 2);
+    assert_eq!(
+        journal.entries()[0].change,
+        Change::Created(links[MAX_JOURNAL_ENTRIES + 1 - MAX_JOURNAL_ENTRIES / 2].clone())
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keeps_stacks_after_dropping_entries() {
+    let dir = temp_dir();
+    let path = dir.join("journal.jsonl");
+    let rust = Change::Created(Link::new("https://www.rust-lang.org"));
+    let docs = Change::Created(Link::new("https://docs.rs"));
+
+    // the entry which created `rust` is dropped, but its undo and redo are kept
+    let entries: Vec<String> = [
+        (EntryKind::Done, &docs),
+        (EntryKind::Undone, &docs),
+        (EntryKind::Undone, &rust),
+        (EntryKind::Redone, &rust),
+    ]
+    .iter()
+    .map(|(kind, change)| {
+        serde_json::to_string(&JournalEntry {
+            id: uuid::Uuid::new_v4(),
+            at: chrono::Utc::now(),
+            kind: *kind,
+            change: (*change).clone(),
+        })
+        .unwrap()
+    })
+    .collect();
+    fs::write(&path, entries.join("\n") + "\n").unwrap();
+
+    let journal = Journal::open(&path).unwrap();
+    assert_eq!(journal.next_undo(), None);
+    assert_eq!(journal.next_redo(), Some(&docs));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn drops_changes_of_purged_links() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+
+    let rust = storage
+        .insert(Link::new("https://www.rust-lang.org").title("Rust"))
+        .unwrap();
+    journal.record(Change::Created(rust.clone())).unwrap();
+    let docs = storage
+        .insert(Link::new("https://docs.rs").title("Docs"))
+        .unwrap();
+    journal.record(Change::Created(docs.clone())).unwrap();
+
+    // the link is deleted forever without forgetting it, e.g. by restoring a backup
+    let trashed = storage.trash(docs.id.unwrap()).unwrap();
+    journal.record(Change::Trashed(trashed.link)).unwrap();
+    storage.purge(docs.id.unwrap()).unwrap();
+
+    // undoing it fails once, then the change before it is undone
+    let err = journal.undo(storage).unwrap_err();
+    assert_eq!(err.error_type(), &ErrorType::LinkNotFound);
+    assert_eq!(journal.next_undo(), Some(&Change::Created(docs.clone())));
+
+    // the purged link is forgotten, so undoing continues with the other link
+    journal.forget(&[docs.id.unwrap()]).unwrap();
+    assert_eq!(journal.next_undo(), Some(&Change::Created(rust.clone())));
+
+    // the dropped changes are still dropped after opening the journal again
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+    assert_eq!(journal.next_undo(), Some(&Change::Created(rust)));
+    journal.undo(storage).unwrap();
+    assert!(titles(storage).is_empty());
+    assert_eq!(journal.next_undo(), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
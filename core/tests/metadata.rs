@@ -0,0 +1,68 @@
+use dsaver_core::metadata::{find_favicon, resolve_url, PageMetadata};
+use dsaver_core::prelude::*;
+
+#[test]
+fn finds_the_favicon() {
+    let html = r#"
+        <html>
+        <head>
+            <link rel="apple-touch-icon" href="/apple.png">
+            <link href="/style.css" rel=stylesheet />
+            <link
+                rel=icon
+                type="image/svg+xml"
+                href="/icons/logo.svg?v=1&amp;theme=dark"
+            />
+            <link rel="icon" href="/second.png">
+        </head>
+        </html>
+    "#;
+
+    // `apple-touch-icon` is not `icon`, and the first icon is used
+    assert_eq!(
+        find_favicon(html),
+        Some(String::from("/icons/logo.svg?v=1&theme=dark"))
+    );
+
+    // no `href`, or an unclosed tag
+    assert_eq!(find_favicon(r#"<link rel="icon">"#), None);
+    assert_eq!(
+        find_favicon(r#"<link rel="icon" href="/icon.png""#),
+        Some(String::from("/icon.png"))
+    );
+}
+
+#[test]
+fn resolves_urls() {
+    assert_eq!(
+        resolve_url("https://example.com", "icon.png"),
+        "https://example.com/icon.png"
+    );
+    assert_eq!(
+        resolve_url("http://example.com/a/b/#top", "../icon.png"),
+        "http://example.com/a/b/../icon.png"
+    );
+    assert_eq!(
+        resolve_url("http://example.com/page", "//cdn.example.com/icon.png"),
+        "http://cdn.example.com/icon.png"
+    );
+    assert_eq!(
+        resolve_url("https://example.com", "data:image/png;base64,AAAA"),
+        "data:image/png;base64,AAAA"
+    );
+}
+
+#[test]
+fn old_links_have_empty_metadata() {
+    let mut value = serde_json::to_value(Link::new("https://example.com")).unwrap();
+    value.as_object_mut().unwrap().remove("metadata");
+
+    let link: Link = serde_json::from_value(value).unwrap();
+    assert!(link.metadata.is_empty());
+
+    let metadata = PageMetadata {
+        language: Some("en".into()),
+        ..Default::default()
+    };
+    assert!(!metadata.is_empty());
+}
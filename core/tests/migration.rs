@@ -0,0 +1,164 @@
+use chrono::{TimeZone, Utc};
+use dsaver_core::prelude::*;
+use dsaver_core::storage::{detect_version, migrate, LinkStore, MigrationError, CURRENT_VERSION};
+use serde_json::{json, Value};
+use std::fs;
+
+mod common;
+use common::temp_dir;
+
+/// Every shape of the links file ever released, oldest first
+const FIXTURES: &[(u32, &str)] = &[
+    (1, include_str!("fixtures/v1.json")),
+    (2, include_str!("fixtures/v2.json")),
+    (3, include_str!("fixtures/v3.json")),
+    (4, include_str!("fixtures/v4.json")),
+    (5, include_str!("fixtures/v5.json")),
+    (6, include_str!("fixtures/v6.json")),
+];
+
+fn parse(fixture: &str) -> Value {
+    serde_json::from_str(fixture).unwrap()
+}
+
+#[test]
+fn fixtures_cover_every_version() {
+    let versions: Vec<u32> = FIXTURES.iter().map(|(version, _)| *version).collect();
+
+    assert_eq!(versions, (1..=CURRENT_VERSION).collect::<Vec<_>>());
+}
+
+#[test]
+fn detects_version_of_every_fixture() {
+    for (version, fixture) in FIXTURES {
+        assert_eq!(detect_version(&parse(fixture)), Some(*version));
+    }
+}
+
+#[test]
+fn migrates_every_fixture_to_current_version() {
+    for (version, fixture) in FIXTURES {
+        let file = migrate(parse(fixture)).unwrap();
+
+        assert_eq!(file.version, CURRENT_VERSION, "fixture v{version}");
+        assert_eq!(file.links.len(), 2, "fixture v{version}");
+    }
+}
+
+#[test]
+fn migrations_keep_the_links() {
+    let latest = migrate(parse(FIXTURES.last().unwrap().1)).unwrap();
+
+    for (version, fixture) in FIXTURES {
+        assert_eq!(
+            migrate(parse(fixture)).unwrap(),
+            latest,
+            "fixture v{version}"
+        );
+    }
+
+    let link = &latest.links[0];
+    assert_eq!(link.url, "https://www.rust-lang.org");
+    assert_eq!(link.tags, vec!["Rust", "Programming"]);
+    assert_eq!(link.priority, 'A');
+    assert_eq!(link.browser, Browser::Firefox);
+}
+
+#[test]
+fn parses_old_dates() {
+    let file = migrate(parse(FIXTURES[1].1)).unwrap();
+
+    assert_eq!(
+        file.links[0].created_at,
+        Utc.with_ymd_and_hms(2022, 1, 14, 0, 0, 0).unwrap()
+    );
+    assert_eq!(
+        file.links[1].created_at,
+        Utc.with_ymd_and_hms(2022, 9, 15, 0, 0, 0).unwrap()
+    );
+    assert_eq!(file.links[1].updated_at, file.links[1].created_at);
+    assert_eq!(file.links[1].last_opened_at, None);
+}
+
+#[test]
+fn uses_migration_time_for_invalid_dates() {
+    let mut value = parse(FIXTURES[1].1);
+    value["links"][0]["date"] = json!("someday");
+
+    let before = Utc::now();
+    let link = migrate(value).unwrap().links.remove(0);
+
+    assert!(link.created_at >= before && link.created_at <= Utc::now());
+}
+
+#[test]
+fn migrates_empty_bare_list() {
+    let file = migrate(json!([])).unwrap();
+
+    assert_eq!(file.version, CURRENT_VERSION);
+    assert!(file.links.is_empty());
+}
+
+#[test]
+fn rejects_newer_version() {
+    let result = migrate(json!({ "version": CURRENT_VERSION + 1, "links": [] }));
+
+    assert!(matches!(result, Err(MigrationError::NewerVersion(v)) if v == CURRENT_VERSION + 1));
+}
+
+#[test]
+fn rejects_unknown_shape() {
+    assert!(matches!(
+        migrate(json!("links")),
+        Err(MigrationError::UnknownShape)
+    ));
+    assert!(matches!(
+        migrate(json!({ "links": [] })),
+        Err(MigrationError::UnknownShape)
+    ));
+    assert!(matches!(
+        migrate(json!({ "version": 0 })),
+        Err(MigrationError::UnknownShape)
+    ));
+}
+
+#[test]
+fn rejects_invalid_links() {
+    let result = migrate(json!([{ "url": "https://www.rust-lang.org" }]));
+
+    assert!(matches!(result, Err(MigrationError::Invalid(_))));
+}
+
+#[test]
+fn store_upgrades_old_file_on_save() {
+    let dir = temp_dir();
+    let path = dir.join("links.json");
+    fs::write(&path, FIXTURES[0].1).unwrap();
+
+    let store = LinkStore::load(&path).unwrap();
+    assert_eq!(store.links().len(), 2);
+
+    store.save().unwrap();
+
+    let saved = parse(&fs::read_to_string(&path).unwrap());
+    assert_eq!(detect_version(&saved), Some(CURRENT_VERSION));
+    assert_eq!(&migrate(saved).unwrap().links, store.links());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn store_refuses_newer_file() {
+    let dir = temp_dir();
+    let path = dir.join("links.json");
+    fs::write(
+        &path,
+        json!({ "version": CURRENT_VERSION + 1, "links": [] }).to_string(),
+    )
+    .unwrap();
+
+    let err = LinkStore::load(&path).unwrap_err();
+    assert_eq!(err.error_type(), &ErrorType::UnsupportedVersion);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,164 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use dsaver_core::check::LinkCheck;
+use dsaver_core::prelude::*;
+use dsaver_core::query::{Comparison, Condition, LinkState, Query, Term};
+
+fn links() -> Vec<Link> {
+    let mut opened = Link::new("https://www.youtube.com/watch?v=ygL_xcavzQ4")
+        .title("Rust tutorial - Youtube")
+        .tags("Rust Video")
+        .priority('C')
+        .browser(Browser::Chrome)
+        .complete(true)
+        .created_at(Utc.with_ymd_and_hms(2025, 12, 31, 23, 0, 0).unwrap());
+    opened.last_opened_at = Some(Utc.with_ymd_and_hms(2026, 2, 1, 10, 0, 0).unwrap());
+
+    let mut broken = Link::new("https://gist.github.com/KRShanto")
+        .title("Gists")
+        .description("Machine learning notes")
+        .tags("Python")
+        .priority('E')
+        .created_at(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap());
+    broken.check = Some(LinkCheck::new(&broken.url).status(404));
+
+    vec![
+        Link::new("https://github.com/rust-lang/rust")
+            .title("The Rust Programming Language")
+            .tags("Rust Code")
+            .priority('A')
+            .browser(Browser::Firefox)
+            .created_at(Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap()),
+        opened,
+        broken,
+    ]
+}
+
+/// Indexes of the links which match the query
+fn matched(query: &str) -> Vec<usize> {
+    let query = Query::parse(query).unwrap();
+
+    links()
+        .iter()
+        .enumerate()
+        .filter(|(_, link)| query.matches(link))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[test]
+fn parses_terms() {
+    let query =
+        Query::parse(r#"tag:rust  priority:<=C -tag:"machine learning" "a:b" tutorial"#).unwrap();
+
+    assert_eq!(
+        query.terms(),
+        &[
+            Term {
+                negated: false,
+                condition: Condition::Tag("rust".into())
+            },
+            Term {
+                negated: false,
+                condition: Condition::Priority(Comparison::LessOrEqual, 'C')
+            },
+            Term {
+                negated: true,
+                condition: Condition::Tag("machine learning".into())
+            },
+            Term {
+                negated: false,
+                condition: Condition::Text("a:b".into())
+            },
+            Term {
+                negated: false,
+                condition: Condition::Text("tutorial".into())
+            },
+        ]
+    );
+    assert_eq!(
+        Query::parse("is:Opened added:>=2026-01-01")
+            .unwrap()
+            .terms(),
+        &[
+            Term {
+                negated: false,
+                condition: Condition::Is(LinkState::Opened)
+            },
+            Term {
+                negated: false,
+                condition: Condition::Added(
+                    Comparison::GreaterOrEqual,
+                    NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+                )
+            },
+        ]
+    );
+    assert!(Query::parse("   ").unwrap().is_empty());
+}
+
+#[test]
+fn matches_links() {
+    assert_eq!(matched(""), vec![0, 1, 2]);
+    assert_eq!(matched("tag:rust"), vec![0, 1]);
+    assert_eq!(matched("tag:rust -tag:video"), vec![0]);
+    assert_eq!(matched("priority:<=C"), vec![0, 1]);
+    assert_eq!(matched("priority:>a"), vec![1, 2]);
+    assert_eq!(matched("browser:firefox"), vec![0]);
+    assert_eq!(matched("browser:default"), vec![2]);
+    assert_eq!(matched("domain:github.com"), vec![0, 2]);
+    assert_eq!(matched("domain:www.youtube.com"), vec![1]);
+    assert_eq!(matched("is:incomplete"), vec![0, 2]);
+    assert_eq!(matched("is:opened"), vec![1]);
+    assert_eq!(matched("is:broken"), vec![2]);
+    assert_eq!(matched("added:>2026-01-01"), vec![0]);
+    assert_eq!(matched("added:2026-01-01"), vec![2]);
+    assert_eq!(matched("updated:<2026-01-01"), vec![1]);
+    assert_eq!(matched("opened:>=2026-02-01"), vec![1]);
+    assert_eq!(matched("-opened:>=2026-02-01"), vec![0, 2]);
+}
+
+#[test]
+fn matches_text() {
+    assert_eq!(matched("RUST"), vec![0, 1]);
+    assert_eq!(matched(r#""machine learning""#), vec![2]);
+    assert_eq!(matched("https://github.com"), vec![0]);
+    assert_eq!(matched("rust -youtube"), vec![0]);
+
+    let query = Query::parse("rust -youtube tag:code tutorial").unwrap();
+    assert_eq!(query.text(), "rust tutorial");
+    assert_eq!(
+        query.without_text(),
+        Query::parse("-youtube tag:code").unwrap()
+    );
+}
+
+#[test]
+fn reports_invalid_queries() {
+    for (query, error) in [
+        ("tga:rust", "unknown key `tga:`"),
+        ("tag:", "no value for `tag:`"),
+        ("tag:<rust", "`tag:` can't be compared with `<` or `>`"),
+        ("priority:AB", "invalid priority `AB`"),
+        ("browser:safari", "unknown browser `safari`"),
+        ("is:hidden", "unknown state `hidden`"),
+        (
+            "tag:\"machine learning",
+            "unclosed quote in `tag:\"machine learning`",
+        ),
+    ] {
+        let report = Query::parse(query).unwrap_err();
+
+        assert_eq!(report.error_type(), &ErrorType::InvalidQuery, "{query}");
+        assert_eq!(report.actual_error(), error);
+        assert!(!report.how_to_fix().is_empty());
+    }
+
+    let report = Query::parse("added:2026-13-01").unwrap_err();
+    assert!(report
+        .actual_error()
+        .starts_with("invalid date `2026-13-01`"));
+    assert_eq!(
+        report.how_to_fix(),
+        &vec!["Write the date like added:>2026-01-01".to_string()]
+    );
+}
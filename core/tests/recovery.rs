@@ -0,0 +1,140 @@
+use dsaver_core::prelude::*;
+use dsaver_core::saved_search::SavedSearch;
+use dsaver_core::storage::{recover_links, LinkStore};
+use dsaver_core::tag::TagInfo;
+use std::fs;
+
+mod common;
+use common::temp_dir;
+
+const V1: &str = include_str!("fixtures/v1.json");
+const V2: &str = include_str!("fixtures/v2.json");
+
+#[test]
+fn recovers_valid_file_completely() {
+    for fixture in [V1, V2] {
+        let recovered = recover_links(fixture);
+
+        assert_eq!(recovered.links.len(), 2);
+        assert_eq!(recovered.lost, 0);
+    }
+}
+
+#[test]
+fn recovers_truncated_file() {
+    for fixture in [V1, V2] {
+        // cut the file in the middle of the second link
+        let cut = fixture.find("Rust tutorial").unwrap();
+        let recovered = recover_links(&fixture[..cut]);
+
+        assert_eq!(recovered.links.len(), 1);
+        assert_eq!(recovered.links[0].url, "https://www.rust-lang.org");
+        assert_eq!(recovered.lost, 1);
+    }
+}
+
+#[test]
+fn skips_broken_link() {
+    // the first link has an invalid priority
+    let data = V2.replacen("\"priority\": \"A\"", "\"priority\": 42", 1);
+    let recovered = recover_links(&data);
+
+    assert_eq!(recovered.links.len(), 1);
+    assert_eq!(recovered.links[0].priority, 'C');
+    assert_eq!(recovered.lost, 1);
+}
+
+#[test]
+fn gives_new_id_to_duplicate_links() {
+    let data = V1.replace(
+        "9d2b4a0c-5f6e-4c1d-8a3b-2e7f1c0d9b8a",
+        "67e55044-10b1-426f-9247-bb680e5fe0c8",
+    );
+    let recovered = recover_links(&data);
+
+    assert_eq!(recovered.links.len(), 2);
+    assert_ne!(recovered.links[0].id, recovered.links[1].id);
+}
+
+#[test]
+fn store_quarantines_corrupted_file() {
+    let dir = std::env::temp_dir().join(format!("dsaver-{}", uuid::Uuid::new_v4()));
+    let path = dir.join("links.json");
+    // the closing bracket of the file is missing
+    let data = V2.trim_end().strip_suffix('}').unwrap();
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(&path, data).unwrap();
+
+    let err = LinkStore::load(&path).unwrap_err();
+    assert_eq!(err.error_type(), &ErrorType::CorruptedData);
+
+    let (store, recovery) = LinkStore::recover(&path).unwrap();
+
+    assert_eq!(store.links().len(), 2);
+    assert_eq!(recovery.recovered, 2);
+    assert_eq!(recovery.lost, 0);
+    assert_eq!(fs::read_to_string(&recovery.quarantined).unwrap(), data);
+    assert_eq!(
+        recovery.report().error_type(),
+        &ErrorType::DataRecovered {
+            recovered: 2,
+            lost: 0
+        }
+    );
+
+    // keeping the recovered links fixes the file
+    store.save().unwrap();
+    assert_eq!(LinkStore::load(&path).unwrap().links(), store.links());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keeps_trashed_links_in_the_trash() {
+    let dir = temp_dir();
+    let path = dir.join("links.json");
+    fs::write(&path, V2).unwrap();
+
+    let mut store = LinkStore::load(&path).unwrap();
+    let id = store.links()[1].id.unwrap();
+    let trashed = store.trash(id).unwrap();
+    store.save().unwrap();
+
+    // the closing bracket of the file is missing
+    let data = fs::read_to_string(&path).unwrap();
+    fs::write(&path, data.trim_end().strip_suffix('}').unwrap()).unwrap();
+
+    let (recovered, recovery) = LinkStore::recover(&path).unwrap();
+
+    assert_eq!(recovered.links(), store.links());
+    assert_eq!(recovered.trashed(), &vec![trashed]);
+    assert_eq!(recovery.recovered, 2);
+    assert_eq!(recovery.lost, 0);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keeps_saved_searches_and_tag_infos() {
+    let dir = temp_dir();
+    let path = dir.join("links.json");
+    fs::write(&path, V2).unwrap();
+
+    let mut store = LinkStore::load(&path).unwrap();
+    let search = store.save_search(SavedSearch::new("Rust", "tag:Rust"));
+    let info = store.save_tag_info(TagInfo::new("Rust").color("#dea584"));
+    store.save().unwrap();
+
+    // the closing bracket of the file is missing
+    let data = fs::read_to_string(&path).unwrap();
+    fs::write(&path, data.trim_end().strip_suffix('}').unwrap()).unwrap();
+
+    let (recovered, recovery) = LinkStore::recover(&path).unwrap();
+
+    assert_eq!(recovered.links(), store.links());
+    assert_eq!(recovered.saved_searches(), &vec![search]);
+    assert_eq!(recovered.tag_infos(), &vec![info]);
+    assert_eq!(recovery.lost, 0);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,68 @@
+use dsaver_core::metadata::PageMetadata;
+use dsaver_core::prelude::*;
+use dsaver_core::refresh::LinkRefresh;
+
+fn with_image(link: Link, image: &str) -> Link {
+    let mut link = link;
+    link.metadata = PageMetadata {
+        image: Some(image.into()),
+        ..Default::default()
+    };
+    link
+}
+
+#[test]
+fn keeps_custom_titles() {
+    let mut saved = Link::new("https://example.com").title("My notes");
+    saved.custom_title = true;
+    let fetched = Link::new("https://example.com").title("Example Domain");
+
+    let refresh = LinkRefresh::new(saved.clone(), fetched);
+    assert_eq!(refresh.title, None);
+    assert!(refresh.is_empty());
+    assert_eq!(refresh.apply(saved.clone()), saved);
+}
+
+#[test]
+fn ignores_missing_fields() {
+    let saved = with_image(
+        Link::new("https://example.com")
+            .title("Example")
+            .description("An example"),
+        "https://example.com/og.png",
+    );
+    // the webpage doesn't have a title or description anymore
+    let fetched = with_image(
+        Link::new("https://example.com").title("").description(" "),
+        "https://example.com/og.png",
+    );
+
+    let refresh = LinkRefresh::new(saved, fetched);
+    assert!(refresh.is_empty());
+    assert!(!refresh.image_changed());
+}
+
+#[test]
+fn finds_changed_thumbnails() {
+    let saved = with_image(
+        Link::new("https://example.com").title("Example"),
+        "https://example.com/old.png",
+    );
+    let fetched = with_image(
+        Link::new("https://example.com").title("Example"),
+        "https://example.com/new.png",
+    );
+
+    let refresh = LinkRefresh::new(saved.clone(), fetched);
+    assert!(refresh.image_changed());
+    assert!(!refresh.is_empty());
+
+    // the link was edited after it was refreshed
+    let edited = saved.tags("Edited");
+    let refreshed = refresh.apply(edited);
+    assert_eq!(refreshed.tags, vec!["Edited"]);
+    assert_eq!(
+        refreshed.metadata.image.as_deref(),
+        Some("https://example.com/new.png")
+    );
+}
@@ -0,0 +1,184 @@
+use dsaver_core::prelude::*;
+use dsaver_core::search::{Highlight, SearchField, SearchIndex};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+fn links() -> Vec<Link> {
+    vec![
+        Link::new("https://www.rust-lang.org")
+            .title("Rust Programming Language")
+            .description("A language empowering everyone to build reliable and efficient software.")
+            .tags("Rust Programming"),
+        Link::new("https://www.youtube.com/watch?v=ygL_xcavzQ4")
+            .title("Rust tutorial - Youtube")
+            .description("")
+            .tags("Videos Tutorial"),
+        Link::new("https://www.python.org")
+            .title("Welcome to Python.org")
+            .description("The official home of the Python Programming Language")
+            .tags("Python Programming"),
+    ]
+}
+
+fn ids(index: &SearchIndex, query: &str) -> Vec<Uuid> {
+    index.search(query).into_iter().map(|hit| hit.id).collect()
+}
+
+#[test]
+fn ranks_titles_higher() {
+    let links = links();
+    let index = SearchIndex::new(&links);
+
+    // both have "rust" in the title and url, but only the first one has it in the tags
+    assert_eq!(
+        ids(&index, "rust"),
+        vec![links[0].id.unwrap(), links[1].id.unwrap()]
+    );
+
+    // "language" is in the title of the first link but in the description of the third one
+    assert_eq!(
+        ids(&index, "language"),
+        vec![links[0].id.unwrap(), links[2].id.unwrap()]
+    );
+}
+
+#[test]
+fn matches_every_word() {
+    let links = links();
+    let index = SearchIndex::new(&links);
+
+    assert_eq!(
+        ids(&index, "programming python"),
+        vec![links[2].id.unwrap()]
+    );
+    assert_eq!(ids(&index, "rust videos"), vec![links[1].id.unwrap()]);
+    assert!(ids(&index, "rust java").is_empty());
+    assert!(ids(&index, "  -- ").is_empty());
+}
+
+#[test]
+fn matches_prefixes_and_typos() {
+    let links = links();
+    let index = SearchIndex::new(&links);
+
+    assert_eq!(ids(&index, "tuto"), vec![links[1].id.unwrap()]);
+    assert_eq!(ids(&index, "pyhton"), vec![links[2].id.unwrap()]);
+    assert_eq!(ids(&index, "Youtub"), vec![links[1].id.unwrap()]);
+    assert_eq!(ids(&index, "efficeint softwar"), vec![links[0].id.unwrap()]);
+
+    // short words must not have typos
+    assert!(ids(&index, "rsu").is_empty());
+    // only the words with the same first letter are compared
+    assert!(ids(&index, "bython").is_empty());
+}
+
+#[test]
+fn highlights_matched_words() {
+    let links = links();
+    let index = SearchIndex::new(&links);
+
+    let hit = index.search("youtube tutorial").remove(0);
+
+    assert_eq!(
+        hit.highlights,
+        vec![
+            Highlight {
+                field: SearchField::Title,
+                start: 5,
+                end: 13
+            },
+            Highlight {
+                field: SearchField::Title,
+                start: 16,
+                end: 23
+            },
+            Highlight {
+                field: SearchField::Url,
+                start: 12,
+                end: 19
+            },
+            Highlight {
+                field: SearchField::Tag(1),
+                start: 0,
+                end: 8
+            },
+        ]
+    );
+    assert_eq!(&links[1].url[12..19], "youtube");
+}
+
+#[test]
+fn updates_incrementally() {
+    let mut links = links();
+    let mut index = SearchIndex::new(&links);
+
+    links[2] = links[2].clone().title("Snakes");
+    index.insert(&links[2]);
+
+    assert_eq!(index.len(), 3);
+    assert!(ids(&index, "welcome").is_empty());
+    assert_eq!(ids(&index, "snakes"), vec![links[2].id.unwrap()]);
+
+    index.remove(links[0].id.unwrap());
+
+    assert_eq!(index.len(), 2);
+    assert_eq!(ids(&index, "rust"), vec![links[1].id.unwrap()]);
+    assert!(ids(&index, "empowering").is_empty());
+}
+
+#[test]
+fn searches_queries() {
+    use dsaver_core::query::Query;
+
+    let links = links();
+    let index = SearchIndex::new(&links);
+    let search = |query: &str| -> Vec<Uuid> {
+        index
+            .search_query(&Query::parse(query).unwrap(), &links)
+            .into_iter()
+            .map(|hit| hit.id)
+            .collect()
+    };
+
+    // the words are searched in the index, so they can have typos
+    assert_eq!(search("progamming tag:python"), vec![links[2].id.unwrap()]);
+    // without words, every matched link is returned in its order
+    assert_eq!(
+        search("tag:programming"),
+        vec![links[0].id.unwrap(), links[2].id.unwrap()]
+    );
+    assert!(search("rust -tag:videos tag:python").is_empty());
+}
+
+#[test]
+fn searches_many_links() {
+    let links: Vec<Link> = (0..10_000)
+        .map(|i| {
+            Link::new(format!("https://www.example{i}.com/page/{}", i * 7))
+                .title(format!("Example page number {i}"))
+                .description(format!(
+                    "Description of the page {i} about topic{}",
+                    i % 100
+                ))
+                .tags(format!("Tag{} Other{}", i % 50, i % 13))
+        })
+        .collect();
+
+    let mut index = SearchIndex::new(&links);
+
+    let hits = index.search("exmple topic42");
+    index.insert(&links[42].clone().title("Renamed"));
+
+    // the links with the exact topic come before the links with a similar topic
+    let expected: HashSet<Uuid> = links
+        .iter()
+        .skip(42)
+        .step_by(100)
+        .map(|link| link.id.unwrap())
+        .collect();
+    let found: HashSet<Uuid> = hits.iter().take(100).map(|hit| hit.id).collect();
+
+    assert_eq!(found, expected);
+
+    assert_eq!(ids(&index, "renamed"), vec![links[42].id.unwrap()]);
+}
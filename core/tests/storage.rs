@@ -0,0 +1,676 @@
+use chrono::{Duration, Utc};
+use dsaver_core::check::LinkCheck;
+use dsaver_core::prelude::*;
+use dsaver_core::storage::{LinkStore, Storage, TrashedLink};
+use dsaver_core::tag::TagInfo;
+use std::fs;
+use uuid::Uuid;
+
+mod common;
+use common::temp_dir;
+
+fn links() -> Vec<Link> {
+    vec![
+        Link::new("https://www.rust-lang.org")
+            .tags("Rust Programming")
+            .priority('B')
+            .browser(Browser::Firefox),
+        Link::new("https://www.python.org")
+            .tags("Python Programming")
+            .priority('B'),
+        Link::new("https://docs.rs").tags("Rust").priority('A'),
+    ]
+}
+
+/// Urls of the links which match the `filter`
+fn urls(storage: &dyn Storage, filter: LinkFilter) -> Vec<String> {
+    storage
+        .query(&filter)
+        .unwrap()
+        .into_iter()
+        .map(|link| link.url)
+        .collect()
+}
+
+/// Every storage must behave the same
+fn check_storage(storage: &mut dyn Storage) {
+    let [rust, python, docs]: [Link; 3] = links().try_into().unwrap();
+
+    for link in [&rust, &python, &docs] {
+        storage.insert(link.clone()).unwrap();
+    }
+    assert_eq!(
+        storage.links().unwrap(),
+        vec![rust.clone(), python.clone(), docs.clone()]
+    );
+
+    let err = storage.insert(rust.clone()).unwrap_err();
+    assert_eq!(err.error_type(), &ErrorType::DuplicateLink);
+
+    // query
+    assert_eq!(urls(storage, LinkFilter::default()).len(), 3);
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default().tags(vec!["Rust"]).priority('B')
+        ),
+        vec![rust.url.clone()]
+    );
+    assert_eq!(
+        urls(storage, LinkFilter::default().tags(vec!["Rust", "Python"])),
+        vec![rust.url.clone(), python.url.clone(), docs.url.clone()]
+    );
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default().browsers(vec![Browser::SysDefault])
+        ),
+        vec![python.url.clone(), docs.url.clone()]
+    );
+    assert!(urls(storage, LinkFilter::default().tags(Vec::<String>::new())).is_empty());
+
+    // all tags, excluded tags and browsers
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default()
+                .tags(vec!["Rust", "Programming"])
+                .tags_mode(MatchMode::All)
+        ),
+        vec![rust.url.clone()]
+    );
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default()
+                .tags(vec!["Programming"])
+                .exclude_tags(vec!["Python"])
+        ),
+        vec![rust.url.clone()]
+    );
+    assert_eq!(
+        urls(storage, LinkFilter::default().exclude_tags(vec!["Rust"])),
+        vec![python.url.clone()]
+    );
+    assert!(urls(
+        storage,
+        LinkFilter::default()
+            .browsers(vec![Browser::Firefox, Browser::SysDefault])
+            .browsers_mode(MatchMode::All)
+    )
+    .is_empty());
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default().exclude_browsers(vec![Browser::Firefox])
+        ),
+        vec![python.url.clone(), docs.url.clone()]
+    );
+
+    // priority ranges, domains, completion and creation time
+    assert_eq!(
+        urls(storage, LinkFilter::default().priority_range('A', 'A')),
+        vec![docs.url.clone()]
+    );
+    assert_eq!(
+        urls(storage, LinkFilter::default().priority_range('A', 'C')).len(),
+        3
+    );
+    assert_eq!(
+        urls(storage, LinkFilter::default().domains(vec!["python.org"])),
+        vec![python.url.clone()]
+    );
+    assert!(urls(storage, LinkFilter::default().complete(true)).is_empty());
+    assert_eq!(
+        urls(storage, LinkFilter::default().complete(false)).len(),
+        3
+    );
+
+    let now = Utc::now();
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default().created_between(now - Duration::days(1), now + Duration::days(1))
+        )
+        .len(),
+        3
+    );
+    assert!(urls(
+        storage,
+        LinkFilter::default().created_between(now + Duration::days(1), now + Duration::days(2))
+    )
+    .is_empty());
+
+    // update keeps the order
+    let renamed = rust.clone().tags("Rust Lang");
+    storage.update(renamed.clone()).unwrap();
+    assert_eq!(storage.links().unwrap()[0], renamed);
+    assert_eq!(
+        urls(storage, LinkFilter::default().tags(vec!["Programming"])),
+        vec![python.url.clone()]
+    );
+
+    // opening a link is remembered
+    let mut opened = renamed.clone();
+    opened.opened(Browser::Chrome);
+    storage.update(opened.clone()).unwrap();
+    assert_eq!(storage.get(rust.id.unwrap()).unwrap(), Some(opened.clone()));
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default().not_opened_since(opened.last_opened_at.unwrap())
+        ),
+        vec![python.url.clone(), docs.url.clone()]
+    );
+    assert!(urls(
+        storage,
+        LinkFilter::default().not_opened_since(now - Duration::days(1))
+    )
+    .is_empty());
+
+    // reorder keeps the links in the order they were added, only `manual_order` is changed
+    let mut links = storage
+        .reorder(&[docs.id.unwrap(), rust.id.unwrap()])
+        .unwrap();
+    assert_eq!(storage.links().unwrap(), links);
+    assert_eq!(
+        links
+            .iter()
+            .map(|link| link.manual_order)
+            .collect::<Vec<_>>(),
+        vec![Some(1), None, Some(0)]
+    );
+    SortOrder::Manual.sort(&mut links);
+    assert_eq!(
+        links.into_iter().map(|link| link.url).collect::<Vec<_>>(),
+        vec![docs.url.clone(), rust.url.clone(), python.url.clone()]
+    );
+
+    // delete
+    assert_eq!(storage.delete(python.id.unwrap()).unwrap(), python);
+    assert_eq!(storage.get(python.id.unwrap()).unwrap(), None);
+    assert_eq!(
+        storage.delete(python.id.unwrap()).unwrap_err().error_type(),
+        &ErrorType::LinkNotFound
+    );
+    assert!(urls(storage, LinkFilter::default().tags(vec!["Python"])).is_empty());
+
+    check_trash(storage);
+    check_saved_searches(storage);
+    check_tag_infos(storage);
+    check_nested_tags(storage);
+    check_link_checks(storage);
+    check_tag_updates(storage);
+    check_tag_normalization(storage);
+}
+
+fn check_trash(storage: &mut dyn Storage) {
+    assert!(storage.trashed().unwrap().is_empty());
+
+    let [rust, docs]: [Link; 2] = storage.links().unwrap().try_into().unwrap();
+    let (rust_id, docs_id) = (rust.id.unwrap(), docs.id.unwrap());
+
+    // trashed links are not shown anymore, the last one first
+    let trashed_rust = storage.trash(rust_id).unwrap();
+    let trashed_docs = storage.trash(docs_id).unwrap();
+    assert_eq!(trashed_rust.link, rust);
+    assert!(storage.links().unwrap().is_empty());
+    assert_eq!(storage.get(rust_id).unwrap(), None);
+    assert!(urls(storage, LinkFilter::default().tags(vec!["Rust"])).is_empty());
+    assert_eq!(
+        storage.trashed().unwrap(),
+        vec![trashed_docs.clone(), trashed_rust.clone()]
+    );
+    assert_eq!(
+        storage.trash(rust_id).unwrap_err().error_type(),
+        &ErrorType::LinkNotFound
+    );
+
+    // restore
+    assert_eq!(storage.restore(docs_id).unwrap(), docs);
+    assert_eq!(storage.links().unwrap(), vec![docs.clone()]);
+    assert_eq!(
+        urls(storage, LinkFilter::default().tags(vec!["Rust"])),
+        vec![docs.url.clone()]
+    );
+    assert_eq!(
+        storage.restore(docs_id).unwrap_err().error_type(),
+        &ErrorType::LinkNotFound
+    );
+
+    // purge the old links, and then forever
+    assert!(storage
+        .purge_before(trashed_rust.deleted_at)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        storage
+            .purge_before(trashed_rust.deleted_at + Duration::milliseconds(1))
+            .unwrap(),
+        vec![trashed_rust.clone()]
+    );
+    assert!(storage.trashed().unwrap().is_empty());
+
+    storage.trash(docs_id).unwrap();
+    assert_eq!(storage.purge(docs_id).unwrap().link, docs);
+    assert_eq!(
+        storage.purge(docs_id).unwrap_err().error_type(),
+        &ErrorType::LinkNotFound
+    );
+    assert!(storage.trashed().unwrap().is_empty());
+
+    // the trash can be replaced, e.g. while migrating
+    storage.replace_trash(vec![trashed_rust.clone()]).unwrap();
+    assert_eq!(storage.trashed().unwrap(), vec![trashed_rust]);
+    assert_eq!(storage.restore(rust_id).unwrap(), rust);
+}
+
+fn check_saved_searches(storage: &mut dyn Storage) {
+    assert!(storage.saved_searches().unwrap().is_empty());
+
+    let unread = SavedSearch::new("Unread", "is:unopened");
+    let priority = SavedSearch::new("Priority A", "priority:A")
+        .filter(LinkFilter::default().tags(vec!["Rust"]));
+
+    storage.save_search(unread.clone()).unwrap();
+    storage.save_search(priority.clone()).unwrap();
+    assert_eq!(
+        storage.saved_searches().unwrap(),
+        vec![unread.clone(), priority.clone()]
+    );
+
+    // saving again replaces the search and keeps its order
+    let mut renamed = unread.clone();
+    renamed.name = "Not opened yet".to_string();
+    storage.save_search(renamed.clone()).unwrap();
+    assert_eq!(
+        storage.saved_searches().unwrap(),
+        vec![renamed, priority.clone()]
+    );
+
+    // delete
+    assert_eq!(storage.delete_search(unread.id).unwrap().id, unread.id);
+    assert_eq!(storage.saved_searches().unwrap(), vec![priority]);
+    assert_eq!(
+        storage.delete_search(unread.id).unwrap_err().error_type(),
+        &ErrorType::SavedSearchNotFound
+    );
+}
+
+fn check_tag_infos(storage: &mut dyn Storage) {
+    assert!(storage.tag_infos().unwrap().is_empty());
+
+    let rust = TagInfo::new("Rust").color("#dea584").icon("🦀");
+    let python = TagInfo::new("Python").description("Snakes");
+
+    storage.save_tag_info(rust.clone()).unwrap();
+    storage.save_tag_info(python.clone()).unwrap();
+    assert_eq!(
+        storage.tag_infos().unwrap(),
+        vec![python.clone(), rust.clone()]
+    );
+
+    // saving again replaces the info of the tag
+    let rust = rust.description("Crabs");
+    storage.save_tag_info(rust.clone()).unwrap();
+    assert_eq!(
+        storage.tag_infos().unwrap(),
+        vec![python.clone(), rust.clone()]
+    );
+
+    // delete
+    assert_eq!(storage.delete_tag_info("Python").unwrap(), python);
+    assert_eq!(storage.tag_infos().unwrap(), vec![rust.clone()]);
+    assert_eq!(
+        storage.delete_tag_info("Python").unwrap_err().error_type(),
+        &ErrorType::TagNotFound
+    );
+
+    storage.delete_tag_info("Rust").unwrap();
+}
+
+/// A parent tag matches the links of its child tags
+fn check_nested_tags(storage: &mut dyn Storage) {
+    let [tokio, rust, python, rustacean] = [
+        "dev/rust/async",
+        "dev/rust",
+        "dev/python Video",
+        "dev/rustacean",
+    ]
+    .map(|tags| {
+        storage
+            .insert(Link::new(format!("https://example.com/{}", tags.replace(' ', "-"))).tags(tags))
+            .unwrap()
+            .url
+    });
+
+    assert_eq!(
+        urls(storage, LinkFilter::default().tags(vec!["dev/rust"])),
+        vec![tokio.clone(), rust.clone()]
+    );
+    // the case is ignored, like in the search bar
+    assert_eq!(
+        urls(storage, LinkFilter::default().tags(vec!["Dev/Rust"])),
+        vec![tokio.clone(), rust.clone()]
+    );
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default()
+                .tags(vec!["dev"])
+                .exclude_tags(vec!["DEV/RUST"])
+        ),
+        vec![python.clone(), rustacean.clone()]
+    );
+    assert_eq!(
+        urls(storage, LinkFilter::default().tags(vec!["dev"])),
+        vec![
+            tokio.clone(),
+            rust.clone(),
+            python.clone(),
+            rustacean.clone()
+        ]
+    );
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default()
+                .tags(vec!["dev", "Video"])
+                .tags_mode(MatchMode::All)
+        ),
+        vec![python.clone()]
+    );
+    assert_eq!(
+        urls(
+            storage,
+            LinkFilter::default()
+                .tags(vec!["dev"])
+                .exclude_tags(vec!["dev/rust"])
+        ),
+        vec![python, rustacean]
+    );
+}
+
+/// The checks are saved only for the links which still have the checked url, and the inconclusive checks don't replace the previous checks
+fn check_link_checks(storage: &mut dyn Storage) {
+    let [dead, alive, moved] = ["dead", "alive", "moved"].map(|name| {
+        storage
+            .insert(Link::new(format!("https://checks.example.com/{name}")))
+            .unwrap()
+    });
+    let deleted = Uuid::new_v4();
+
+    let updated = storage
+        .save_checks(vec![
+            (dead.id.unwrap(), LinkCheck::new(&dead.url).status(404)),
+            (alive.id.unwrap(), LinkCheck::new(&alive.url).status(200)),
+            // the url was edited while checking
+            (
+                moved.id.unwrap(),
+                LinkCheck::new("https://checks.example.com/old").error("timeout"),
+            ),
+            (
+                deleted,
+                LinkCheck::new("https://checks.example.com/deleted"),
+            ),
+        ])
+        .unwrap();
+    assert_eq!(updated.len(), 2);
+
+    let checked =
+        |storage: &dyn Storage, link: &Link| storage.get(link.id.unwrap()).unwrap().unwrap().check;
+    assert_eq!(checked(storage, &dead).unwrap().status, Some(404));
+    assert_eq!(checked(storage, &alive).unwrap().status, Some(200));
+    assert_eq!(checked(storage, &moved), None);
+    assert_eq!(storage.get(deleted).unwrap(), None);
+
+    // the user is offline, so the links keep their previous checks
+    let updated = storage
+        .save_checks(vec![
+            (dead.id.unwrap(), LinkCheck::new(&dead.url).error("timeout")),
+            (alive.id.unwrap(), LinkCheck::new(&alive.url).status(429)),
+        ])
+        .unwrap();
+    assert!(updated.is_empty());
+    assert_eq!(checked(storage, &dead).unwrap().status, Some(404));
+    assert_eq!(checked(storage, &alive).unwrap().status, Some(200));
+
+    let domain = LinkFilter::default().domains(vec!["checks.example.com"]);
+    assert_eq!(urls(storage, domain.clone().broken(true)), vec![dead.url]);
+    assert_eq!(
+        urls(storage, domain.broken(false)),
+        vec![alive.url, moved.url]
+    );
+}
+
+/// The tags are normalized when a link is saved
+fn check_tag_normalization(storage: &mut dyn Storage) {
+    let link = storage
+        .insert(Link::new("https://normalize.example.com").tags_vec(vec!["dev\\go/", "dev/go"]))
+        .unwrap();
+    assert_eq!(link.tags, vec![String::from("dev/go")]);
+
+    let link = storage
+        .update(link.tags_vec(vec!["/lang//go", "lang go"]))
+        .unwrap();
+    assert_eq!(
+        link.tags,
+        vec![
+            String::from("lang/go"),
+            String::from("lang"),
+            String::from("go")
+        ]
+    );
+    assert_eq!(storage.get(link.id.unwrap()).unwrap(), Some(link.clone()));
+
+    let updated = storage
+        .update_tags(vec![link.clone().tags_vec(vec!["go\\", "go"])], &[], &[])
+        .unwrap();
+    assert_eq!(updated[0].tags, vec![String::from("go")]);
+    assert_eq!(
+        storage.get(link.id.unwrap()).unwrap(),
+        Some(updated[0].clone())
+    );
+
+    // the trash is written as it is, so its links are normalized when they are restored
+    let trashed = storage.trash(link.id.unwrap()).unwrap();
+    let link = trashed.link.clone().tags_vec(vec!["/zig/", "zig"]);
+    storage
+        .replace_trash(vec![TrashedLink { link, ..trashed }])
+        .unwrap();
+    let restored = storage.restore(updated[0].id.unwrap()).unwrap();
+    assert_eq!(restored.tags, vec![String::from("zig")]);
+    assert_eq!(
+        storage.get(restored.id.unwrap()).unwrap(),
+        Some(restored.clone())
+    );
+
+    storage.delete(restored.id.unwrap()).unwrap();
+}
+
+/// The tags of many links and the infos of the tags are changed together
+fn check_tag_updates(storage: &mut dyn Storage) {
+    let [go, zig] = ["go", "zig"].map(|name| {
+        storage
+            .insert(Link::new(format!("https://tags.example.com/{name}")).tags("lang"))
+            .unwrap()
+    });
+    let lang = TagInfo::new("lang").color("#00add8");
+    storage.save_tag_info(lang.clone()).unwrap();
+
+    let deleted = storage.delete(zig.id.unwrap()).unwrap();
+    let languages = TagInfo::new("languages").color("#00add8");
+    let updated = storage
+        .update_tags(
+            vec![go.clone().tags("languages"), deleted.tags("languages")],
+            &[lang],
+            std::slice::from_ref(&languages),
+        )
+        .unwrap();
+
+    // the deleted link is skipped
+    assert_eq!(updated, vec![go.clone().tags("languages")]);
+    assert_eq!(
+        storage.get(go.id.unwrap()).unwrap(),
+        Some(updated[0].clone())
+    );
+    assert_eq!(storage.get(zig.id.unwrap()).unwrap(), None);
+    assert!(storage.tag_infos().unwrap().contains(&languages));
+    assert!(!storage
+        .tag_infos()
+        .unwrap()
+        .iter()
+        .any(|info| info.tag == "lang"));
+}
+
+#[test]
+fn json_storage() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+
+    check_storage(&mut storage);
+
+    // every change is already saved
+    let loaded = LinkStore::load(dir.join("links.json")).unwrap();
+    assert_eq!(
+        Storage::links(&loaded).unwrap(),
+        Storage::links(&storage).unwrap()
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn checks_keep_backups() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+
+    let link = storage
+        .insert(Link::new("https://www.rust-lang.org"))
+        .unwrap();
+    storage.update(link.clone().title("Rust")).unwrap();
+    let backups = storage.backups().unwrap();
+
+    let checks = vec![(link.id.unwrap(), LinkCheck::new(&link.url).status(200))];
+    assert_eq!(storage.save_checks(checks).unwrap().len(), 1);
+
+    assert_eq!(storage.backups().unwrap(), backups);
+    let loaded = LinkStore::load(dir.join("links.json")).unwrap();
+    assert!(Storage::links(&loaded).unwrap()[0].check.is_some());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn json_storage_is_unchanged_when_saving_fails() {
+    let dir = temp_dir();
+    let path = dir.join("links.json");
+    let mut storage = LinkStore::load(&path).unwrap();
+    let link = Storage::insert(&mut storage, Link::new("https://www.rust-lang.org")).unwrap();
+
+    // the file can't be written anymore
+    fs::remove_file(&path).unwrap();
+    fs::create_dir(&path).unwrap();
+
+    let storage: &mut dyn Storage = &mut storage;
+    assert!(storage.insert(Link::new("https://docs.rs")).is_err());
+    assert!(storage.update(link.clone().title("Rust")).is_err());
+    assert!(storage.trash(link.id.unwrap()).is_err());
+    assert!(storage
+        .save_search(SavedSearch::new("Rust", "rust"))
+        .is_err());
+    assert!(storage.save_tag_info(TagInfo::new("Rust")).is_err());
+
+    assert_eq!(storage.links().unwrap(), vec![link]);
+    assert!(storage.trashed().unwrap().is_empty());
+    assert!(storage.saved_searches().unwrap().is_empty());
+    assert!(storage.tag_infos().unwrap().is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn migrates_into_json_with_one_write() {
+    use dsaver_core::storage::migrate_storage;
+
+    let dir = temp_dir();
+    let mut from = LinkStore::load(dir.join("old").join("links.json")).unwrap();
+    Storage::replace_all(&mut from, links()).unwrap();
+    Storage::save_search(&mut from, SavedSearch::new("Rust", "tag:rust")).unwrap();
+    Storage::save_tag_info(&mut from, TagInfo::new("Rust").color("#dea584")).unwrap();
+
+    let mut to = LinkStore::load(dir.join("new").join("links.json"))
+        .unwrap()
+        .max_backups(100);
+    Storage::insert(&mut to, Link::new("https://docs.rs")).unwrap();
+    Storage::save_search(&mut to, SavedSearch::new("Docs", "docs")).unwrap();
+    Storage::save_tag_info(&mut to, TagInfo::new("Docs")).unwrap();
+    let backups = Storage::backups(&to).unwrap().len();
+
+    // the backups are named by milliseconds
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    assert_eq!(migrate_storage(&from, &mut to).unwrap(), 3);
+
+    assert_eq!(Storage::backups(&to).unwrap().len(), backups + 1);
+    let loaded = LinkStore::load(dir.join("new").join("links.json")).unwrap();
+    assert_eq!(loaded.links(), from.links());
+    assert_eq!(loaded.saved_searches(), from.saved_searches());
+    assert_eq!(loaded.tag_infos(), from.tag_infos());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_storage() {
+    use dsaver_core::storage::SqliteStorage;
+
+    let dir = temp_dir();
+    let mut storage = SqliteStorage::open(dir.join("links.db")).unwrap();
+
+    check_storage(&mut storage);
+
+    // every change is already saved
+    let loaded = SqliteStorage::open(dir.join("links.db")).unwrap();
+    assert_eq!(loaded.links().unwrap(), storage.links().unwrap());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn migrates_between_storages() {
+    use dsaver_core::storage::{migrate_storage, SqliteStorage};
+
+    let dir = temp_dir();
+    let mut json = LinkStore::load(dir.join("links.json")).unwrap();
+    let mut sqlite = SqliteStorage::open(dir.join("links.db")).unwrap();
+
+    json.replace_all(links()).unwrap();
+    let search = json.save_search(SavedSearch::new("Rust", "tag:rust"));
+    let info = json.save_tag_info(TagInfo::new("Rust").color("#dea584"));
+    let trashed = json.trash(links_of(&json)[2].id.unwrap()).unwrap();
+
+    assert_eq!(migrate_storage(&json, &mut sqlite).unwrap(), 2);
+    assert_eq!(sqlite.links().unwrap(), links_of(&json));
+    assert_eq!(sqlite.saved_searches().unwrap(), vec![search.clone()]);
+    assert_eq!(sqlite.tag_infos().unwrap(), vec![info.clone()]);
+    assert_eq!(sqlite.trashed().unwrap(), vec![trashed.clone()]);
+
+    // back again, replacing what is there
+    let id = links_of(&json)[0].id.unwrap();
+    Storage::delete(&mut json, id).unwrap();
+    assert_eq!(migrate_storage(&sqlite, &mut json).unwrap(), 2);
+    assert_eq!(links_of(&json), sqlite.links().unwrap());
+    assert_eq!(json.saved_searches(), &vec![search]);
+    assert_eq!(json.tag_infos(), &vec![info]);
+    assert_eq!(json.trashed(), &vec![trashed]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+fn links_of(store: &LinkStore) -> Vec<Link> {
+    Storage::links(store).unwrap()
+}
@@ -0,0 +1,377 @@
+use dsaver_core::journal::{Change, Journal};
+use dsaver_core::prelude::*;
+use dsaver_core::query::Query;
+use dsaver_core::storage::{LinkStore, Storage};
+use dsaver_core::tag::{delete_tag, merge_tags, rename_tag, tag_tree, TagInfo};
+use std::fs;
+
+mod common;
+use common::temp_dir;
+
+fn tags(storage: &dyn Storage) -> Vec<Vec<String>> {
+    storage
+        .links()
+        .unwrap()
+        .into_iter()
+        .map(|link| link.tags)
+        .collect()
+}
+
+fn insert_links(storage: &mut dyn Storage) -> Vec<Link> {
+    [
+        "Rust Code",
+        "RustLang Docs",
+        "Rust RustLang Video",
+        "Python",
+    ]
+    .iter()
+    .enumerate()
+    .map(|(i, tags)| {
+        storage
+            .insert(Link::new(format!("https://example.com/{i}")).tags(*tags))
+            .unwrap()
+    })
+    .collect()
+}
+
+#[test]
+fn renames_merges_and_deletes_tags() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let links = insert_links(storage);
+
+    // rename
+    let change = rename_tag(storage, "Code", "Programming").unwrap();
+    assert!(matches!(&change, Change::TagRenamed { before, .. } if before == &links[..1]));
+    assert_eq!(tags(storage)[0], vec!["Rust", "Programming"]);
+
+    // merge keeps the position of the first tag and removes the duplicate
+    let change = merge_tags(storage, "RustLang", "Rust").unwrap();
+    assert!(matches!(&change, Change::TagsMerged { after, .. } if after.len() == 2));
+    assert_eq!(
+        tags(storage),
+        vec![
+            vec!["Rust", "Programming"],
+            vec!["Rust", "Docs"],
+            vec!["Rust", "Video"],
+            vec!["Python"],
+        ]
+    );
+
+    // delete keeps the links
+    delete_tag(storage, "Rust").unwrap();
+    assert_eq!(
+        tags(storage),
+        vec![
+            vec!["Programming"],
+            vec!["Docs"],
+            vec!["Video"],
+            vec!["Python"],
+        ]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn fails_on_invalid_tags() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let links = insert_links(storage);
+
+    let error_type =
+        |result: Result<Change, ErrorReporter>| result.unwrap_err().error_type().clone();
+
+    assert_eq!(
+        error_type(rename_tag(storage, "Code", "Source Code")),
+        ErrorType::InvalidTag
+    );
+    assert_eq!(
+        error_type(rename_tag(storage, "Code", "Python")),
+        ErrorType::InvalidTag
+    );
+    assert_eq!(
+        error_type(rename_tag(storage, "Go", "Golang")),
+        ErrorType::TagNotFound
+    );
+    assert_eq!(
+        error_type(merge_tags(storage, "Rust", "Rust")),
+        ErrorType::InvalidTag
+    );
+    assert_eq!(
+        error_type(merge_tags(storage, "Rust", "Go")),
+        ErrorType::TagNotFound
+    );
+    assert_eq!(
+        error_type(delete_tag(storage, "Go")),
+        ErrorType::TagNotFound
+    );
+
+    // nothing is changed
+    assert_eq!(storage.links().unwrap(), links);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn undoes_every_tag_change_at_once() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+    let links = insert_links(storage);
+
+    let change = merge_tags(storage, "RustLang", "Rust").unwrap();
+    journal.record(change).unwrap();
+    let merged = storage.links().unwrap();
+
+    let change = delete_tag(storage, "Rust").unwrap();
+    journal.record(change).unwrap();
+
+    journal.undo(storage).unwrap();
+    assert_eq!(storage.links().unwrap(), merged);
+
+    journal.undo(storage).unwrap();
+    assert_eq!(storage.links().unwrap(), links);
+
+    journal.redo(storage).unwrap();
+    assert_eq!(storage.links().unwrap(), merged);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn nests_tags() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+
+    for (i, tags) in [
+        "dev/rust/async",
+        "dev\\rust //dev/rust/",
+        "dev/python Video",
+    ]
+    .iter()
+    .enumerate()
+    {
+        let link = Link::new(format!("https://example.com/{i}"))
+            .tags(*tags)
+            .complete(i == 0);
+        storage.insert(link).unwrap();
+    }
+
+    // the separators are normalized
+    assert_eq!(
+        tags(storage),
+        vec![
+            vec!["dev/rust/async"],
+            vec!["dev/rust"],
+            vec!["dev/python", "Video"]
+        ]
+    );
+
+    // the counts include the child tags
+    let tree = tag_tree(&storage.links().unwrap());
+    let summary = |node: &dsaver_core::tag::TagNode| {
+        (
+            node.path.clone(),
+            node.name.clone(),
+            node.count,
+            node.complete,
+        )
+    };
+    assert_eq!(
+        tree.iter().map(summary).collect::<Vec<_>>(),
+        vec![
+            ("Video".into(), "Video".into(), 1, 0),
+            ("dev".into(), "dev".into(), 3, 1),
+        ]
+    );
+    assert_eq!(
+        tree[1].children.iter().map(summary).collect::<Vec<_>>(),
+        vec![
+            ("dev/python".into(), "python".into(), 1, 0),
+            ("dev/rust".into(), "rust".into(), 2, 1),
+        ]
+    );
+    assert_eq!(tree[1].children[1].children[0].path, "dev/rust/async");
+
+    // a parent tag matches its child tags, but not the tags with the same beginning
+    let query = Query::parse("tag:DEV/Rust").unwrap();
+    let links = storage.links().unwrap();
+    assert_eq!(links.iter().filter(|link| query.matches(link)).count(), 2);
+    assert!(!query.matches(&Link::new("https://example.com").tags("dev/rustacean")));
+
+    // the child tags are moved with their parent
+    rename_tag(storage, "dev/rust", "code/rust").unwrap();
+    assert_eq!(
+        tags(storage),
+        vec![
+            vec!["code/rust/async"],
+            vec!["code/rust"],
+            vec!["dev/python", "Video"]
+        ]
+    );
+
+    merge_tags(storage, "code", "dev").unwrap();
+    assert_eq!(
+        tags(storage),
+        vec![
+            vec!["dev/rust/async"],
+            vec!["dev/rust"],
+            vec!["dev/python", "Video"]
+        ]
+    );
+    assert_eq!(
+        merge_tags(storage, "dev", "dev/rust")
+            .unwrap_err()
+            .error_type(),
+        &ErrorType::InvalidTag
+    );
+    assert_eq!(
+        rename_tag(storage, "Video", "dev//video")
+            .unwrap_err()
+            .error_type(),
+        &ErrorType::InvalidTag
+    );
+
+    delete_tag(storage, "dev/rust").unwrap();
+    assert_eq!(
+        tags(storage),
+        vec![vec![], vec![], vec!["dev/python", "Video"]]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn moves_tag_infos_with_tags() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+
+    for (i, tags) in ["dev dev/rust", "code", "Video"].iter().enumerate() {
+        storage
+            .insert(Link::new(format!("https://example.com/{i}")).tags(*tags))
+            .unwrap();
+    }
+
+    let dev = TagInfo::new("dev").color("#2e8b57");
+    let rust = TagInfo::new("dev/rust").icon("🦀");
+    let code = TagInfo::new("code").color("#0099cc");
+    for info in [&dev, &rust, &code] {
+        storage.save_tag_info(info.clone()).unwrap();
+    }
+    let infos = storage.tag_infos().unwrap();
+
+    let tags_of = |storage: &dyn Storage| -> Vec<String> {
+        storage
+            .tag_infos()
+            .unwrap()
+            .into_iter()
+            .map(|info| info.tag)
+            .collect()
+    };
+
+    // renamed with the child tags
+    let change = rename_tag(storage, "dev", "programming").unwrap();
+    journal.record(change).unwrap();
+    assert_eq!(
+        storage.tag_infos().unwrap(),
+        vec![
+            code.clone(),
+            TagInfo::new("programming").color("#2e8b57"),
+            TagInfo::new("programming/rust").icon("🦀"),
+        ]
+    );
+
+    // the tag which is merged into keeps its own info
+    let change = merge_tags(storage, "programming", "code").unwrap();
+    journal.record(change).unwrap();
+    assert_eq!(tags_of(storage), vec!["code", "code/rust"]);
+    assert_eq!(storage.tag_infos().unwrap()[0], code);
+
+    let change = delete_tag(storage, "code").unwrap();
+    journal.record(change).unwrap();
+    assert!(storage.tag_infos().unwrap().is_empty());
+
+    // every change of the infos is undone with its tag
+    for _ in 0..3 {
+        journal.undo(storage).unwrap();
+    }
+    assert_eq!(storage.tag_infos().unwrap(), infos);
+
+    journal.redo(storage).unwrap();
+    assert_eq!(
+        tags_of(storage),
+        vec!["code", "programming", "programming/rust"]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn renames_in_one_write() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json"))
+        .unwrap()
+        .max_backups(100);
+    let storage: &mut dyn Storage = &mut storage;
+
+    for i in 0..15 {
+        storage
+            .insert(Link::new(format!("https://example.com/{i}")).tags("Rust"))
+            .unwrap();
+    }
+    storage
+        .save_tag_info(TagInfo::new("Rust").icon("🦀"))
+        .unwrap();
+    let backups = storage.backups().unwrap();
+
+    // the names of the backups only keep milliseconds
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    rename_tag(storage, "Rust", "RustLang").unwrap();
+
+    // only the file before renaming is backed up, the older backups are kept
+    let after = storage.backups().unwrap();
+    assert_eq!(after.len(), backups.len() + 1);
+    assert_eq!(&after[1..], &backups[..]);
+    assert!(tags(storage).iter().all(|tags| tags == &["RustLang"]));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn undoing_tags_keeps_other_edits() {
+    let dir = temp_dir();
+    let mut storage = LinkStore::load(dir.join("links.json")).unwrap();
+    let storage: &mut dyn Storage = &mut storage;
+    let mut journal = Journal::open(dir.join("journal.jsonl")).unwrap();
+    let links = insert_links(storage);
+
+    let change = rename_tag(storage, "Code", "Programming").unwrap();
+    journal.record(change).unwrap();
+
+    // the link is edited after renaming the tag
+    let edited = storage
+        .update(
+            storage
+                .get(links[0].id.unwrap())
+                .unwrap()
+                .unwrap()
+                .title("Rust"),
+        )
+        .unwrap();
+    storage.delete(links[3].id.unwrap()).unwrap();
+
+    journal.undo(storage).unwrap();
+
+    let undone = storage.get(links[0].id.unwrap()).unwrap().unwrap();
+    assert_eq!(undone.tags, links[0].tags);
+    assert_eq!(undone.title, edited.title);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
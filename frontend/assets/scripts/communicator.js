@@ -2,68 +2,437 @@
 // NOTE: If you want to send data to Rust, you need to serialize it to JSON and then in Rust deserialize it to Rust object
 // NOTE: If you want to send data to backend, you need to serialize it to JSON string and then in backend deserialize it to Rust object via `serde_json::from_str()`
 
-// The directory to store data from the app
-const ROOT_DIR = ".DSaver";
+// Get all links from the Rust backend. It will always return String. The string can be parsed as Vec<Link> if the links are successfully read. If any error occur, it can be parsed as `ErrorReporter`.
+export async function getLinks() {
+    const invoke = window.__TAURI__.invoke;
 
+    try {
+        return JSON.stringify(await invoke("get_links"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
 
-// Store data in the file system. It will return Option<String>. If it returns None, it means success. Else it means error. 
-export async function storeData(fullData) {
-    const { writeTextFile, createDir, BaseDirectory } = window.__TAURI__.fs;
+// Validate the link and save it. It will always return String. If this function successfully adds the data, then it will return `Link`. If any error occur, it will probably return `ErrorReporter`.
+export async function addLink(link) {
+    const invoke = window.__TAURI__.invoke;
 
     try {
-        // create the directory
-        await createDir(ROOT_DIR, { dir: BaseDirectory.Home, recursive: true });
-        // write the data into the file
-        await writeTextFile(`${ROOT_DIR}/links.json`, fullData, { dir: BaseDirectory.Home, recursive: true });
+        // Validate the link and get new link from it
+        const returnedData = await invoke("add_link", { link });
+        console.log("New returned data ", returnedData);
 
-        return null;
+        return JSON.stringify(returnedData);
     } catch (error) {
         console.log("ERROR: ", error);
         return JSON.stringify(error);
     }
 }
 
-// Add data in the file system. It will return Option<String>. It will always return String. The string can be used by by parsing it from json to Rust structs. If this function successfully adds the data, then it will return `Link`. If any error occur, it will probably return `ErrorReporter`.
-export async function addData(fullDataArg, newData) {
+// Save the link without validating it. It will always return String. The string can be parsed as `Link` or `ErrorReporter`.
+export async function insertLink(link) {
     const invoke = window.__TAURI__.invoke;
-    // parse the json into a js array
-    const fullData = JSON.parse(fullDataArg);
 
     try {
-        // Validate the link and get new link from it
-        const returnedData = await invoke("validate_link", { link: newData });
-        console.log("New returned data ", returnedData);
+        return JSON.stringify(await invoke("insert_link", { link }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
 
-        // push the new data to the full data
-        fullData.push(returnedData);
+// Update the link which has the same id. It will always return String. The string can be parsed as `Link` or `ErrorReporter`.
+export async function updateLink(link) {
+    const invoke = window.__TAURI__.invoke;
 
-        await storeData(JSON.stringify(fullData));
+    try {
+        return JSON.stringify(await invoke("update_link", { link }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
 
-        return JSON.stringify(returnedData);
+// Move the link with the given id into the trash. It will always return String. The string can be parsed as `Link` (the deleted link) or `ErrorReporter`.
+export async function deleteLink(id) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("delete_link", { id }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Save the order of the links chosen by the user. It will always return String. The string can be parsed as Vec<Link> or `ErrorReporter`.
+export async function reorderLinks(ids) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("reorder_links", { ids }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Undo the last change of the user. It will always return String. The string can be parsed as Vec<Link> or `ErrorReporter`.
+export async function undo() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("undo"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Redo the last undone change of the user. It will always return String. The string can be parsed as Vec<Link> or `ErrorReporter`.
+export async function redo() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("redo"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Get the activity log of the user. It will always return String. The string can be parsed as Vec<JournalEntry> or `ErrorReporter`.
+export async function getActivity() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("get_activity"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Get all the links inside the trash. It will always return String. The string can be parsed as Vec<TrashedLink> or `ErrorReporter`.
+export async function getTrash() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("get_trash"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Move the link with the given id out of the trash. It will always return String. The string can be parsed as Link (the restored link) or `ErrorReporter`.
+export async function restoreLink(id) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("restore_link", { id }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Delete forever the link with the given id from the trash. It will always return String. The string can be parsed as TrashedLink or `ErrorReporter`.
+export async function purgeLink(id) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("purge_link", { id }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Delete forever all the links inside the trash. It will always return String. The string can be parsed as Vec<TrashedLink> or `ErrorReporter`.
+export async function emptyTrash() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("empty_trash"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Rename a tag in every link. It will always return String. The string can be parsed as Vec<Link> (all links after renaming) or `ErrorReporter`.
+export async function renameTag(from, to) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("rename_tag", { from, to }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Replace a tag with another tag in every link. It will always return String. The string can be parsed as Vec<Link> (all links after merging) or `ErrorReporter`.
+export async function mergeTags(from, into) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("merge_tags", { from, into }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Remove a tag from every link. It will always return String. The string can be parsed as Vec<Link> (all links after removing the tag) or `ErrorReporter`.
+export async function deleteTag(tag) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("delete_tag", { tag }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Get the colors, icons and descriptions of the tags. It will always return String. The string can be parsed as Vec<TagInfo> or `ErrorReporter`.
+export async function getTagInfos() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("get_tag_infos"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Save the color, icon and description of a tag. It will always return String. The string can be parsed as `TagInfo` or `ErrorReporter`.
+export async function saveTagInfo(info) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("save_tag_info", { info }));
     } catch (error) {
         console.log("ERROR: ", error);
         return JSON.stringify(error);
     }
+}
+
+// Get the url of a cached image (a favicon or a thumbnail) from its key. The image is served by the backend through the `dsimg` protocol, so it is never loaded from the website.
+export function imageSrc(key) {
+    return window.__TAURI__.tauri.convertFileSrc(key, "dsimg");
+}
+
+// Check every link in the background. It will always return String. The string can be parsed as the number of links which will be checked (`0` if the links are already being checked) or `ErrorReporter`.
+export async function checkLinks() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("check_links"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Listen the progress of checking the links. `progress` is called with a JSON string of `CheckProgress` and `done` with the number of checked links. It returns a function which removes the listeners.
+export function onLinkCheck(progress, done) {
+    const listen = window.__TAURI__.event.listen;
+
+    const unlisteners = [
+        listen("link-check-progress", (event) => progress(JSON.stringify(event.payload))),
+        listen("link-check-done", (event) => done(event.payload)),
+    ];
+
+    return () => unlisteners.forEach((unlisten) => unlisten.then((remove) => remove()));
+}
+
+// Fetch the webpage of a saved link again. It will always return String. The string can be parsed as `LinkRefresh` (what is changed, nothing is saved yet) or `ErrorReporter`.
+export async function refreshLink(id) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("refresh_link", { id }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Save the changes of a refreshed link. It will always return String. The string can be parsed as `Link` (the updated link) or `ErrorReporter`.
+export async function applyRefresh(refresh) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("apply_refresh", { refresh }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Get all backups of the links. It will always return String. The string can be parsed as Vec<Backup> or `ErrorReporter`.
+export async function listBackups() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("list_backups"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Restore the backup with the given name. It will always return String. The string can be parsed as Vec<Link> (the restored links) or `ErrorReporter`.
+export async function restoreBackup(name) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("restore_backup", { name }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Save the links recovered from the corrupted file. It will always return String. The string can be parsed as Vec<Link> or `ErrorReporter`.
+export async function keepRecoveredLinks() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("keep_recovered_links"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Open the copy of the corrupted file. It will always return String. If it can be parsed as `ErrorReporter`, then the file couldn't be opened.
+export async function openQuarantinedFile() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("open_quarantined_file"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Get the links which match the filter. It will always return String. The string can be parsed as Vec<Link> or `ErrorReporter`.
+export async function queryLinks(filter) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("query_links", { filter }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Get the ids of the links which match the filter. It will always return String. The string can be parsed as Vec<Uuid> or `ErrorReporter`.
+export async function queryLinkIds(filter) {
+    const invoke = window.__TAURI__.invoke;
 
+    try {
+        return JSON.stringify(await invoke("query_link_ids", { filter }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
 }
 
-// Get data from the file system. It returns Option<String>. It will be None if the file is not exits or can't read the file. The string can be used by parsing it from json to Rust struct. You can parse the String to Vec<Link> if the file contains valid data
-export async function getData() {
-    const { readTextFile, BaseDirectory } = window.__TAURI__.fs;
+// Search the links which match the query and the filter. It will always return String. The string can be parsed as Vec<SearchHit> or `ErrorReporter`.
+export async function searchLinks(query, filter) {
+    const invoke = window.__TAURI__.invoke;
 
-    // The file may not exist, so we need to handle the error
     try {
-        // if the file exists, read the data from the file
-        return await readTextFile(`${ROOT_DIR}/links.json`, { dir: BaseDirectory.Home });
+        return JSON.stringify(await invoke("search_links", { query, filter }));
     } catch (error) {
-        // else return null
-        return null;
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
     }
+}
+
+// Get all saved searches. It will always return String. The string can be parsed as Vec<SavedSearch> or `ErrorReporter`.
+export async function getSavedSearches() {
+    const invoke = window.__TAURI__.invoke;
 
+    try {
+        return JSON.stringify(await invoke("get_saved_searches"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Save a new search or replace the saved search with the same id. It will always return String. The string can be parsed as `SavedSearch` or `ErrorReporter`.
+export async function saveSearch(search) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("save_search", { search }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
 }
 
-// Open the browser corresponding to the the user's OS
-export async function openBrowser(path, browser) {
+// Delete a saved search. It will always return String. The string can be parsed as `SavedSearch` or `ErrorReporter`.
+export async function deleteSearch(id) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("delete_search", { id }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Get the settings of the app. It will always return String. The string can be parsed as `Settings` or `ErrorReporter`.
+export async function getSettings() {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("get_settings"));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Save the settings of the app. It will always return String. The string can be parsed as `Settings` or `ErrorReporter`.
+export async function updateSettings(settings) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("update_settings", { settings }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Move all links into another storage. It will always return String. The string can be parsed as `Settings` or `ErrorReporter`.
+export async function migrateStorage(backend) {
+    const invoke = window.__TAURI__.invoke;
+
+    try {
+        return JSON.stringify(await invoke("migrate_storage", { backend }));
+    } catch (error) {
+        console.log("ERROR: ", error);
+        return JSON.stringify(error);
+    }
+}
+
+// Open the browser corresponding to the the user's OS. The `id` of a saved link is used to remember when it was opened.
+export async function openBrowser(path, browser, id) {
     const invoke = window.__TAURI__.invoke;
     const { platform } = window.__TAURI__.os;
 
@@ -82,7 +451,7 @@ export async function openBrowser(path, browser) {
     }
 
     try {
-        const result = await invoke(functionName, { path, browser });
+        const result = await invoke(functionName, { path, browser, id });
         return JSON.stringify(result);
     } catch (err) {
         return JSON.stringify(err);
@@ -95,16 +464,9 @@ export async function generateLink() {
 
     const links = await invoke("generate");
 
-    let oldData = JSON.parse(await getData());
-
-    oldData.forEach(data => {
-        links.push(data);
-    });
-
-    let result = await storeData(JSON.stringify(links));
-
-    if (result === null) {
-        console.log("Random links are created");
+    for (const link of links) {
+        await invoke("insert_link", { link: JSON.stringify(link) });
     }
 
-}
\ No newline at end of file
+    console.log("Random links are created");
+}
@@ -22,6 +22,32 @@ export function ifNotClicked(elementId, whatToDo) {
     return () => document.removeEventListener('click', callback);
 }
 
+// Call `undo` when the user presses Ctrl+Z and `redo` when the user presses Ctrl+Shift+Z (or Ctrl+Y).
+// Inside a text field the shortcuts are left to the text field.
+export function onUndoRedo(undo, redo) {
+    function callback(event) {
+        const target = event.target;
+
+        if (!(event.ctrlKey || event.metaKey) || target.tagName === 'INPUT' || target.tagName === 'TEXTAREA' || target.isContentEditable) {
+            return;
+        }
+
+        const key = event.key.toLowerCase();
+
+        if (key === 'z' && !event.shiftKey) {
+            event.preventDefault();
+            undo();
+        } else if ((key === 'z' && event.shiftKey) || key === 'y') {
+            event.preventDefault();
+            redo();
+        }
+    }
+
+    document.addEventListener('keydown', callback);
+
+    return () => document.removeEventListener('keydown', callback);
+}
+
 // Decrease the opacity of the given id
 export function downOpacity(elementId) {
     const element = document.getElementById(elementId);
@@ -0,0 +1,137 @@
+use crate::*;
+use chrono::Local;
+use dsaver_core::journal::{EntryKind, JournalEntry};
+
+/// Show the activity log of the user and undo or redo the changes
+///
+/// This component is a popup box and it will appear if you set the [`PopupBox`] to [`PopupBox::Activity`].
+///
+/// It shows every change made, undone and redone by the user (newest first). The changes can also be undone with Ctrl+Z and redone with Ctrl+Shift+Z, see [`App`].
+#[function_component(Activity)]
+pub fn activity() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+
+    // `None` until the activity is loaded
+    let entries = use_state(|| None::<Vec<JournalEntry>>);
+
+    {
+        // Load the activity when the component is mounted and after every undo or redo
+        let entries = entries.clone();
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let result = get_activity().await.unwrap().as_string().unwrap();
+
+                    if let Ok(list) = string_to_struct::<Vec<JournalEntry>>(&result) {
+                        entries.set(Some(list));
+                    } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                        show_journal_error(error_reporter, display_error_data, popup_box_state);
+                    } else {
+                        console_error!(result);
+                    }
+                });
+
+                || ()
+            },
+            links.clone(),
+        );
+    }
+
+    let button = |text: &'static str, redo: bool| {
+        let links = links.clone();
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
+
+        html! {
+            <button class={classes!("button", text.to_lowercase())} onclick={
+                move |_| undo_or_redo(redo, links.clone(), display_error_data.clone(), popup_box_state.clone())
+            }>{text}</button>
+        }
+    };
+
+    html! {
+        <Popup title="Activity" id="activity">
+            <div class="activity">
+                <div class="buttons">
+                    {button("Undo", false)}
+                    {button("Redo", true)}
+                </div>
+                {
+                    match &*entries {
+                        None => html! {
+                            <p class="message">{"Loading activity..."}</p>
+                        },
+                        Some(list) if list.is_empty() => html! {
+                            <p class="message">{"No activity yet. Every link you add, edit or delete is shown here."}</p>
+                        },
+                        Some(list) => list.iter().map(|entry| {
+                            let kind = match entry.kind {
+                                EntryKind::Done => "",
+                                EntryKind::Undone => "Undone",
+                                EntryKind::Redone => "Redone",
+                                EntryKind::Dropped => "Dropped",
+                            };
+
+                            html! {
+                                <div class={classes!("entry", kind.to_lowercase())}>
+                                    <p class="date">{entry.at.with_timezone(&Local).format("%d %B %Y, %H:%M").to_string()}</p>
+                                    <p class="description">
+                                        if !kind.is_empty() {
+                                            <span class="kind">{kind}</span>
+                                        }
+                                        {entry.change.description()}
+                                    </p>
+                                </div>
+                            }
+                        }).collect::<Html>(),
+                    }
+                }
+            </div>
+        </Popup>
+    }
+}
+
+/// Undo the last change of the user (or redo the last undone change if `redo` is `true`), and update the [`LinksState`] state with the links after it
+pub fn undo_or_redo(
+    redo: bool,
+    links: UseStateHandle<Vec<Link>>,
+    display_error_data: UseStateHandle<Option<DisplayErrorInnerData>>,
+    popup_box_state: UseStateHandle<PopupBox>,
+) {
+    spawn_local(async move {
+        let result = if redo {
+            crate::redo().await
+        } else {
+            undo().await
+        };
+        let result = result.unwrap().as_string().unwrap();
+
+        if let Ok(new_links) = string_to_struct::<Vec<Link>>(&result) {
+            links.set(new_links);
+        } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+            show_journal_error(error_reporter, display_error_data, popup_box_state);
+        } else {
+            console_error!(result);
+        }
+    });
+}
+
+/// Show the error in the [`DisplayError`] popup
+fn show_journal_error(
+    error_reporter: ErrorReporter,
+    display_error_data: UseStateHandle<Option<DisplayErrorInnerData>>,
+    popup_box_state: UseStateHandle<PopupBox>,
+) {
+    display_error_data.set(Some(DisplayErrorInnerData {
+        class: DisplayErrorClass::Error,
+        error_reporter,
+        options_buttons: None,
+        options_message: None,
+    }));
+
+    popup_box_state.set(PopupBox::DisplayError);
+}
@@ -1,4 +1,7 @@
 use crate::*;
+use dsaver_core::check::CheckProgress;
+use dsaver_core::settings::Settings;
+use dsaver_core::tag::{is_within, TagInfo};
 
 
 /// State of collection of [`Link`]s.
@@ -38,14 +41,6 @@ pub struct EditingLinkIdState(pub UseStateHandle<Option<Uuid>>);
 #[derive(Clone, PartialEq)]
 pub struct LinksTagsState(pub UseStateHandle<HashMap<String, i32>>);
 
-/// The tags, through which the links will be shown.
-/// 
-/// Those tags will be stored here which user has selected/filtered to show the links.
-/// 
-/// This state can be get from anywhere in the app. But it should only be changed when the user selects the tags to show the links associated with those tags.
-#[derive(Clone, PartialEq)]
-pub struct DisplayedTagsState(pub UseStateHandle<Vec<String>>);
-
 /// List of all browsers from all [`Link`]s.
 /// 
 /// All link's browsers will be stored here. And you can use this state everywhere in the app.
@@ -67,13 +62,103 @@ pub struct DisplayedTagsState(pub UseStateHandle<Vec<String>>);
 pub struct LinksBrowsersState(pub UseStateHandle<HashMap<Browser, i32>>);
 
 
-/// The browsers, through which the links will be shown.
+/// List of all priorities from all [`Link`]s, and how many links have each priority.
+/// 
+/// It is updated when the links state [`LinksState`] changes, just like [`LinksTagsState`].
+#[derive(Clone, PartialEq)]
+pub struct LinksPrioritiesState(pub UseStateHandle<HashMap<char, i32>>);
+
+/// List of all domains (see [`Link::domain`]) from all [`Link`]s, and how many links are from each domain.
+/// 
+/// It is updated when the links state [`LinksState`] changes, just like [`LinksTagsState`]. The links without a domain are not counted.
+#[derive(Clone, PartialEq)]
+pub struct LinksDomainsState(pub UseStateHandle<HashMap<String, i32>>);
+
+/// How the displayed links are grouped, see [`GroupBy`].
+/// 
+/// By default the links are grouped by their priority.
+#[derive(Clone, PartialEq)]
+pub struct GroupByState(pub UseStateHandle<GroupBy>);
+
+/// How the displayed links are sorted, see [`SortOrder`].
+/// 
+/// It is loaded from the settings when the app starts, and saved into the settings by the [`SortSwitch`] component. So the user's choice is remembered between sessions.
+#[derive(Clone, PartialEq)]
+pub struct SortOrderState(pub UseStateHandle<SortOrder>);
+
+/// Whether the complete links are hidden from the list, see [`Settings::archive_completed`].
+/// 
+/// It is loaded from the settings when the app starts, and changed by the [`EditSettings`] component.
+#[derive(Clone, PartialEq)]
+pub struct ArchiveCompletedState(pub UseStateHandle<bool>);
+
+/// Whether the user is looking at the archive, which shows only the complete links.
+/// 
+/// The user opens and closes the archive from the [`Sidebar`].
+#[derive(Clone, PartialEq)]
+pub struct ShowArchiveState(pub UseStateHandle<bool>);
+
+/// Whether the user is looking at the broken links, which shows only the links which were dead when they were checked last time (see [`Link::is_broken`]).
+/// 
+/// The user opens and closes it from the [`Sidebar`], just like the archive.
+#[derive(Clone, PartialEq)]
+pub struct ShowBrokenState(pub UseStateHandle<bool>);
+
+/// Progress of checking all the links in the background. It is `None` while no check is running.
+/// 
+/// The check is started from the [`Sidebar`], and the progress is sent by the backend (see [`on_link_check`]).
+#[derive(Clone, PartialEq)]
+pub struct LinkCheckProgressState(pub UseStateHandle<Option<CheckProgress>>);
+
+/// The tags and browsers, through which the links will be shown.
+/// 
+/// The user selects them in the [`Filter`] component, along with the priorities, domains, completion and creation date of the links. A tag or browser can be included (only the links which have it are shown) or excluded (the links which have it are hidden). The included ones are matched with any (OR) or all (AND) of them, see [`MatchMode`].
+/// 
+/// By default nothing is selected, so all links are shown. The selection is shown above the links by the [`FilterChips`] component.
+/// 
+/// This state can be get from anywhere in the app. But it should only be changed when the user selects the tags or browsers to show the links associated with them.
+#[derive(Clone, PartialEq)]
+pub struct LinkFilterState(pub UseStateHandle<LinkFilter>);
+
+/// The text typed by the user in the [`Search`] bar.
+/// 
+/// If it is empty, then all links will be shown.
+#[derive(Clone, PartialEq)]
+pub struct SearchQueryState(pub UseStateHandle<String>);
+
+/// All searches saved by the user.
+/// 
+/// They are shown in the sidebar by the [`SavedSearches`] component. They are loaded when the app starts, so update this state after saving or deleting a search.
+#[derive(Clone, PartialEq)]
+pub struct SavedSearchesState(pub UseStateHandle<Vec<SavedSearch>>);
+
+/// The search which is being edited inside the [`EditSavedSearch`] popup.
+/// 
+/// If its `id` is not inside [`SavedSearchesState`], then it is a new search.
+/// 
+/// If the value is `None`, it means that no search is being edited.
+#[derive(Clone, PartialEq)]
+pub struct EditingSavedSearchState(pub UseStateHandle<Option<SavedSearch>>);
+
+/// The tag which is being renamed, merged or deleted inside the [`EditTag`] popup.
 /// 
-/// Those browsers will be stored here which user has selected/filtered to show the links associated with those browsers.
+/// If the value is `None`, it means that no tag is being edited.
+#[derive(Clone, PartialEq)]
+pub struct EditingTagState(pub UseStateHandle<Option<String>>);
+
+/// The `id`s of the links which are refreshed inside the [`RefreshLinks`] popup.
 /// 
-/// This state can be get from anywhere in the app. But it should only be changed when the user selects the browsers to show the links associated with those browsers.
+/// It is set by the [`DisplayLinks`] component before opening the popup, with a single link or the selected links.
 #[derive(Clone, PartialEq)]
-pub struct DisplayedBrowsersState(pub UseStateHandle<Vec<Browser>>);
+pub struct RefreshingLinksState(pub UseStateHandle<Vec<Uuid>>);
+
+/// The colors, icons and descriptions of the tags, by their tag.
+/// 
+/// They are loaded when the app starts and whenever the links change, because renaming, merging or deleting a tag (or undoing it) moves them too. Update this state after saving the info of a tag.
+/// 
+/// A tag without any info is not inside it.
+#[derive(Clone, PartialEq)]
+pub struct TagInfosState(pub UseStateHandle<HashMap<String, TagInfo>>);
 
 /// Data state for [`DisplayError`] component.
 #[derive(Clone, PartialEq)]
@@ -188,6 +273,34 @@ pub enum PopupBox {
     /// 
     /// Use it if you want to show an error to the user.
     DisplayError,
+    /// Popup of [`Backups`] component.
+    /// 
+    /// Use it if user wants to restore the links from a backup.
+    Backups,
+    /// Popup of [`EditSettings`] component.
+    /// 
+    /// Use it if user wants to change the settings.
+    EditSettings,
+    /// Popup of [`Activity`] component.
+    /// 
+    /// Use it if user wants to see the activity log or undo the changes.
+    Activity,
+    /// Popup of [`Trash`] component.
+    /// 
+    /// Use it if user wants to restore the deleted links or delete them forever.
+    Trash,
+    /// Popup of [`EditSavedSearch`] component.
+    /// 
+    /// Use it if user wants to save or edit a search. Set the [`EditingSavedSearchState`] first.
+    EditSavedSearch,
+    /// Popup of [`EditTag`] component.
+    /// 
+    /// Use it if user wants to rename, merge or delete a tag. Set the [`EditingTagState`] first.
+    EditTag,
+    /// Popup of [`RefreshLinks`] component.
+    /// 
+    /// Use it if user wants to fetch the title, description and thumbnail of the links again. Set the [`RefreshingLinksState`] first.
+    RefreshLinks,
     /// No popup.
     /// 
     /// Use it if you don't want to show any popup to the user.
@@ -198,6 +311,93 @@ pub enum PopupBox {
     // TODO: Message popup
 }
 
+/// Replace the link which has the same `id` inside the [`LinksState`] state, e.g. after it is updated by the backend.
+pub fn replace_link(links: &UseStateHandle<Vec<Link>>, link: Link) {
+    let mut new_links = (**links).clone();
+
+    if let Some(old_link) = new_links.iter_mut().find(|old_link| old_link.id == link.id) {
+        *old_link = link;
+        links.set(new_links);
+    }
+}
+
+/// Replace every link of `new_links` which has the same `id` inside the [`LinksState`] state. The other links are not added.
+pub fn replace_links(links: &UseStateHandle<Vec<Link>>, new_links: Vec<Link>) {
+    let mut links_copy = (**links).clone();
+    let mut changed = false;
+
+    for link in new_links {
+        if let Some(old_link) = links_copy.iter_mut().find(|old_link| old_link.id == link.id) {
+            *old_link = link;
+            changed = true;
+        }
+    }
+
+    if changed {
+        links.set(links_copy);
+    }
+}
+
+/// Message for the user when the links file was corrupted and the links are recovered.
+fn recovery_message(error_reporter: &ErrorReporter) -> String {
+    match error_reporter.error_type() {
+        ErrorType::DataRecovered { recovered, lost } => format!(
+            "{recovered} links are recovered and {lost} links are lost. You can restore your links from a backup, keep the recovered links or fix the original file by hand."
+        ),
+        _ => String::new(),
+    }
+}
+
+/// Options for the user when the links file was corrupted and the links are recovered.
+fn recovery_buttons(
+    links: UseStateHandle<Vec<Link>>,
+    popup_box_state: UseStateHandle<PopupBox>,
+) -> Vec<DisplayErrorButton> {
+    vec![
+        DisplayErrorButton {
+            name: String::from("Restore from backup"),
+            button_type: DisplayErrorButtonType::Safe,
+            callback: Callback::from({
+                let popup_box_state = popup_box_state.clone();
+                move |_| popup_box_state.set(PopupBox::Backups)
+            }),
+        },
+        DisplayErrorButton {
+            name: String::from("Keep recovered"),
+            button_type: DisplayErrorButtonType::Danger,
+            callback: Callback::from(move |_| {
+                let links = links.clone();
+                let popup_box_state = popup_box_state.clone();
+
+                spawn_local(async move {
+                    let result = keep_recovered_links().await.unwrap().as_string().unwrap();
+
+                    if let Ok(recovered_links) = string_to_struct::<Vec<Link>>(&result) {
+                        links.set(recovered_links);
+                        popup_box_state.set(PopupBox::None);
+                    } else {
+                        console_error!(result);
+                    }
+                });
+            }),
+        },
+        DisplayErrorButton {
+            name: String::from("Open raw file"),
+            button_type: DisplayErrorButtonType::Safe,
+            callback: Callback::from(|_| {
+                // keep the popup open, so the user can choose another option after fixing the file
+                spawn_local(async move {
+                    let result = open_quarantined_file().await.unwrap().as_string().unwrap();
+
+                    if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                        console_error!(format!("Error: {:?}", error_reporter));
+                    }
+                });
+            }),
+        },
+    ]
+}
+
 impl DisplayErrorInnerData {
     /// Get a default value for debugging purposes.
     /// 
@@ -254,10 +454,24 @@ pub fn app() -> Html {
     let links = use_state(Vec::new);
 
     let links_tags = use_state(HashMap::new);
-    let displayed_tags = use_state(Vec::new);
-
     let links_browsers = use_state(HashMap::new);
-    let displayed_browsers = use_state(Vec::new);
+    let links_priorities = use_state(HashMap::new);
+    let links_domains = use_state(HashMap::new);
+    let link_filter = use_state(LinkFilter::default);
+    let group_by = use_state(GroupBy::default);
+    let sort_order = use_state(SortOrder::default);
+    let archive_completed = use_state(|| false);
+    let show_archive = use_state(|| false);
+    let show_broken = use_state(|| false);
+    let link_check_progress = use_state(|| None);
+
+    let search_query = use_state(String::new);
+
+    let saved_searches = use_state(Vec::new);
+    let editing_saved_search = use_state(|| None);
+    let editing_tag = use_state(|| None);
+    let refreshing_links = use_state(Vec::new);
+    let tag_infos = use_state(HashMap::new);
 
     let display_error_data = use_state(|| None);
 
@@ -277,24 +491,93 @@ pub fn app() -> Html {
 
     { // Get all the links from the filesystem
         let links = links.clone();
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
         use_effect_with_deps(
             |_| {
                 spawn_local(async move {
-                    let data = get_data().await.unwrap().as_string();
-                    
-                    if let Some(data) = data {
-                        if let Ok(data) = string_to_struct::<Vec<Link>>(&data) {
-                            // data found
-                            links.set(data);
-                        } else {
-                            // TODO: Handle error // Show the user a message that the file is corrupted. And him two options:
-                            // 1. Delete the file and start.
-                            // 2. Manually fix the file.
-                            console_error!("Error: The file is corrupted.");
-                        }
+                    let data = get_links().await.unwrap().as_string().unwrap();
+
+                    if let Ok(data) = string_to_struct::<Vec<Link>>(&data) {
+                        // data found
+                        links.set(data);
+                    } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&data) {
+                        let data_recovered = matches!(error_reporter.error_type(), ErrorType::DataRecovered { .. });
+
+                        display_error_data.set(Some(DisplayErrorInnerData {
+                            class: if data_recovered { DisplayErrorClass::Warn } else { DisplayErrorClass::Error },
+                            options_message: data_recovered.then(|| recovery_message(&error_reporter)),
+                            options_buttons: data_recovered.then(|| recovery_buttons(links, popup_box_state.clone())),
+                            error_reporter,
+                        }));
+
+                        popup_box_state.set(PopupBox::DisplayError);
                     } else {
-                        // TODO: error only for debugging
-                        console_error!("No data found from the filesystem! Create new data");
+                        console_error!("Neither `Vec<Link>` nor `ErrorReporter` was found")
+                    }
+                });
+
+                || ()
+            },
+            (),
+        );
+    }
+
+    { // Get all the saved searches from the storage
+        let saved_searches = saved_searches.clone();
+        use_effect_with_deps(
+            |_| {
+                spawn_local(async move {
+                    let data = get_saved_searches().await.unwrap().as_string().unwrap();
+
+                    if let Ok(data) = string_to_struct::<Vec<SavedSearch>>(&data) {
+                        saved_searches.set(data);
+                    } else {
+                        // the error of the storage is already shown while getting the links
+                        console_error!(data);
+                    }
+                });
+
+                || ()
+            },
+            (),
+        );
+    }
+
+    { // Get the infos of the tags from the storage, again whenever the links change
+        let tag_infos = tag_infos.clone();
+        use_effect_with_deps(
+            |_| {
+                spawn_local(async move {
+                    let data = get_tag_infos().await.unwrap().as_string().unwrap();
+
+                    if let Ok(data) = string_to_struct::<Vec<TagInfo>>(&data) {
+                        tag_infos.set(data.into_iter().map(|info| (info.tag.clone(), info)).collect());
+                    } else {
+                        // the error of the storage is already shown while getting the links
+                        console_error!(data);
+                    }
+                });
+
+                || ()
+            },
+            links.clone(),
+        );
+    }
+
+    { // Get the sort order and the archive chosen by the user from the settings
+        let sort_order = sort_order.clone();
+        let archive_completed = archive_completed.clone();
+        use_effect_with_deps(
+            |_| {
+                spawn_local(async move {
+                    let data = get_settings().await.unwrap().as_string().unwrap();
+
+                    if let Ok(settings) = string_to_struct::<Settings>(&data) {
+                        sort_order.set(settings.sort_order);
+                        archive_completed.set(settings.archive_completed);
+                    } else {
+                        console_error!(data);
                     }
                 });
 
@@ -305,12 +588,13 @@ pub fn app() -> Html {
     }
 
     
-    { // Update the value of `LinksTagsState`, `DisplayedTagsState`, `LinksBrowsersState`, `DisplayedBrowsersState` when the `LinksState` changes
+    { // Update the value of `LinksTagsState`, `LinksBrowsersState`, `LinksPrioritiesState`, `LinksDomainsState`, `LinkFilterState` when the `LinksState` changes
         let links = links.clone();
         let links_tags = links_tags.clone();
-        let displayed_tags = displayed_tags.clone();
         let links_browsers = links_browsers.clone();
-        let displayed_browsers = displayed_browsers.clone();
+        let links_priorities = links_priorities.clone();
+        let links_domains = links_domains.clone();
+        let link_filter = link_filter.clone();
         use_effect_with_deps(
             move |links| {
                 // list of tags from all links
@@ -321,6 +605,10 @@ pub fn app() -> Html {
                 // This hashmap will store two things (browser_name, how_many_same_browsers)
                 // which means it will not store same browsers multiple times. Instead it will increment the 2nd value for same browsers
                 let mut browsers_map = HashMap::new();
+                // how many links have each priority
+                let mut priorities_map = HashMap::new();
+                // how many links are from each domain
+                let mut domains_map = HashMap::new();
 
                 // looping over links
                 for link in (**links).clone() {
@@ -336,6 +624,12 @@ pub fn app() -> Html {
                         }
                     }
 
+                    *priorities_map.entry(link.priority).or_insert(0) += 1;
+
+                    if let Some(domain) = link.domain() {
+                        *domains_map.entry(domain).or_insert(0) += 1;
+                    }
+
                     // check if the `link.browser` presnet in the `browsers_map` or not
                     if let Some(browser) = browsers_map.get_mut(&link.browser) {
                         // If the browser is already present, then we will increment the second value by 1.
@@ -346,15 +640,38 @@ pub fn app() -> Html {
                     }
                 }
 
+                // unselect the tags and browsers which don't exist anymore, so they can't hide all links
+                let mut filter = (*link_filter).clone();
+                for tag in filter.tags.clone().into_iter().flatten().chain(filter.excluded_tags.clone()) {
+                    // a parent tag exists while one of its child tags exists
+                    if !tags_map.keys().any(|t: &String| is_within(t, &tag)) {
+                        remove_selection(&mut filter.tags, &mut filter.excluded_tags, &tag);
+                    }
+                }
+                for browser in filter.browsers.clone().into_iter().flatten().chain(filter.excluded_browsers.clone()) {
+                    if !browsers_map.contains_key(&browser) {
+                        remove_selection(&mut filter.browsers, &mut filter.excluded_browsers, &browser);
+                    }
+                }
+                if let Some(domains) = &mut filter.domains {
+                    domains.retain(|domain| domains_map.contains_key(domain));
+
+                    if domains.is_empty() {
+                        filter.domains = None;
+                    }
+                }
+                if filter != *link_filter {
+                    link_filter.set(filter);
+                }
+
                 // update `LinksTagsState with` the new value
-                links_tags.set(tags_map.clone());
-                // update `DisplayedTagsState` with the new value
-                displayed_tags.set(tags_map.into_keys().collect::<Vec<String>>());
+                links_tags.set(tags_map);
 
                 // update `LinksBrowsersState` with the new value
-                links_browsers.set(browsers_map.clone());
-                // update `DisplayedBrowsersState` with the new value
-                displayed_browsers.set(browsers_map.into_keys().collect::<Vec<Browser>>());
+                links_browsers.set(browsers_map);
+
+                links_priorities.set(priorities_map);
+                links_domains.set(domains_map);
 
                 || ()
             },
@@ -362,6 +679,96 @@ pub fn app() -> Html {
         );
     }
 
+    { // Listen the progress of checking the links in the background
+        let links = links.clone();
+        let link_check_progress = link_check_progress.clone();
+        use_effect_with_deps(
+            move |_| {
+                let progress_callback = Closure::wrap(Box::new({
+                    let link_check_progress = link_check_progress.clone();
+                    move |data: String| {
+                        if let Ok(progress) = string_to_struct::<CheckProgress>(&data) {
+                            link_check_progress.set(Some(progress));
+                        } else {
+                            console_error!(data);
+                        }
+                    }
+                }) as Box<dyn Fn(String)>);
+                let done_callback = Closure::wrap(Box::new(move |_: JsValue| {
+                    link_check_progress.set(None);
+
+                    // get the links again, in case a progress is missed
+                    let links = links.clone();
+                    spawn_local(async move {
+                        let data = get_links().await.unwrap().as_string().unwrap();
+
+                        if let Ok(data) = string_to_struct::<Vec<Link>>(&data) {
+                            links.set(data);
+                        } else {
+                            console_error!(data);
+                        }
+                    });
+                }) as Box<dyn Fn(JsValue)>);
+
+                let remove_listener = on_link_check(progress_callback.as_ref().unchecked_ref(), done_callback.as_ref().unchecked_ref());
+
+                move || {
+                    // remove the event listeners before the callbacks are dropped
+                    remove_listener.call0(&JsValue::UNDEFINED).unwrap();
+                    drop((progress_callback, done_callback));
+                }
+            },
+            (),
+        );
+    }
+
+    { // Show the links which are checked in the background
+        let links = links.clone();
+        use_effect_with_deps(
+            move |progress| {
+                if let Some(progress) = &**progress {
+                    replace_links(&links, progress.links.clone());
+                }
+
+                || ()
+            },
+            link_check_progress.clone(),
+        );
+    }
+
+    { // Undo with Ctrl+Z and redo with Ctrl+Shift+Z. Not while a popup is open, because it may show a link which is changed by undoing
+        let links = links.clone();
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
+        use_effect_with_deps(
+            move |state| {
+                let listener = if **state == PopupBox::None {
+                    let undo_callback = callback({
+                        let (links, display_error_data, popup_box_state) = (links.clone(), display_error_data.clone(), popup_box_state.clone());
+                        move || undo_or_redo(false, links.clone(), display_error_data.clone(), popup_box_state.clone())
+                    });
+                    let redo_callback = callback(move || {
+                        undo_or_redo(true, links.clone(), display_error_data.clone(), popup_box_state.clone())
+                    });
+
+                    let remove_listener = on_undo_redo(undo_callback.as_ref().unchecked_ref(), redo_callback.as_ref().unchecked_ref());
+
+                    Some((remove_listener, undo_callback, redo_callback))
+                } else {
+                    None
+                };
+
+                move || {
+                    // remove the event listener before the callbacks are dropped
+                    if let Some((remove_listener, _, _)) = listener {
+                        remove_listener.call0(&JsValue::UNDEFINED).unwrap();
+                    }
+                }
+            },
+            popup_box_state,
+        );
+    }
+
     { // When any popup appears, decrease the opacity of the background (#app element) and increase when the popup disappears
         let popup_box_state = popup_box_state.clone();
         use_effect_with_deps(
@@ -398,9 +805,22 @@ pub fn app() -> Html {
         <ContextProvider<LinksState> context={LinksState(links)}>
         <ContextProvider<EditingLinkIdState> context={EditingLinkIdState(editing_link_id)}>
         <ContextProvider<LinksTagsState> context={LinksTagsState(links_tags)}>
-        <ContextProvider<DisplayedTagsState> context={DisplayedTagsState(displayed_tags)}>
         <ContextProvider<LinksBrowsersState> context={LinksBrowsersState(links_browsers)}>
-        <ContextProvider<DisplayedBrowsersState> context={DisplayedBrowsersState(displayed_browsers)}>
+        <ContextProvider<LinksPrioritiesState> context={LinksPrioritiesState(links_priorities)}>
+        <ContextProvider<LinksDomainsState> context={LinksDomainsState(links_domains)}>
+        <ContextProvider<LinkFilterState> context={LinkFilterState(link_filter)}>
+        <ContextProvider<GroupByState> context={GroupByState(group_by)}>
+        <ContextProvider<SortOrderState> context={SortOrderState(sort_order)}>
+        <ContextProvider<ArchiveCompletedState> context={ArchiveCompletedState(archive_completed)}>
+        <ContextProvider<ShowArchiveState> context={ShowArchiveState(show_archive)}>
+        <ContextProvider<ShowBrokenState> context={ShowBrokenState(show_broken)}>
+        <ContextProvider<LinkCheckProgressState> context={LinkCheckProgressState(link_check_progress)}>
+        <ContextProvider<SearchQueryState> context={SearchQueryState(search_query)}>
+        <ContextProvider<SavedSearchesState> context={SavedSearchesState(saved_searches)}>
+        <ContextProvider<EditingSavedSearchState> context={EditingSavedSearchState(editing_saved_search)}>
+        <ContextProvider<EditingTagState> context={EditingTagState(editing_tag)}>
+        <ContextProvider<RefreshingLinksState> context={RefreshingLinksState(refreshing_links)}>
+        <ContextProvider<TagInfosState> context={TagInfosState(tag_infos)}>
         <ContextProvider<DisplayErrorData> context={DisplayErrorData(display_error_data)}>
         <ContextProvider<PopupBoxState> context={PopupBoxState(popup_box_state.clone())}>
         <ContextProvider<PopupBoxReadyState> context={PopupBoxReadyState(popup_box_ready_state)}>
@@ -421,6 +841,27 @@ pub fn app() -> Html {
                     PopupBox::DisplayError => {
                         html! {<DisplayError />}
                     }
+                    PopupBox::Backups => {
+                        html! {<Backups />}
+                    }
+                    PopupBox::EditSettings => {
+                        html! {<EditSettings />}
+                    }
+                    PopupBox::EditSavedSearch => {
+                        html! {<EditSavedSearch />}
+                    }
+                    PopupBox::EditTag => {
+                        html! {<EditTag />}
+                    }
+                    PopupBox::Activity => {
+                        html! {<Activity />}
+                    }
+                    PopupBox::Trash => {
+                        html! {<Trash />}
+                    }
+                    PopupBox::RefreshLinks => {
+                        html! {<RefreshLinks />}
+                    }
                     PopupBox::None => html!{}
                 }
             }
@@ -429,9 +870,22 @@ pub fn app() -> Html {
         </ContextProvider<PopupBoxReadyState>>
         </ContextProvider<PopupBoxState>>
         </ContextProvider<DisplayErrorData>>
-        </ContextProvider<DisplayedBrowsersState>>
+        </ContextProvider<TagInfosState>>
+        </ContextProvider<RefreshingLinksState>>
+        </ContextProvider<EditingTagState>>
+        </ContextProvider<EditingSavedSearchState>>
+        </ContextProvider<SavedSearchesState>>
+        </ContextProvider<SearchQueryState>>
+        </ContextProvider<LinkCheckProgressState>>
+        </ContextProvider<ShowBrokenState>>
+        </ContextProvider<ShowArchiveState>>
+        </ContextProvider<ArchiveCompletedState>>
+        </ContextProvider<SortOrderState>>
+        </ContextProvider<GroupByState>>
+        </ContextProvider<LinkFilterState>>
+        </ContextProvider<LinksDomainsState>>
+        </ContextProvider<LinksPrioritiesState>>
         </ContextProvider<LinksBrowsersState>>
-        </ContextProvider<DisplayedTagsState>>
         </ContextProvider<LinksTagsState>>
         </ContextProvider<EditingLinkIdState>>
         </ContextProvider<LinksState>>
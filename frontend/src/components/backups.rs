@@ -0,0 +1,114 @@
+use crate::*;
+use dsaver_core::storage::Backup;
+
+/// Restore the links from a backup
+///
+/// This component is a popup box and it will appear if you set the [`PopupBox`] to [`PopupBox::Backups`].
+///
+/// It will show all the backups of the links (newest first). After restoring a backup, it will update the [`LinksState`] state with the restored links.
+#[function_component(Backups)]
+pub fn backups() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+
+    // `None` until the backups are loaded
+    let backups = use_state(|| None::<Vec<Backup>>);
+
+    {
+        // Load the backups when the component is mounted
+        let backups = backups.clone();
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let result = list_backups().await.unwrap().as_string().unwrap();
+
+                    if let Ok(list) = string_to_struct::<Vec<Backup>>(&result) {
+                        backups.set(Some(list));
+                    } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                        show_error(error_reporter, display_error_data, popup_box_state);
+                    } else {
+                        console_error!(result);
+                    }
+                });
+
+                || ()
+            },
+            (),
+        );
+    }
+
+    html! {
+        <Popup title="Backups" id="backups">
+            <div class="backups">
+            {
+                match &*backups {
+                    None => html! {
+                        <p class="message">{"Loading backups..."}</p>
+                    },
+                    Some(list) if list.is_empty() => html! {
+                        <p class="message">{"No backups yet. A backup is created every time your links are saved."}</p>
+                    },
+                    Some(list) => list.iter().map(|backup| {
+                        let name = backup.name.clone();
+                        let links = links.clone();
+                        let display_error_data = display_error_data.clone();
+                        let popup_box_state = popup_box_state.clone();
+
+                        html! {
+                            <div class="backup">
+                                <div class="info">
+                                    <p class="date">{backup.created_at.format("%d %B %Y, %H:%M:%S UTC").to_string()}</p>
+                                    <p class="size">{format!("{} KB", (backup.size + 1023) / 1024)}</p>
+                                </div>
+
+                                <button class="restore" onclick={
+                                    move |_| {
+                                        let name = name.clone();
+                                        let links = links.clone();
+                                        let display_error_data = display_error_data.clone();
+                                        let popup_box_state = popup_box_state.clone();
+
+                                        spawn_local(async move {
+                                            let result = restore_backup(name).await.unwrap().as_string().unwrap();
+
+                                            if let Ok(restored_links) = string_to_struct::<Vec<Link>>(&result) {
+                                                links.set(restored_links);
+                                                popup_box_state.set(PopupBox::None);
+
+                                                console_log!("Successfully restored");
+                                            } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                                                show_error(error_reporter, display_error_data, popup_box_state);
+                                            } else {
+                                                console_error!(result);
+                                            }
+                                        });
+                                    }
+                                }>{"Restore"}</button>
+                            </div>
+                        }
+                    }).collect::<Html>(),
+                }
+            }
+            </div>
+        </Popup>
+    }
+}
+
+/// Show the error in the [`DisplayError`] popup
+fn show_error(
+    error_reporter: ErrorReporter,
+    display_error_data: UseStateHandle<Option<DisplayErrorInnerData>>,
+    popup_box_state: UseStateHandle<PopupBox>,
+) {
+    display_error_data.set(Some(DisplayErrorInnerData {
+        class: DisplayErrorClass::Error,
+        error_reporter,
+        options_buttons: None,
+        options_message: None,
+    }));
+
+    popup_box_state.set(PopupBox::DisplayError);
+}
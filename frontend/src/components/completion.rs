@@ -0,0 +1,44 @@
+use crate::*;
+
+/// Show how many links are complete and incomplete
+///
+/// If user clicks any of them, then only the complete or incomplete links will be displayed. Click it again to display all links.
+///
+/// The selection is stored in the [`LinkFilterState`] state.
+#[function_component(Completion)]
+pub fn completion() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+
+    let complete_count = links.iter().filter(|link| link.complete).count();
+
+    html! {
+        <div class="filter-completion filterable">
+            <div class="title-bar">
+                <h1 class="title">{"Completion"}</h1>
+            </div>
+            <div class="completion elements">
+            {
+                [(true, "Complete", complete_count), (false, "Incomplete", links.len() - complete_count)].into_iter().map(|(complete, name, count)| {
+                    let selected = link_filter.complete == Some(complete);
+
+                    html! {
+                        <p class={classes!("completion-state", "element", if selected { "included" } else { "" })} onclick={
+                            let link_filter = link_filter.clone();
+
+                            move |_| {
+                                let mut filter = (*link_filter).clone();
+                                filter.complete = if selected { None } else { Some(complete) };
+                                link_filter.set(filter);
+                            }
+                        }>
+                            {name}
+                            <span class="count">{count}</span>
+                        </p>
+                    }
+                }).collect::<Html>()
+            }
+            </div>
+        </div>
+    }
+}
@@ -6,7 +6,7 @@ use crate::*;
 ///
 /// It will show a form to create a new link. After creating the link, it will validate the link and then add it to the list of links in the user's filesystem.
 ///
-/// It will show an error if the link is not valid or the website is not reachable.
+/// It will show an error if the link is not valid or the website is not reachable, and a warning if the website returns an error status (like 404) for the page. In both cases the user can still add the link.
 #[function_component(CreateLink)]
 pub fn new() -> Html {
     let links = use_context::<LinksState>().unwrap().0;
@@ -57,7 +57,7 @@ pub fn new() -> Html {
             let display_error_data = display_error_data.clone();
 
             // Create new Link object
-            let link = Link::new_with_date(url)
+            let link = Link::new(url)
                 .tags(tags)
                 .priority(priority.parse().unwrap())
                 .browser(Browser::from(browser));
@@ -67,7 +67,10 @@ pub fn new() -> Html {
             let link = if title.is_empty() {
                 link
             } else {
-                link.title(title)
+                // the title written by the user is kept when the link is refreshed
+                let mut link = link.title(title);
+                link.custom_title = true;
+                link
             };
 
             // If the description is not empty, set it
@@ -82,15 +85,12 @@ pub fn new() -> Html {
             let popup_box_state = popup_box_state.clone();
 
             spawn_local(async move {
-                // save the link to the filesystem
-                let new_link = add_data(
-                    struct_to_string(&*links).unwrap(),
-                    struct_to_string(&link).unwrap(),
-                )
-                .await
-                .unwrap()
-                .as_string()
-                .unwrap();
+                // validate the link and save it to the filesystem
+                let new_link = add_link(struct_to_string(&link).unwrap())
+                    .await
+                    .unwrap()
+                    .as_string()
+                    .unwrap();
 
                 // It will give new `Link` object with `title`, `description` fields. Show it in the UI
                 if let Ok(new_link) = string_to_struct::<Link>(&new_link) {
@@ -109,9 +109,19 @@ pub fn new() -> Html {
                         error
                     ));
 
+                    // the website is working but the page is not, so it is only a warning
+                    let page_error = matches!(
+                        error.error_type(),
+                        ErrorType::PageNotFound | ErrorType::HttpError { .. }
+                    );
+
                     // fill data for `DisplayError` component
                     display_error_data.set(Some(DisplayErrorInnerData {
-                        class: DisplayErrorClass::Error,
+                        class: if page_error {
+                            DisplayErrorClass::Warn
+                        } else {
+                            DisplayErrorClass::Error
+                        },
                         error_reporter: error,
                         options_message: Some(
                             "You can still add the link to the collections. Do you want to add it?"
@@ -129,23 +139,30 @@ pub fn new() -> Html {
                                         .title(String::new())
                                         .description(String::new());
 
-                                    // push the old (newly created by user) `link` to the old collections
-                                    let mut old_links = (*links).clone();
-                                    old_links.push(link);
-
                                     {
-                                        // Save the link to the filesystem
-                                        let old_links = old_links.clone();
+                                        // Save the link to the filesystem without validating it
+                                        let links = links.clone();
                                         spawn_local(async move {
-                                            store_data(struct_to_string(&old_links).unwrap())
-                                                .await
-                                                .unwrap();
+                                            let result =
+                                                insert_link(struct_to_string(&link).unwrap())
+                                                    .await
+                                                    .unwrap()
+                                                    .as_string()
+                                                    .unwrap();
+
+                                            if let Ok(link) = string_to_struct::<Link>(&result) {
+                                                // push the old (newly created by user) `link` to the old collections
+                                                let mut old_links = (*links).clone();
+                                                old_links.push(link);
+
+                                                // update the state
+                                                links.set(old_links);
+                                            } else {
+                                                console_error!(result);
+                                            }
                                         });
                                     }
 
-                                    // update the state
-                                    links.set(old_links);
-
                                     // hide the component
                                     // popup_box_hide_state.set(true);
                                     popup_box_state.set(PopupBox::None);
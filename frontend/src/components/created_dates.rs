@@ -0,0 +1,109 @@
+use crate::*;
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+
+/// A range of creation dates, shown by the [`CreatedDates`] component
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DateRange {
+    /// Created today
+    Today,
+    /// Created in the last 7 days, including today
+    LastWeek,
+    /// Created in the last 30 days, including today
+    LastMonth,
+    /// Created in the last 365 days, including today
+    LastYear,
+    /// Created before the last 365 days
+    Older,
+}
+
+impl DateRange {
+    /// All the ranges, in the order they are shown to the user
+    pub fn get_vec() -> Vec<Self> {
+        vec![
+            Self::Today,
+            Self::LastWeek,
+            Self::LastMonth,
+            Self::LastYear,
+            Self::Older,
+        ]
+    }
+
+    /// Name of the range, shown to the user
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Today => "Today",
+            Self::LastWeek => "Last 7 days",
+            Self::LastMonth => "Last 30 days",
+            Self::LastYear => "Last year",
+            Self::Older => "Older",
+        }
+    }
+
+    /// The first time (included) and the last time (not included) of the range, in the user's timezone.
+    ///
+    /// The range starts and ends at midnight, so it doesn't change during a day.
+    pub fn bounds(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        let today = Local::now().date_naive();
+        let midnight = |days_ago: i64| {
+            let date = today - Duration::days(days_ago);
+            Local
+                .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+                .earliest()
+                .unwrap_or_else(Local::now)
+                .with_timezone(&Utc)
+        };
+
+        let tomorrow = midnight(-1);
+
+        match self {
+            Self::Today => (midnight(0), tomorrow),
+            Self::LastWeek => (midnight(6), tomorrow),
+            Self::LastMonth => (midnight(29), tomorrow),
+            Self::LastYear => (midnight(364), tomorrow),
+            Self::Older => (DateTime::<Utc>::MIN_UTC, midnight(364)),
+        }
+    }
+}
+
+/// Show how many links are created in some ranges of dates, like today or in the last 7 days
+///
+/// If user clicks any of these ranges, then only the links which are created in that range will be displayed. Click it again to display all links.
+///
+/// The selected range is stored in the [`LinkFilterState`] state.
+#[function_component(CreatedDates)]
+pub fn created_dates() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+
+    html! {
+        <div class="filter-created-dates filterable">
+            <div class="title-bar">
+                <h1 class="title">{"Created"}</h1>
+            </div>
+            <div class="created-dates elements">
+            {
+                DateRange::get_vec().into_iter().map(|range| {
+                    let (from, to) = range.bounds();
+                    let count = links.iter().filter(|link| from <= link.created_at && link.created_at < to).count();
+                    let selected = link_filter.created_range == Some((from, to));
+
+                    html! {
+                        <p class={classes!("created-date", "element", if selected { "included" } else { "" })} onclick={
+                            let link_filter = link_filter.clone();
+
+                            move |_| {
+                                let mut filter = (*link_filter).clone();
+                                filter.created_range = if selected { None } else { Some((from, to)) };
+                                link_filter.set(filter);
+                            }
+                        }>
+                            {range.name()}
+                            <span class="count">{count}</span>
+                        </p>
+                    }
+                }).collect::<Html>()
+            }
+            </div>
+        </div>
+    }
+}
@@ -23,17 +23,16 @@ pub fn dis() -> Html {
     // clear all the data when the component is unmounted
     use_effect_with_deps(move |_| move || data_state.0.set(None), ());
 
-    let class = match data.class {
-        DisplayErrorClass::Error => "error",
-        DisplayErrorClass::Warn => "warn",
-    }
-    .to_string();
+    let (class, kind) = match data.class {
+        DisplayErrorClass::Error => ("error", "Error"),
+        DisplayErrorClass::Warn => ("warn", "Warning"),
+    };
 
     html! {
         <Popup
             id="display-error"
-            title={format!("Error - {}", error_reporter.error_title())}
-            classes={vec![class]}
+            title={format!("{kind} - {}", error_reporter.error_title())}
+            classes={vec![class.to_string()]}
         >
             <div class="display-error">
                 <div class="inner">
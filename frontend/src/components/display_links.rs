@@ -1,15 +1,48 @@
 use crate::*;
-use itertools::Itertools;
+use chrono::Local;
+use dsaver_core::check::LinkCheck;
+use dsaver_core::search::{Highlight, SearchField, SearchHit};
+use dsaver_core::storage::image_key;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A row of the [`VirtualList`] inside [`DisplayLinks`]
+#[derive(Clone, PartialEq)]
+enum Row {
+    /// Title of a group, and the index of the group
+    Group(String, usize),
+    /// `id` of a link, and the index of its group
+    Link(Uuid, usize),
+}
+
+/// Color of the group with the given index, so that the neighbouring groups have different colors
+fn group_color(group_index: usize) -> &'static str {
+    if group_index % 2 == 0 {
+        "orange"
+    } else {
+        "red"
+    }
+}
 
 /// Display the links according to their tags and browser fields
 ///
-/// It will display the links that matches the tags in [`DisplayedTagsState`] and the browser field in [`DisplayedBrowsersState`]
+/// It will display the links that matches the tags and browsers selected in [`LinkFilterState`]. The selection is shown above the links with the [`FilterChips`] component.
+///
+/// It will display the links in groups. By default they are grouped by their priority field, but the user can choose another [`GroupBy`] with the [`GroupBySwitch`].
+///
+/// Inside every group the links are sorted by the [`SortOrder`] chosen with the [`SortSwitch`]. With [`SortOrder::Manual`] the user can drag a link and drop it on another link to move it there.
 ///
-/// It will display the links in the order of thier priority field.
+/// If the user has typed something in the [`Search`] bar, then only the matched links will be displayed, the best match first, with the matched words highlighted.
 ///
-/// When the user double click on a link's *head*, it will open the link in the browser.
+/// The complete links are hidden if the user has chosen to archive them (see [`ArchiveCompletedState`]), and the archive shows only the complete links (see [`ShowArchiveState`]). Every link has a checkbox to mark it as complete.
 ///
-/// User can also click the arrow buttons to show more options such as edit, delete, and open in browser.
+/// The broken links (see [`ShowBrokenState`]) show only the links which were dead the last time they were checked. A broken link can be moved to its new url, archived or deleted.
+///
+/// Only the visible links are rendered with the [`VirtualList`], so it stays fast even with thousands of links.
+///
+/// When the user double click on a link's *head*, it will open the link in the browser. With Ctrl+click (or Cmd+click) the user can select many links, and fetch their title, description and thumbnail again with the [`RefreshLinks`] popup.
+///
+/// User can also click the arrow buttons to show more options such as edit, delete, and open in browser. A deleted link is moved into the [`Trash`], it can be restored from there or with Ctrl+Z (see [`Activity`]).
 ///
 /// # Warning
 ///
@@ -17,302 +50,708 @@ use itertools::Itertools;
 #[function_component(DisplayLinks)]
 pub fn show_links() -> Html {
     let links = use_context::<LinksState>().unwrap().0;
-    let editing_link_id = use_context::<EditingLinkIdState>().unwrap().0;
-    let displayed_tags = use_context::<DisplayedTagsState>().unwrap().0;
-    let displayed_browsers = use_context::<DisplayedBrowsersState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+    let search_query = use_context::<SearchQueryState>().unwrap().0;
+    let group_by = use_context::<GroupByState>().unwrap().0;
+    let sort_order = use_context::<SortOrderState>().unwrap().0;
+    let archive_completed = use_context::<ArchiveCompletedState>().unwrap().0;
+    let show_archive = use_context::<ShowArchiveState>().unwrap().0;
+    let show_broken = use_context::<ShowBrokenState>().unwrap().0;
+    let refreshing_links = use_context::<RefreshingLinksState>().unwrap().0;
     let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
-    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
 
     /* How the rendering works here:
-        1. Loop all the links through `displayed_tags` state and store all links which's tags are selected to be display (displayed_tags) to the `displayed_links_for_tags` vector.
+        1. Send the `link_filter` state to the backend. The backend returns the ids of all links which's tags and browser are selected to be displayed, and they are stored to the `displayed_ids` state.
+           If the user is searching, then the backend searches those links and returns the ids of the matched ones (the best match first) with the matched words, and they are stored to the `highlights` state.
 
-        2. Loop the browsers through `displayed_browsers` state and then loop the `displayed_links_for_tags` vector and check if the browser of the link is in the `displayed_browsers` state. If it is, then store the link to the `displayed_links_for_browsers` vector.
+        2. Find the displayed links inside the `links` state by their ids, without cloning them. Sort them with the `sort_order` state, unless the user is searching. Then group them with the `group_by` state.
 
-        3. Store only the unique values of `displayed_links_for_browsers` vector to a new variable `displayed_links`.
-
-        4. Get all the priorities from the `links` state and store them to a new variable `priorities`.
+        3. Flatten the groups into rows (a title for every group, then its links) and show them with the `VirtualList`, so only the visible rows are rendered. Only the links of those rows are cloned.
     */
 
-    // all links which's tag is in displayed_tags
-    let mut displayed_links_for_tags = Vec::new();
-
-    // looping `links`'s tags with `displayed_tags` and if any `links.tags` match with `displayed_tags`,
-    // then the `link` will be pushed into `displayed_links_for_tags`.
-    for display_tag in (*displayed_tags).clone() {
-        (*links).iter().for_each(|link| {
-            for tag in &link.tags {
-                if &display_tag == tag {
-                    displayed_links_for_tags.push(link.clone());
-                    break;
+    // ids of the links which match the `link_filter`
+    let displayed_ids_state: UseStateHandle<Vec<Uuid>> = use_state(Vec::new);
+    // matched words of the searched links
+    let highlights_state: UseStateHandle<HashMap<Uuid, Vec<Highlight>>> = use_state(HashMap::new);
+    // error of the search query. It is shown below the search bar instead of a popup, because the query is invalid while the user is typing
+    let search_error_state: UseStateHandle<Option<ErrorReporter>> = use_state(|| None);
+    // number of the latest query. The responses may arrive in any order, so only the response of the latest query is used
+    let query_number: Rc<RefCell<u64>> = use_mut_ref(|| 0);
+
+    {
+        // query the links again whenever the links, the filters or the search query change
+        let displayed_ids_state = displayed_ids_state.clone();
+        let highlights_state = highlights_state.clone();
+        let search_error_state = search_error_state.clone();
+        use_effect_with_deps(
+            move |(_, link_filter, search_query, archive_completed, show_archive, show_broken)| {
+                *query_number.borrow_mut() += 1;
+                let number = *query_number.borrow();
+                let is_stale = move || *query_number.borrow() != number;
+
+                let mut filter = (**link_filter).clone();
+
+                // the archive shows only the complete links, and the other links are shown everywhere else
+                if **show_archive {
+                    filter.complete = Some(true);
+                } else if **archive_completed && filter.complete.is_none() {
+                    filter.complete = Some(false);
                 }
-            }
-        });
+                if **show_broken {
+                    filter.broken = Some(true);
+                }
+                let search_query = search_query.trim().to_string();
+
+                spawn_local(async move {
+                    if search_query.is_empty() {
+                        let result = query_link_ids(struct_to_string(&filter).unwrap())
+                            .await
+                            .unwrap()
+                            .as_string()
+                            .unwrap();
+
+                        if is_stale() {
+                            return;
+                        }
+
+                        if let Ok(ids) = string_to_struct::<Vec<Uuid>>(&result) {
+                            displayed_ids_state.set(ids);
+                            highlights_state.set(HashMap::new());
+                            search_error_state.set(None);
+                        } else {
+                            console_error!(result);
+                        }
+                    } else {
+                        let result = search_links(search_query, struct_to_string(&filter).unwrap())
+                            .await
+                            .unwrap()
+                            .as_string()
+                            .unwrap();
+
+                        if is_stale() {
+                            return;
+                        }
+
+                        if let Ok(hits) = string_to_struct::<Vec<SearchHit>>(&result) {
+                            // keep the order of the hits, so the best match is shown first
+                            displayed_ids_state.set(hits.iter().map(|hit| hit.id).collect());
+                            highlights_state.set(
+                                hits.into_iter()
+                                    .map(|hit| (hit.id, hit.highlights))
+                                    .collect(),
+                            );
+                            search_error_state.set(None);
+                        } else if let Ok(error_reporter) =
+                            string_to_struct::<ErrorReporter>(&result)
+                        {
+                            // keep showing the previous links until the query is fixed
+                            search_error_state.set(Some(error_reporter));
+                        } else {
+                            console_error!(result);
+                        }
+                    }
+                });
+
+                || ()
+            },
+            (
+                links.clone(),
+                link_filter,
+                search_query.clone(),
+                archive_completed,
+                show_archive.clone(),
+                show_broken.clone(),
+            ),
+        );
     }
 
-    let mut displayed_links_for_browsers = Vec::new();
+    // position of every link inside the `links` state, by its id
+    let positions: Rc<HashMap<Uuid, usize>> = Rc::new(
+        links
+            .iter()
+            .enumerate()
+            .filter_map(|(position, link)| Some((link.id?, position)))
+            .collect(),
+    );
+
+    // the ids may be older than the `links` state, so skip the links which don't exist anymore
+    let mut displayed_links: Vec<&Link> = displayed_ids_state
+        .iter()
+        .filter_map(|id| positions.get(id))
+        .map(|position| &links[*position])
+        .collect();
+
+    // the searched links are shown in the order of the search, the best match first
+    let searching = !search_query.trim().is_empty();
+    if !searching {
+        sort_order.sort(&mut displayed_links);
+    }
 
-    // now remove those links whose browsers != displayed_browsers
-    for browser in (*displayed_browsers).clone() {
-        displayed_links_for_tags.iter().for_each(|link| {
-            if link.browser == browser {
-                displayed_links_for_browsers.push(link.clone());
-            }
-        })
+    // the links can only be dragged in the manual order
+    let draggable = *sort_order == SortOrder::Manual && !searching;
+    // id of the link which is being dragged
+    let dragged_link: UseStateHandle<Option<Uuid>> = use_state(|| None);
+    // id of the link which's body (.link-body) is open
+    // if this is `None`, then no link is open
+    let opened_link: UseStateHandle<Option<Uuid>> = use_state(|| None);
+    // ids of the links which are selected with Ctrl+click
+    let selected_links: UseStateHandle<Vec<Uuid>> = use_state(Vec::new);
+
+    let mut rows = Vec::new();
+    for (group_index, (title, group_links)) in
+        group_by.group(&displayed_links).into_iter().enumerate()
+    {
+        rows.push(Row::Group(title, group_index));
+        rows.extend(
+            group_links
+                .into_iter()
+                .filter_map(|link| Some(Row::Link(link.id?, group_index))),
+        );
     }
+    let no_links = displayed_links.is_empty();
+    let rows = Rc::new(rows);
+
+    let render = {
+        let rows = rows.clone();
+        let links = links.clone();
+        let highlights = (*highlights_state).clone();
+        let opened_link = opened_link.clone();
+        let dragged_link = dragged_link.clone();
+        let selected_links = selected_links.clone();
+
+        Callback::from(move |index: usize| match &rows[index] {
+            Row::Group(title, group_index) => html! {
+                <div class={classes!("group-row", "group-title", group_color(*group_index))}>
+                    <div class="priority">
+                        <h2 class="text">{title}</h2>
+                    </div>
+                </div>
+            },
+            Row::Link(id, group_index) => {
+                let link = links[positions[id]].clone();
+                let id = *id;
+
+                let on_toggle = {
+                    let opened_link = opened_link.clone();
+                    // close the link body if user clicked on the opened link, else open it
+                    Callback::from(move |_| {
+                        opened_link.set(if *opened_link == Some(id) {
+                            None
+                        } else {
+                            Some(id)
+                        })
+                    })
+                };
+
+                let on_select = {
+                    let selected_links = selected_links.clone();
+                    // unselect the link if it is selected, else select it
+                    Callback::from(move |_| {
+                        let mut selected = (*selected_links).clone();
+                        if selected.contains(&id) {
+                            selected.retain(|selected_id| *selected_id != id);
+                        } else {
+                            selected.push(id);
+                        }
+                        selected_links.set(selected);
+                    })
+                };
 
-    // remove duplicate links
-    let displayed_links = displayed_links_for_browsers.into_iter().unique();
+                html! {
+                    <div class={classes!("group-row", group_color(*group_index))}>
+                        <LinkRow
+                            {link}
+                            highlights={highlights.get(&id).cloned().unwrap_or_default()}
+                            opened={*opened_link == Some(id)}
+                            {on_toggle}
+                            selected={selected_links.contains(&id)}
+                            {on_select}
+                            {draggable}
+                            dragged_link={dragged_link.clone()}
+                        />
+                    </div>
+                }
+            }
+        })
+    };
 
-    // list of priorities of all links
-    let mut priorities = Vec::new();
+    html! {
+        <>
+        <div class="display-links" id="display-links">
+            if *show_archive {
+                <div class="archive-bar">
+                    <h2 class="title">{"Archive"}</h2>
+                    <button class="close" onclick={
+                        let show_archive = show_archive.clone();
+                        move |_| show_archive.set(false)
+                    }>{"Back to all links"}</button>
+                </div>
+            }
+            if *show_broken {
+                <div class="archive-bar broken-bar">
+                    <h2 class="title">{"Broken links"}</h2>
+                    <button class="close" onclick={
+                        let show_broken = show_broken.clone();
+                        move |_| show_broken.set(false)
+                    }>{"Back to all links"}</button>
+                </div>
+            }
+            if !selected_links.is_empty() {
+                <div class="archive-bar selection-bar">
+                    <h2 class="title">{format!("{} selected", selected_links.len())}</h2>
+                    <button class="refresh" title="Fetch the title, description and thumbnail again" onclick={
+                        let selected_links = selected_links.clone();
+                        move |_| {
+                            refreshing_links.set((*selected_links).clone());
+                            popup_box_state.set(PopupBox::RefreshLinks);
+                            selected_links.set(Vec::new());
+                        }
+                    }>{"Refresh"}</button>
+                    <button class="close" onclick={
+                        let selected_links = selected_links.clone();
+                        move |_| selected_links.set(Vec::new())
+                    }>{"Clear selection"}</button>
+                </div>
+            }
+            <Search />
+            <GroupBySwitch />
+            <SortSwitch />
+            <FilterChips />
+
+            if let Some(error_reporter) = &*search_error_state {
+                <div class="search-error">
+                    <p class="error">{error_reporter.actual_error()}</p>
+                    <ul class="how-to-fix">
+                        {
+                        error_reporter.how_to_fix().iter().map(|fix| {
+                            html! {
+                                <li>{fix}</li>
+                            }
+                        }).collect::<Html>()
+                        }
+                    </ul>
+                </div>
+            } else if no_links && searching {
+                <p class="no-results">{"No links match your search"}</p>
+            }
 
-    for link in &*links {
-        priorities.push(link.priority);
+            <VirtualList len={rows.len()} {render} row_height={70.0} class="display-links-container" />
+        </div>
+        </>
     }
+}
 
-    // sort the priorities
-    priorities.sort();
-    // remove duplicate priorities
-    priorities.dedup();
+/// Props for the [`LinkRow`] component.
+#[derive(Properties, PartialEq)]
+struct LinkRowProps {
+    /// The link to show
+    link: Link,
+    /// Words of the link matched by the search
+    highlights: Vec<Highlight>,
+    /// Is the body (.link-body) of the link open?
+    opened: bool,
+    /// Called when the user clicks the arrow button to open or close the body
+    on_toggle: Callback<()>,
+    /// Is the link selected with Ctrl+click?
+    selected: bool,
+    /// Called when the user Ctrl+clicks the *head* to select or unselect the link
+    on_select: Callback<()>,
+    /// Can the link be dragged to reorder it?
+    draggable: bool,
+    /// id of the link which is being dragged
+    dragged_link: UseStateHandle<Option<Uuid>>,
+}
 
-    // index of the priority
-    let mut priority_index = 0;
-    // index of the links to be displayed
-    let mut link_index = 0;
-    // index of the link which's body (.link-body) is open
-    // if this is `None`, then no link is open
-    // if this is `Some`, then the link with the index is open
-    let opened_link: UseStateHandle<Option<i32>> = use_state(|| None);
+/// A link inside [`DisplayLinks`]
+///
+/// Its *head* shows the title. The body shows the other fields and the buttons to open, edit and delete the link, and it is only visible when it is opened.
+///
+/// The tags are shown with the colors and icons from the [`TagInfosState`]. The favicon of the website is shown next to the title, and the thumbnail and site name from the [`PageMetadata`](dsaver_core::metadata::PageMetadata) at the top of the body.
+///
+/// The images are loaded from the cache of the backend (see [`image_src`]). If the favicon is not cached, then the first letter of the domain is shown instead.
+///
+/// If the link is checked (see [`Link::check`]), then the result is shown in the body. A link which is redirected can be moved to its new url, and a broken link can be archived.
+///
+/// The Refresh button fetches the title, description and thumbnail of the link again, and shows the changes in the [`RefreshLinks`] popup.
+#[function_component(LinkRow)]
+fn link_row(props: &LinkRowProps) -> Html {
+    let LinkRowProps {
+        link,
+        highlights,
+        opened,
+        on_toggle,
+        selected,
+        on_select,
+        draggable,
+        dragged_link,
+    } = props;
+    let (opened, selected, draggable) = (*opened, *selected, *draggable);
+    let dragged_link = dragged_link.clone();
+
+    let links = use_context::<LinksState>().unwrap().0;
+    let editing_link_id = use_context::<EditingLinkIdState>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let tag_infos = use_context::<TagInfosState>().unwrap().0;
+    let refreshing_links = use_context::<RefreshingLinksState>().unwrap().0;
+
+    // the images which couldn't be loaded from the cache
+    let failed_images: UseStateHandle<Vec<String>> = use_state(Vec::new);
+
+    let link = link.clone();
+    let link_highlights = highlights.as_slice();
+
+    let cached_image = |url: &Option<String>| {
+        url.clone()
+            .filter(|url| !failed_images.contains(url))
+            .map(|url| (image_src(&image_key(&url)), url))
+    };
+    let on_image_error = |url: String| {
+        let failed_images = failed_images.clone();
+
+        move |_: Event| {
+            let mut failed = (*failed_images).clone();
+            failed.push(url.clone());
+            failed_images.set(failed);
+        }
+    };
 
     html! {
-        <>
-        <div class="display-links" id="display-links">
-            {
-            priorities.iter().map(|priority| {
-                priority_index+=1;
+                <div
+                    class={classes!(
+                        "link",
+                        if *dragged_link == link.id && link.id.is_some() { "dragging" } else { "" },
+                        if selected { "selected" } else { "" }
+                    )}
+                    draggable={draggable.to_string()}
+                    ondragstart={
+                        let dragged_link = dragged_link.clone();
+                        let id = link.id;
+                        move |_: DragEvent| dragged_link.set(id)
+                    }
+                    ondragend={
+                        let dragged_link = dragged_link.clone();
+                        move |_: DragEvent| dragged_link.set(None)
+                    }
+                    // the link can only be dropped if the default is prevented
+                    ondragover={move |event: DragEvent| {
+                        if draggable {
+                            event.prevent_default();
+                        }
+                    }}
+                    ondrop={
+                        let links = links.clone();
+                        let dragged_link = dragged_link.clone();
+                        let target = link.id;
+                        let display_error_data = display_error_data.clone();
+                        let popup_box_state = popup_box_state.clone();
+                        move |event: DragEvent| {
+                            event.prevent_default();
+
+                            if let (Some(dragged), Some(target)) = (*dragged_link, target) {
+                                dragged_link.set(None);
+
+                                if dragged != target {
+                                    move_link(links.clone(), dragged, target, display_error_data.clone(), popup_box_state.clone());
+                                }
+                            }
+                        }
+                    }
+                >
+                    <div class="link-head">
+                        <input
+                            type="checkbox"
+                            class="complete"
+                            title={if link.complete { "Mark as incomplete" } else { "Mark as complete" }}
+                            checked={link.complete}
+                            onclick={
+                                let links = links.clone();
+                                let link = link.clone();
+                                move |_| set_complete(links.clone(), link.clone(), !link.complete)
+                            }
+                        />
+                        <div class="title-area" onclick={
+                            let on_select = on_select.clone();
+                            move |event: MouseEvent| {
+                                if event.ctrl_key() || event.meta_key() {
+                                    on_select.emit(());
+                                }
+                            }
+                        } ondblclick={
+                            let browser = link.browser.clone();
+                            let url = link.url.clone();
+                            let id = link.id;
+                            let links = links.clone();
+                            let display_error_data = display_error_data.clone();
+                            let popup_box_state = popup_box_state.clone();
+
+                            move |_| {
+                                // if current link is not opened, then open the browser on dblclick
+                                // if current link is opened, then the user can open the link by clicking on the Open button.
+                                // so no need to open the browser on dblclick
+                                if !opened {
+                                    open_user_browser(
+                                        url.clone(),
+                                        browser.clone(),
+                                        id,
+                                        links.clone(),
+                                        display_error_data.clone(),
+                                        popup_box_state.clone(),
+                                    );
+                                }
+                            }
+                        }>
+                            if let Some((src, url)) = cached_image(&link.metadata.favicon) {
+                                <img class="favicon" src={src} alt="" onerror={on_image_error(url)} />
+                            } else {
+                                <span class="favicon avatar" style={avatar_style(&link)}>{avatar_letter(&link)}</span>
+                            }
+                            <h3 class="title">{highlight(&link.title.clone().unwrap(), SearchField::Title, link_highlights)}</h3>
+                        </div>
+                        <div class="icon" onclick={
+                            // toggle the link body
+                            let on_toggle = on_toggle.clone();
+                            move |_| on_toggle.emit(())
+                        }>
+                            if opened {
+                                <img class="up" src="icons/up-arrow.svg" alt="Up arrow" />
+                            } else {
+                                <img class="down" src="icons/down-arrow.svg" alt="Down arrow" />
+                            }
+                        </div>
+                    </div>
+                    <div class={classes!("link-body", if opened { "display" } else { "" })}>
+                        <div class="info">
+                            if let Some((src, url)) = cached_image(&link.metadata.image) {
+                                <img class="thumbnail" src={src} alt="Thumbnail" loading="lazy" onerror={on_image_error(url)} />
+                            }
+                            if let Some(site_name) = &link.metadata.site_name {
+                                <p class="site-name">{site_name}</p>
+                            }
+                            <div class="url-div">
+                                <p class="url">{highlight(&link.url, SearchField::Url, link_highlights)}</p>
+                                <img
+                                    src="icons/copy.svg"
+                                    alt="Copy"
+                                    title="Copy URL"
+                                    onclick={
+                                        let url = link.url.clone();
+                                        move |_| {
+                                            let url = url.clone();
+                                            spawn_local(async move {
+                                                copy_to_clipboard(url).await.unwrap();
+                                            });
+                                        }
+                                    }
+                                />
+                            </div>
 
-                // the links will be shown based on their priority
-                // get the links according to the priority
-                let links_to_show = displayed_links.clone().into_iter().filter(|link| &link.priority == priority);
+                            <p class="description">{highlight(&link.description.clone().unwrap(), SearchField::Description, link_highlights)}</p>
 
-                html! {
-                    <>
-                    if links_to_show.clone().next().is_some() {
-                        <div class={classes!(
-                            "display-links-container",
-                            if priority_index % 2 == 0 {
-                                "red"
-                            } else {
-                                "orange"
+                            <ul class="tags">
+                                {
+                                link.tags.iter().enumerate().map(|(i, tag)| {
+                                    let info = tag_infos.get(tag);
+
+                                    html! {
+                                        <li
+                                            class="tag"
+                                            style={tag_style(info).unwrap_or_default()}
+                                            title={info.and_then(|info| info.description.clone()).unwrap_or_default()}
+                                        >
+                                            if let Some(icon) = info.and_then(|info| info.icon.as_ref()) {
+                                                <span class="tag-icon">{icon}</span>
+                                            }
+                                            {highlight(tag, SearchField::Tag(i), link_highlights)}
+                                        </li>
+                                    }
+                                }).collect::<Html>()
+                                }
+                            </ul>
+
+                            <p class="date">{link.created_at.with_timezone(&Local).format("%d %B %Y").to_string()}</p>
+
+                            if let Some(check) = &link.check {
+                                <p class={classes!("check", if check.is_broken() { "broken" } else { "" })} title={check.error.clone().unwrap_or_default()}>
+                                    {check_text(check)}
+                                </p>
+                                if let Some(redirect) = &check.redirect {
+                                    <p class="redirect">{format!("Moved to {redirect}")}</p>
+                                }
                             }
-                        )}>
-                            <div class="right-side" />
-                            <div class="left-side">
-                                <div class="priority">
-                                    <h2 class="text">{priority}</h2>
-                                </div>
+
+                            <p class="opened" title={open_history_text(&link)}>
                                 {
-                                    links_to_show.clone().into_iter().map(|link| {
-                                        link_index += 1;
-
-                                        html! {
-                                            <div class="link">
-                                                <div class="link-head">
-                                                    <div class="title-area" ondblclick={
-                                                        let browser = link.browser.clone();
-                                                        let url = link.url.clone();
-                                                        let opened_link = opened_link.clone();
-                                                        let display_error_data = display_error_data.clone();
-                                                        let popup_box_state = popup_box_state.clone();
-
-                                                        move |_| {
-                                                            // if current link is not opened, then open the browser on dblclick
-                                                            // if current link is opened, then the user can open the link by clicking on the Open button.
-                                                            // so no need to open the browser on dblclick
-                                                            if let Some(display) = *opened_link {
-                                                                if display != (link_index - 1) {
-                                                                    open_user_browser(
-                                                                        url.clone(),
-                                                                        browser.clone(),
-                                                                        display_error_data.clone(),
-                                                                        popup_box_state.clone(),
-                                                                    );
-                                                                }
-                                                            } else {
-                                                                open_user_browser(
-                                                                    url.clone(),
-                                                                    browser.clone(),
-                                                                    display_error_data.clone(),
-                                                                    popup_box_state.clone()
-                                                                );
-                                                            }
-                                                        }
-                                                    }>
-                                                        <h3 class="title">{link.title.clone().unwrap()}</h3>
-                                                    </div>
-                                                    <div class="icon" onclick={
-                                                        let opened_link = opened_link.clone();
-                                                        move |_| {
-                                                            // toggle the link body
-                                                            if let Some(link) = *opened_link {
-                                                                if link == (link_index - 1) {
-                                                                    // close the link body if user clicked on the opened link
-                                                                    opened_link.set(None);
-                                                                } else {
-                                                                    // open the link body if user clicked on the closed link
-                                                                    opened_link.set(Some(link_index - 1));
-                                                                }
-                                                            } else {
-                                                                // open the link body if user clicked on the closed link
-                                                                opened_link.set(Some(link_index - 1));
-                                                            }
-
-                                                        }
-                                                    }>
-                                                        if let Some(link) = *opened_link {
-                                                            if link == (link_index - 1) {
-                                                                <img class="up" src="icons/up-arrow.svg" alt="Up arrow" />
-                                                            } else {
-                                                                <img class="down" src="icons/down-arrow.svg" alt="Down arrow" />
-                                                            }
-                                                        } else {
-                                                            <img class="down" src="icons/down-arrow.svg" alt="Down arrow" />
-                                                        }
-                                                    </div>
-                                                </div>
-                                                <div class={classes!(
-                                                    "link-body",
-                                                    if let Some(display) = *opened_link {
-                                                        if display == (link_index - 1) {
-                                                            "display"
-                                                        } else {
-                                                            ""
-                                                        }
-                                                    } else {
-                                                        ""
-                                                    }
-                                                )}>
-                                                    <div class="info">
-                                                        <div class="url-div">
-                                                            <p class="url">{&link.url}</p>
-                                                            <img
-                                                                src="icons/copy.svg"
-                                                                alt="Copy"
-                                                                title="Copy URL"
-                                                                onclick={
-                                                                    let url = link.url.clone();
-                                                                    move |_| {
-                                                                        let url = url.clone();
-                                                                        spawn_local(async move {
-                                                                            copy_to_clipboard(url).await.unwrap();
-                                                                        });
-                                                                    }
-                                                                }
-                                                            />
-                                                        </div>
-
-                                                        <p class="description">{link.description.clone().unwrap()}</p>
-
-                                                        <ul class="tags">
-                                                            {
-                                                            link.tags.iter().map(|tag| {
-                                                                html! {
-                                                                    <li class="tag">{tag}</li>
-                                                                }
-                                                            }).collect::<Html>()
-                                                            }
-                                                        </ul>
-
-                                                        <p class="date">{&link.date}</p>
-                                                    </div>
-                                                    <div class="options">
-                                                        // Open browser button
-                                                        <Open
-                                                            href={link.url.clone()}
-                                                            class="open button"
-                                                            browser={link.browser.clone()}
-                                                        >{"Open"}</Open>
-                                                        // Edit button
-                                                        <button class="edit button" onclick={
-                                                            let popup_box_state = popup_box_state.clone();
-                                                            let editing_link_id = editing_link_id.clone();
-                                                            move |_| {
-                                                                editing_link_id.set(Some(link.id.unwrap()));
-                                                                popup_box_state.set(PopupBox::EditLink);
-                                                            }
-                                                        }>{"Edit"}</button>
-                                                        // Delete button
-                                                        <button class="delete button" onclick={
-                                                            let links = links.clone();
-                                                            let link = link.clone();
-                                                            let editing_link_id = editing_link_id.clone();
-                                                            let popup_box_state = popup_box_state.clone();
-                                                            move |_| {
-                                                                // if the link is in editing mode, then close the editing mode else it will cause an error
-                                                                // Delete this link's EditLink component
-                                                                if let Some(id) = *editing_link_id {
-                                                                    if id == link.id.unwrap() {
-                                                                        popup_box_state.set(PopupBox::None);
-                                                                        editing_link_id.set(None);
-                                                                    }
-                                                                }
-
-                                                                let mut old_links = (*links).clone();
-
-                                                                // remove this link from `old_links`
-                                                                old_links.retain(|old_link| old_link != &link);
-
-                                                                // update the `links` state without this link
-                                                                links.set(old_links.clone());
-
-                                                                // store the links to the filesystem
-                                                                spawn_local(async move {
-                                                                    let result = store_data(struct_to_string(&old_links).unwrap())
-                                                                        .await
-                                                                        .unwrap();
-
-                                                                    // if the result is null, it means success
-                                                                    if let Some(error) = result.as_string() {
-                                                                        console_error!(error);
-                                                                    } else {
-                                                                        console_log!("Successfully deleted");
-                                                                    }
-                                                                });
-                                                            }
-                                                        }>{"Delete"}</button>
-                                                    </div>
-                                                </div>
-                                            </div>
+                                    match link.last_opened_at {
+                                        Some(opened_at) => format!(
+                                            "Opened {} {}, last on {}",
+                                            link.open_count,
+                                            if link.open_count == 1 { "time" } else { "times" },
+                                            opened_at.with_timezone(&Local).format("%d %B %Y")
+                                        ),
+                                        None => String::from("Never opened"),
+                                    }
+                                }
+                            </p>
+                        </div>
+                        <div class="options">
+                            // Open browser button
+                            <Open
+                                href={link.url.clone()}
+                                class="open button"
+                                browser={link.browser.clone()}
+                                link_id={link.id}
+                            >{"Open"}</Open>
+                            // Edit button
+                            <button class="edit button" onclick={
+                                let popup_box_state = popup_box_state.clone();
+                                let editing_link_id = editing_link_id.clone();
+                                move |_| {
+                                    editing_link_id.set(Some(link.id.unwrap()));
+                                    popup_box_state.set(PopupBox::EditLink);
+                                }
+                            }>{"Edit"}</button>
+                            // Refresh button
+                            <button class="refresh button" title="Fetch the title, description and thumbnail again" onclick={
+                                let popup_box_state = popup_box_state.clone();
+                                let id = link.id.unwrap();
+                                move |_| {
+                                    refreshing_links.set(vec![id]);
+                                    popup_box_state.set(PopupBox::RefreshLinks);
+                                }
+                            }>{"Refresh"}</button>
+                            if let Some(redirect) = link.check.as_ref().and_then(|check| check.redirect.clone()) {
+                                <button class="new-url button" title={format!("Change the url to {redirect}")} onclick={
+                                    let links = links.clone();
+                                    let link = link.clone();
+                                    move |_| move_to_new_url(links.clone(), link.clone(), redirect.clone())
+                                }>{"Use new URL"}</button>
+                            }
+                            if link.is_broken() && !link.complete {
+                                <button class="archive button" title="Mark as complete" onclick={
+                                    let links = links.clone();
+                                    let link = link.clone();
+                                    move |_| set_complete(links.clone(), link.clone(), true)
+                                }>{"Archive"}</button>
+                            }
+                            // Delete button
+                            <button class="delete button" title="Move to the trash" onclick={
+                                let links = links.clone();
+                                let link = link.clone();
+                                let editing_link_id = editing_link_id.clone();
+                                let popup_box_state = popup_box_state.clone();
+                                move |_| {
+                                    // if the link is in editing mode, then close the editing mode else it will cause an error
+                                    // Delete this link's EditLink component
+                                    if let Some(id) = *editing_link_id {
+                                        if id == link.id.unwrap() {
+                                            popup_box_state.set(PopupBox::None);
+                                            editing_link_id.set(None);
                                         }
-
-                                    }).collect::<Html>()
+                                    }
+
+                                    let links = links.clone();
+                                    let link = link.clone();
+
+                                    // move the link into the trash
+                                    spawn_local(async move {
+                                        let result = delete_link(link.id.unwrap().to_string())
+                                            .await
+                                            .unwrap()
+                                            .as_string()
+                                            .unwrap();
+
+                                        if let Ok(error) = string_to_struct::<ErrorReporter>(&result) {
+                                            console_error!(format!("{:?}", error));
+                                        } else {
+                                            // update the `links` state without this link
+                                            // NOTE: it is updated after deleting, so that the displayed links are queried without this link
+                                            let mut old_links = (*links).clone();
+                                            old_links.retain(|old_link| old_link != &link);
+                                            links.set(old_links);
+
+                                            console_log!("Successfully moved to the trash");
+                                        }
+                                    });
                                 }
-                            </div>
+                            }>{"Delete"}</button>
                         </div>
-                    } else {}
-                    </>
-                }
-            }).collect::<Html>()
-            }
-        </div>
-        </>
+                    </div>
+                </div>
+
     }
 }
 
-/// Open user's selected browser
-fn open_user_browser(
-    url: String,
-    browser: Browser,
+/// Move the link `dragged` to the place of the link `target` in the manual order, and save the new order
+///
+/// The order contains all links, not only the displayed ones. So the hidden links keep their place.
+fn move_link(
+    links: UseStateHandle<Vec<Link>>,
+    dragged: Uuid,
+    target: Uuid,
     display_error_data: UseStateHandle<Option<DisplayErrorInnerData>>,
     popup_box_state: UseStateHandle<PopupBox>,
 ) {
+    let mut ordered = (*links).clone();
+    SortOrder::Manual.sort(&mut ordered);
+
+    let mut ids: Vec<Uuid> = ordered.iter().filter_map(|link| link.id).collect();
+
+    let (from, to) = match (
+        ids.iter().position(|id| *id == dragged),
+        ids.iter().position(|id| *id == target),
+    ) {
+        (Some(from), Some(to)) => (from, to),
+        _ => return,
+    };
+
+    // a link dragged down is placed after the target, and a link dragged up before it
+    let id = ids.remove(from);
+    ids.insert(to, id);
+
     spawn_local(async move {
-        let result = open_browser(url, struct_to_string(&browser).unwrap())
+        let result = reorder_links(struct_to_string(&ids).unwrap())
             .await
             .unwrap()
             .as_string()
             .unwrap();
 
+        if let Ok(new_links) = string_to_struct::<Vec<Link>>(&result) {
+            links.set(new_links);
+        } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+            display_error_data.set(Some(DisplayErrorInnerData {
+                class: DisplayErrorClass::Error,
+                error_reporter,
+                options_buttons: None,
+                options_message: None,
+            }));
+            popup_box_state.set(PopupBox::DisplayError);
+        } else {
+            console_error!(result);
+        }
+    });
+}
+
+/// Open user's selected browser
+fn open_user_browser(
+    url: String,
+    browser: Browser,
+    id: Option<Uuid>,
+    links: UseStateHandle<Vec<Link>>,
+    display_error_data: UseStateHandle<Option<DisplayErrorInnerData>>,
+    popup_box_state: UseStateHandle<PopupBox>,
+) {
+    spawn_local(async move {
+        let result = open_browser(
+            url,
+            struct_to_string(&browser).unwrap(),
+            id.map(|id| id.to_string()),
+        )
+        .await
+        .unwrap()
+        .as_string()
+        .unwrap();
+
         if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
             // fill the `display_error_data` state with the error data
             display_error_data.set(Some(DisplayErrorInnerData {
@@ -326,7 +765,146 @@ fn open_user_browser(
             // open the error popup box
             popup_box_state.set(PopupBox::DisplayError);
         } else {
+            // the saved link is updated by the backend, e.g. its `last_opened_at`
+            if let Ok(Some(link)) = string_to_struct::<Option<Link>>(&result) {
+                replace_link(&links, link);
+            }
+
             console_log!("Successfully opened");
         }
     });
 }
+
+/// Mark the `link` as complete or incomplete, and save it
+fn set_complete(links: UseStateHandle<Vec<Link>>, link: Link, complete: bool) {
+    spawn_local(async move {
+        let result = update_link(struct_to_string(&link.complete(complete)).unwrap())
+            .await
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        if let Ok(link) = string_to_struct::<Link>(&result) {
+            replace_link(&links, link);
+        } else {
+            console_error!(result);
+        }
+    });
+}
+
+/// Change the url of the `link` to the `new_url` where it is redirected, and save it
+///
+/// The backend removes the old check, because it is for the old url.
+fn move_to_new_url(links: UseStateHandle<Vec<Link>>, mut link: Link, new_url: String) {
+    link.url = new_url;
+
+    spawn_local(async move {
+        let result = update_link(struct_to_string(&link).unwrap())
+            .await
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        if let Ok(link) = string_to_struct::<Link>(&result) {
+            replace_link(&links, link);
+        } else {
+            console_error!(result);
+        }
+    });
+}
+
+/// Show the `text` of the link's `field`, with the words matched by the search inside `<mark>` tags
+fn highlight(text: &str, field: SearchField, highlights: &[Highlight]) -> Html {
+    let mut ranges: Vec<(usize, usize)> = highlights
+        .iter()
+        .filter(|highlight| highlight.field == field)
+        .map(|highlight| (highlight.start, highlight.end))
+        .collect();
+    ranges.sort_unstable();
+
+    let mut html = Vec::new();
+    // end of the last highlighted word
+    let mut last = 0;
+
+    for (start, end) in ranges {
+        // the link may have changed after the search, so skip the ranges which don't fit anymore
+        if let (Some(before), Some(word)) = (text.get(last..start), text.get(start..end)) {
+            html.push(html! { {before} });
+            html.push(html! { <mark>{word}</mark> });
+            last = end;
+        }
+    }
+
+    html.push(html! { {&text[last..]} });
+
+    html.into_iter().collect::<Html>()
+}
+
+/// The letter of the avatar which is shown instead of a missing favicon: the first letter of the domain
+fn avatar_letter(link: &Link) -> String {
+    link.domain()
+        .and_then(|domain| domain.chars().next())
+        .map(|letter| letter.to_uppercase().to_string())
+        .unwrap_or_else(|| String::from("?"))
+}
+
+/// The background color of the avatar (see [`avatar_letter`]). Every domain has its own color.
+fn avatar_style(link: &Link) -> String {
+    let hue = link
+        .domain()
+        .unwrap_or_default()
+        .bytes()
+        .fold(0u32, |hash, byte| {
+            hash.wrapping_mul(31).wrapping_add(byte.into())
+        })
+        % 360;
+
+    format!("background-color: hsl({hue}, 55%, 40%);")
+}
+
+/// The result of checking the link, e.g. `Broken (404), checked on 18 October 2026`
+fn check_text(check: &LinkCheck) -> String {
+    let state = match check.status {
+        Some(status) if check.is_broken() => format!("Broken ({status})"),
+        Some(status) if check.is_inconclusive() => {
+            format!("Unknown, the website refused the check ({status})")
+        }
+        Some(_) => String::from("Working"),
+        None if check.is_broken() => String::from("Unreachable"),
+        None => String::from("Unknown, the website didn't answer"),
+    };
+
+    format!(
+        "{state}, checked on {}",
+        check.checked_at.with_timezone(&Local).format("%d %B %Y")
+    )
+}
+
+/// Every opening of the `link`, one per line, the newest first. The openings which are not kept in the history are counted at the end.
+fn open_history_text(link: &Link) -> String {
+    let mut lines: Vec<String> = link
+        .open_history
+        .iter()
+        .rev()
+        .map(|record| {
+            format!(
+                "{} in {}",
+                record
+                    .opened_at
+                    .with_timezone(&Local)
+                    .format("%d %B %Y %H:%M"),
+                record.browser
+            )
+        })
+        .collect();
+
+    let older = (link.open_count as usize).saturating_sub(link.open_history.len());
+    if older > 0 {
+        lines.push(format!(
+            "and {older} more {}",
+            if older == 1 { "time" } else { "times" }
+        ));
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,57 @@
+use crate::*;
+
+/// Show all domains from the links_state
+///
+/// If user clicks any of these domain elements, then only those links will be displayed which are from that domain. Several domains can be selected, and the links from any of them are displayed.
+///
+/// The selected domains are stored in the [`LinkFilterState`] state.
+#[function_component(Domains)]
+pub fn domains() -> Html {
+    let links_domains = use_context::<LinksDomainsState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+
+    // the domain with the most links first
+    let mut domains: Vec<(String, i32)> = (*links_domains)
+        .iter()
+        .map(|(domain, count)| (domain.clone(), *count))
+        .collect();
+    domains.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    html! {
+        <div class="filter-domains filterable">
+            <div class="title-bar">
+                <h1 class="title">{"Domains"}</h1>
+            </div>
+            <div class="domains elements">
+            {
+                domains.into_iter().map(|(domain, count)| {
+                    let selected = matches!(&link_filter.domains, Some(domains) if domains.contains(&domain));
+
+                    html! {
+                        <p class={classes!("domain", "element", if selected { "included" } else { "" })} onclick={
+                            let domain = domain.clone();
+                            let link_filter = link_filter.clone();
+
+                            move |_| {
+                                let mut filter = (*link_filter).clone();
+
+                                if selected {
+                                    // domains can't be excluded
+                                    remove_selection(&mut filter.domains, &mut Vec::new(), &domain);
+                                } else {
+                                    filter.domains.get_or_insert_with(Vec::new).push(domain.clone());
+                                }
+
+                                link_filter.set(filter);
+                            }
+                        }>
+                            {domain}
+                            <span class="count">{count}</span>
+                        </p>
+                    }
+                }).collect::<Html>()
+            }
+            </div>
+        </div>
+    }
+}
@@ -39,6 +39,8 @@ pub fn editlink() -> Html {
     let tags_value = use_state(|| editing_link.tags.join(" "));
     let priority_value = use_state(|| editing_link.priority.to_string());
     let browser_value = use_state(|| editing_link.browser.to_string());
+    // the checkbox is checked if this is `true`
+    let complete_value = use_state(|| editing_link.complete);
 
     // previously created tags || tags that matches tags from `displayed_tags`
     let previously_matched_tags = use_state(Vec::new);
@@ -60,6 +62,7 @@ pub fn editlink() -> Html {
         let priority = priority_value.to_string();
         let tags = tags_value.to_string();
         let browser = browser_value.to_string();
+        let complete = *complete_value;
         let url = url.clone();
 
         move |_| {
@@ -72,6 +75,9 @@ pub fn editlink() -> Html {
                 id: editing_link.id,
                 url: url.clone(),
                 title: Some(title.clone()),
+                // a title changed by the user is kept when the link is refreshed
+                custom_title: editing_link.custom_title
+                    || editing_link.title.as_deref() != Some(title.as_str()),
                 description: Some(description.to_string()),
                 tags: tags
                     .split_whitespace()
@@ -80,31 +86,37 @@ pub fn editlink() -> Html {
                     .collect(),
                 priority: priority.chars().next().unwrap(),
                 browser: Browser::from(browser.clone()),
-                complete: editing_link.complete,
-                date: editing_link.date.clone(),
+                complete,
+                created_at: editing_link.created_at,
+                updated_at: editing_link.updated_at,
+                last_opened_at: editing_link.last_opened_at,
+                open_count: editing_link.open_count,
+                open_history: editing_link.open_history.clone(),
+                manual_order: editing_link.manual_order,
+                metadata: editing_link.metadata.clone(),
+                check: editing_link.check.clone(),
             };
 
             let links = links.clone();
 
             spawn_local(async move {
-                // change the `links` state with the new link
-                let mut old_links = (*links).clone();
-                old_links[editing_link_position] = new_link;
-                links.set(old_links.clone());
-
-                // store the links to the filesystem
-                spawn_local(async move {
-                    let result = store_data(struct_to_string(&old_links).unwrap())
-                        .await
-                        .unwrap();
-
-                    // if the result is null, it means success
-                    if let Some(error) = result.as_string() {
-                        console_error!(error);
-                    } else {
-                        console_log!("Successfully updated");
-                    }
-                });
+                // store the link to the filesystem
+                let result = update_link(struct_to_string(&new_link).unwrap())
+                    .await
+                    .unwrap()
+                    .as_string()
+                    .unwrap();
+
+                if let Ok(new_link) = string_to_struct::<Link>(&result) {
+                    // change the `links` state with the new link
+                    let mut old_links = (*links).clone();
+                    old_links[editing_link_position] = new_link;
+                    links.set(old_links);
+
+                    console_log!("Successfully updated");
+                } else {
+                    console_error!(result);
+                }
             });
         }
     });
@@ -225,6 +237,14 @@ pub fn editlink() -> Html {
                     />
                 </InputWrapper>
 
+                <InputWrapper id="edit-complete">
+                    <Checkbox
+                        label_text="I have read/finished this link"
+                        input_value_is_empty={true}
+                        disabled={complete_value}
+                    />
+                </InputWrapper>
+
                 <Select>
                     <SelectLabel text="Priority of the link" />
                     <Box
@@ -0,0 +1,179 @@
+use crate::*;
+use chrono::Local;
+
+/// Save or edit a search
+///
+/// This component is a popup box and it will appear if you set the [`PopupBox`] to [`PopupBox::EditSavedSearch`].
+///
+/// Before you can use this component you need to set the search in [`EditingSavedSearchState`]. When this component goes out of scope, it will make that state [`None`].
+///
+/// The user can change the name and the query of the search. The query is checked by the backend, so an invalid query is shown with the [`DisplayError`] popup. After saving, the [`SavedSearchesState`] state is updated.
+#[function_component(EditSavedSearch)]
+pub fn edit_saved_search() -> Html {
+    let saved_searches = use_context::<SavedSearchesState>().unwrap().0;
+    let editing_saved_search = use_context::<EditingSavedSearchState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+
+    let editing = (*editing_saved_search).clone().unwrap();
+    let is_new = !saved_searches.iter().any(|search| search.id == editing.id);
+
+    let name_value = use_state(|| editing.name.clone());
+    let query_value = use_state(|| editing.query.clone());
+
+    {
+        // Remove the search from the `EditingSavedSearchState` when the component is unmounted
+        let editing_saved_search = editing_saved_search;
+        use_effect_with_deps(move |_| move || editing_saved_search.set(None), ());
+    }
+
+    let onclick = Callback::from({
+        let name = name_value.trim().to_string();
+        let query = query_value.to_string();
+        let editing = editing.clone();
+
+        move |_| {
+            popup_box_state.set(PopupBox::None);
+
+            let search = SavedSearch {
+                name: if name.is_empty() {
+                    String::from("Untitled search")
+                } else {
+                    name.clone()
+                },
+                query: query.clone(),
+                ..editing.clone()
+            };
+
+            let saved_searches = saved_searches.clone();
+            let display_error_data = display_error_data.clone();
+            let popup_box_state = popup_box_state.clone();
+
+            spawn_local(async move {
+                let result = save_search(struct_to_string(&search).unwrap())
+                    .await
+                    .unwrap()
+                    .as_string()
+                    .unwrap();
+
+                if let Ok(saved) = string_to_struct::<SavedSearch>(&result) {
+                    let mut searches = (*saved_searches).clone();
+
+                    match searches.iter_mut().find(|search| search.id == saved.id) {
+                        Some(search) => *search = saved,
+                        None => searches.push(saved),
+                    }
+                    saved_searches.set(searches);
+
+                    console_log!("Successfully saved the search");
+                } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                    display_error_data.set(Some(DisplayErrorInnerData {
+                        class: DisplayErrorClass::Error,
+                        error_reporter,
+                        options_buttons: None,
+                        options_message: None,
+                    }));
+                    popup_box_state.set(PopupBox::DisplayError);
+                } else {
+                    console_error!(result);
+                }
+            });
+        }
+    });
+
+    // the tags and browsers which are saved with the search
+    let filter = &editing.filter;
+    let browser_names = |browsers: &[Browser]| {
+        browsers
+            .iter()
+            .map(|browser| browser.to_string())
+            .collect::<Vec<String>>()
+    };
+    let mut filters = Vec::new();
+    if let Some(tags) = &filter.tags {
+        filters.push(format!(
+            "{} of the tags: {}",
+            mode_name(filter.tags_mode),
+            tags.join(", ")
+        ));
+    }
+    if !filter.excluded_tags.is_empty() {
+        filters.push(format!(
+            "None of the tags: {}",
+            filter.excluded_tags.join(", ")
+        ));
+    }
+    if let Some(browsers) = &filter.browsers {
+        filters.push(format!(
+            "{} of the browsers: {}",
+            mode_name(filter.browsers_mode),
+            browser_names(browsers).join(", ")
+        ));
+    }
+    if !filter.excluded_browsers.is_empty() {
+        filters.push(format!(
+            "None of the browsers: {}",
+            browser_names(&filter.excluded_browsers).join(", ")
+        ));
+    }
+    if let Some((from, to)) = filter.priority_range {
+        filters.push(format!("Priorities: {from} to {to}"));
+    }
+    if let Some(domains) = &filter.domains {
+        filters.push(format!("Any of the domains: {}", domains.join(", ")));
+    }
+    if let Some(complete) = filter.complete {
+        filters.push(String::from(if complete {
+            "Complete"
+        } else {
+            "Incomplete"
+        }));
+    }
+    if let Some((from, to)) = filter.created_range {
+        filters.push(format!(
+            "Created from {} to {}",
+            from.with_timezone(&Local).format("%d %B %Y"),
+            to.with_timezone(&Local).format("%d %B %Y")
+        ));
+    }
+
+    html! {
+        <Popup title={if is_new { "Save the search" } else { "Edit the search" }} id="edit-saved-search">
+            if !filters.is_empty() {
+                <div class="saved-search-filters">
+                {
+                    filters.into_iter().map(|filter| html! {
+                        <p>{filter}</p>
+                    }).collect::<Html>()
+                }
+                </div>
+            }
+
+            <Form id="edit-saved-search" {onclick} button_text={"Save"}>
+                <InputWrapper id="saved-search-name">
+                    <InputDiv>
+                        <Label text="Name of the search" />
+                        <Input value_state={name_value} init_focus={true} />
+                    </InputDiv>
+                </InputWrapper>
+
+                <InputWrapper id="saved-search-query">
+                    <InputDiv>
+                        <Label text="Query">
+                            <span>{" (e.g. tutorial tag:rust is:unopened)"}</span>
+                        </Label>
+                        <Input value_state={query_value} />
+                    </InputDiv>
+                </InputWrapper>
+            </Form>
+        </Popup>
+    }
+}
+
+/// Name of the [`MatchMode`] inside the description of the filter
+fn mode_name(mode: MatchMode) -> &'static str {
+    match mode {
+        MatchMode::Any => "Any",
+        MatchMode::All => "All",
+    }
+}
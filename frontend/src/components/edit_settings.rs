@@ -0,0 +1,187 @@
+use crate::*;
+use dsaver_core::settings::Settings;
+use dsaver_core::storage::StorageBackend;
+
+/// Edit the settings of the app
+///
+/// This component is a popup box and it will appear if you set the [`PopupBox`] to [`PopupBox::EditSettings`].
+///
+/// If the user chooses another storage, then all links will be moved into that storage and the [`LinksState`] state will be updated with the links of the new storage.
+///
+/// The user can also choose to archive the complete links, to mark the links as complete when they are opened, and how many days the deleted links stay in the [`Trash`].
+#[function_component(EditSettings)]
+pub fn edit_settings() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+    let archive_completed = use_context::<ArchiveCompletedState>().unwrap().0;
+
+    // `None` until the settings are loaded
+    let settings = use_state(|| None::<Settings>);
+    let storage_value = use_state(String::new);
+    // the checkboxes are checked if these are `true`
+    let archive_value = use_state(|| false);
+    let complete_on_open_value = use_state(|| false);
+    let retention_value = use_state(String::new);
+
+    {
+        // Load the settings when the component is mounted
+        let settings = settings.clone();
+        let storage_value = storage_value.clone();
+        let archive_value = archive_value.clone();
+        let complete_on_open_value = complete_on_open_value.clone();
+        let retention_value = retention_value.clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let result = get_settings().await.unwrap().as_string().unwrap();
+
+                    if let Ok(loaded) = string_to_struct::<Settings>(&result) {
+                        storage_value.set(loaded.storage.to_string());
+                        archive_value.set(loaded.archive_completed);
+                        complete_on_open_value.set(loaded.complete_on_open);
+                        retention_value.set(loaded.trash_retention_days.to_string());
+                        settings.set(Some(loaded));
+                    } else {
+                        console_error!(result);
+                    }
+                });
+
+                || ()
+            },
+            (),
+        );
+    }
+
+    let onclick = Callback::from({
+        let settings = settings.clone();
+        let storage_value = storage_value.clone();
+        let archive_value = archive_value.clone();
+        let complete_on_open_value = complete_on_open_value.clone();
+        let retention_value = retention_value.clone();
+
+        move |_| {
+            let (current, new_settings) = match &*settings {
+                Some(settings) => (
+                    settings.storage,
+                    Settings {
+                        archive_completed: *archive_value,
+                        complete_on_open: *complete_on_open_value,
+                        // keep the old value if the user typed something else than a number
+                        trash_retention_days: retention_value
+                            .trim()
+                            .parse()
+                            .unwrap_or(settings.trash_retention_days),
+                        ..settings.clone()
+                    }
+                    .validated(),
+                ),
+                None => return,
+            };
+
+            // the storage chosen by the user
+            let backend = StorageBackend::get_vec()
+                .into_iter()
+                .find(|backend| backend.to_string() == *storage_value)
+                .unwrap_or(current);
+
+            popup_box_state.set(PopupBox::None);
+
+            if Some(&new_settings) != settings.as_ref() {
+                archive_completed.set(new_settings.archive_completed);
+
+                spawn_local(async move {
+                    let result = update_settings(struct_to_string(&new_settings).unwrap())
+                        .await
+                        .unwrap()
+                        .as_string()
+                        .unwrap();
+
+                    if string_to_struct::<Settings>(&result).is_err() {
+                        console_error!(result);
+                    }
+                });
+            }
+
+            if backend == current {
+                return;
+            }
+
+            let links = links.clone();
+            let display_error_data = display_error_data.clone();
+            let popup_box_state = popup_box_state.clone();
+
+            spawn_local(async move {
+                // move the links into the new storage
+                let result = migrate_storage(struct_to_string(&backend).unwrap())
+                    .await
+                    .unwrap()
+                    .as_string()
+                    .unwrap();
+
+                if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                    display_error_data.set(Some(DisplayErrorInnerData {
+                        class: DisplayErrorClass::Error,
+                        error_reporter,
+                        options_buttons: None,
+                        options_message: None,
+                    }));
+                    popup_box_state.set(PopupBox::DisplayError);
+
+                    return;
+                }
+
+                // read the links from the new storage
+                let result = get_links().await.unwrap().as_string().unwrap();
+
+                if let Ok(new_links) = string_to_struct::<Vec<Link>>(&result) {
+                    links.set(new_links);
+
+                    console_log!("Successfully moved the links");
+                } else {
+                    console_error!(result);
+                }
+            });
+        }
+    });
+
+    html! {
+        <Popup title="Settings" id="edit-settings">
+            if settings.is_some() {
+                <Form id="edit-settings" {onclick} button_text={"Save"}>
+                    <Select>
+                        <SelectLabel text="Where the links are stored (SQLite is faster for thousands of links)" />
+                        <Box
+                            list={StorageBackend::get_vec().iter().map(|backend| backend.to_string()).collect::<Vec<String>>()}
+                            class="storage-div"
+                            id="storage-div"
+                            value_state={storage_value}
+                        />
+                    </Select>
+                    <InputWrapper id="settings-archive">
+                        <Checkbox
+                            label_text="Hide the complete links, they are only shown in the archive"
+                            input_value_is_empty={true}
+                            disabled={archive_value}
+                        />
+                    </InputWrapper>
+                    <InputWrapper id="settings-complete-on-open">
+                        <Checkbox
+                            label_text="Mark a link as complete after opening it"
+                            input_value_is_empty={true}
+                            disabled={complete_on_open_value}
+                        />
+                    </InputWrapper>
+                    <InputWrapper id="settings-trash-retention">
+                        <InputDiv>
+                            <Label text="Days to keep the deleted links in the trash">
+                                <span>{" (0 to keep them until you delete them)"}</span>
+                            </Label>
+                            <Input value_state={retention_value} />
+                        </InputDiv>
+                    </InputWrapper>
+                </Form>
+            }
+        </Popup>
+    }
+}
@@ -0,0 +1,265 @@
+use crate::*;
+use dsaver_core::tag::{is_within, normalize_tag, TagInfo};
+
+/// Rename, merge or delete a tag in all links, or change its color, icon and description
+///
+/// This component is a popup box and it will appear if you set the [`PopupBox`] to [`PopupBox::EditTag`].
+///
+/// Before you can use this component you need to set the tag in [`EditingTagState`]. When this component goes out of scope, it will make that state [`None`].
+///
+/// The tag can be renamed, merged into another tag (a link with both tags keeps only the other one) or removed from all links. Its child tags (like `dev/rust` of `dev`) are always moved or removed with it. Every change is a single change in the [`Activity`], so it can be undone with Ctrl+Z. After the change, the [`LinksState`] and the [`LinkFilterState`] are updated.
+///
+/// The color, icon and description of the tag are saved into the [`TagInfosState`]. They are moved with the tag when it is renamed or merged.
+#[function_component(EditTag)]
+pub fn edit_tag() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let links_tags = use_context::<LinksTagsState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+    let editing_tag = use_context::<EditingTagState>().unwrap().0;
+    let tag_infos = use_context::<TagInfosState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+
+    let tag = (*editing_tag).clone().unwrap();
+    let name_value = use_state(|| tag.clone());
+
+    let info = tag_infos
+        .get(&tag)
+        .cloned()
+        .unwrap_or_else(|| TagInfo::new(&tag));
+    let color_value = use_state(|| info.color.unwrap_or_default());
+    let icon_value = use_state(|| info.icon.unwrap_or_default());
+    let description_value = use_state(|| info.description.unwrap_or_default());
+
+    {
+        // Remove the tag from the `EditingTagState` when the component is unmounted
+        let editing_tag = editing_tag;
+        use_effect_with_deps(move |_| move || editing_tag.set(None), ());
+    }
+
+    // Save the color, icon and description, and update the `TagInfosState`
+    let save_info = Callback::from({
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
+
+        // an empty value is not stored
+        let value = |state: &UseStateHandle<String>| {
+            Some(state.trim().to_string()).filter(|value| !value.is_empty())
+        };
+        let info = TagInfo {
+            tag: tag.clone(),
+            color: value(&color_value),
+            icon: value(&icon_value),
+            description: value(&description_value),
+        };
+
+        move |_| {
+            popup_box_state.set(PopupBox::None);
+
+            let info = info.clone();
+            let tag_infos = tag_infos.clone();
+            let display_error_data = display_error_data.clone();
+            let popup_box_state = popup_box_state.clone();
+
+            spawn_local(async move {
+                let result = save_tag_info(struct_to_string(&info).unwrap())
+                    .await
+                    .unwrap()
+                    .as_string()
+                    .unwrap();
+
+                if let Ok(info) = string_to_struct::<TagInfo>(&result) {
+                    let mut infos = (*tag_infos).clone();
+                    if info.is_empty() {
+                        infos.remove(&info.tag);
+                    } else {
+                        infos.insert(info.tag.clone(), info);
+                    }
+                    tag_infos.set(infos);
+                } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                    show_tag_error(error_reporter, display_error_data, popup_box_state);
+                } else {
+                    console_error!(result);
+                }
+            });
+        }
+    });
+
+    // Run the change, then update the links and replace the tag inside the filter with `new_tag`
+    let change_tags = {
+        let links = links.clone();
+        let link_filter = link_filter.clone();
+        let tag = tag.clone();
+
+        move |action: TagAction| {
+            popup_box_state.set(PopupBox::None);
+
+            let links = links.clone();
+            let link_filter = link_filter.clone();
+            let display_error_data = display_error_data.clone();
+            let popup_box_state = popup_box_state.clone();
+            let tag = tag.clone();
+
+            spawn_local(async move {
+                let (result, new_tag) = match action {
+                    TagAction::Rename(to) => (rename_tag(tag.clone(), to.clone()).await, Some(to)),
+                    TagAction::Merge(into) => {
+                        (merge_tags(tag.clone(), into.clone()).await, Some(into))
+                    }
+                    TagAction::Delete => (delete_tag(tag.clone()).await, None),
+                };
+                let result = result.unwrap().as_string().unwrap();
+
+                if let Ok(new_links) = string_to_struct::<Vec<Link>>(&result) {
+                    // the new tag is selected like the old one was
+                    let mut filter = (*link_filter).clone();
+                    let selection = Selection::of(&filter.tags, &filter.excluded_tags, &tag);
+                    remove_selection(&mut filter.tags, &mut filter.excluded_tags, &tag);
+
+                    if let Some(new_tag) = new_tag {
+                        if Selection::of(&filter.tags, &filter.excluded_tags, &new_tag)
+                            == Selection::None
+                        {
+                            match selection {
+                                Selection::Included => {
+                                    filter.tags.get_or_insert_with(Vec::new).push(new_tag)
+                                }
+                                Selection::Excluded => filter.excluded_tags.push(new_tag),
+                                Selection::None => (),
+                            }
+                        }
+                    }
+
+                    link_filter.set(filter);
+                    links.set(new_links);
+                } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                    show_tag_error(error_reporter, display_error_data, popup_box_state);
+                } else {
+                    console_error!(result);
+                }
+            });
+        }
+    };
+
+    let onclick = Callback::from({
+        let change_tags = change_tags.clone();
+        let name = normalize_tag(name_value.trim());
+        let tag = tag.clone();
+        let links_tags = links_tags.clone();
+
+        move |_| {
+            if name == tag {
+                return;
+            }
+
+            // renaming to an existing tag (or parent tag) joins both tags
+            if links_tags.keys().any(|other| is_within(other, &name)) {
+                change_tags(TagAction::Merge(name.clone()));
+            } else {
+                change_tags(TagAction::Rename(name.clone()));
+            }
+        }
+    });
+
+    let mut other_tags = links_tags
+        .keys()
+        // a tag can't be merged into its child tags
+        .filter(|other| !is_within(other, &tag))
+        .cloned()
+        .collect::<Vec<String>>();
+    other_tags.sort();
+
+    let has_children = links_tags
+        .keys()
+        .any(|other| *other != tag && is_within(other, &tag));
+
+    html! {
+        <Popup title={format!("Edit the tag {tag}")} id="edit-tag">
+            <Form id="edit-tag" {onclick} button_text={"Rename"}>
+                <InputWrapper id="tag-name">
+                    <InputDiv>
+                        <Label text="New name of the tag">
+                            <span>{" (an existing tag merges both tags)"}</span>
+                        </Label>
+                        <Input value_state={name_value} init_focus={true} />
+                    </InputDiv>
+                </InputWrapper>
+
+                <InputWrapper id="merge-tag">
+                    <InputTags
+                        id="merge-tags"
+                        label_text="Or merge it into another tag"
+                        tags_values={other_tags}
+                        tag_type={
+                            TagsType::Button(Callback::from({
+                                let change_tags = change_tags.clone();
+                                move |(_, into): (MouseEvent, String)| change_tags(TagAction::Merge(into))
+                            }))
+                        }
+                    />
+                </InputWrapper>
+            </Form>
+
+            <Form id="tag-info" onclick={save_info} button_text={"Save"}>
+                <InputWrapper id="tag-color">
+                    <InputDiv>
+                        <Label text="Color">
+                            <span>{" (like #dea584)"}</span>
+                        </Label>
+                        <Input value_state={color_value} />
+                    </InputDiv>
+                </InputWrapper>
+
+                <InputWrapper id="tag-icon">
+                    <InputDiv>
+                        <Label text="Icon">
+                            <span>{" (an emoji)"}</span>
+                        </Label>
+                        <Input value_state={icon_value} />
+                    </InputDiv>
+                </InputWrapper>
+
+                <InputWrapper id="tag-description">
+                    <InputDiv>
+                        <Label text="Description" />
+                        <Input value_state={description_value} />
+                    </InputDiv>
+                </InputWrapper>
+            </Form>
+
+            <button class="delete-tag" onclick={move |_| change_tags(TagAction::Delete)}>
+                {if has_children {
+                    format!("Remove {tag} and its child tags from all links")
+                } else {
+                    format!("Remove {tag} from all links")
+                }}
+            </button>
+        </Popup>
+    }
+}
+
+/// Show the error in the [`DisplayError`] popup
+fn show_tag_error(
+    error_reporter: ErrorReporter,
+    display_error_data: UseStateHandle<Option<DisplayErrorInnerData>>,
+    popup_box_state: UseStateHandle<PopupBox>,
+) {
+    display_error_data.set(Some(DisplayErrorInnerData {
+        class: DisplayErrorClass::Error,
+        error_reporter,
+        options_buttons: None,
+        options_message: None,
+    }));
+
+    popup_box_state.set(PopupBox::DisplayError);
+}
+
+/// What the user wants to do with the tag
+enum TagAction {
+    /// Rename it to a new tag
+    Rename(String),
+    /// Merge it into an existing tag
+    Merge(String),
+    /// Remove it from all links
+    Delete,
+}
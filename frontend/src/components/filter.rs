@@ -2,13 +2,15 @@ use crate::*;
 
 /// Component for filtering links
 ///
-/// With this component you can filter links by their `tags` and `browser` attributes
+/// With this component you can filter links by their `tags`, `browser`, `priority`, domain, `complete`, `created_at` and `last_opened_at` attributes
 ///
-/// It will display all tags and browser from all links and when you click on them, only that links will be displayed which have the same tag or browser
+/// It will display all tags and browser from all links. Click on them to show only the links which have them, click again to hide those links, and click once more to unselect them. The selection is stored in the [`LinkFilterState`] state.
 #[function_component(Filter)]
 pub fn filter() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
     let links_tags = use_context::<LinksTagsState>().unwrap().0;
     let links_browsers = use_context::<LinksBrowsersState>().unwrap().0;
+    let links_domains = use_context::<LinksDomainsState>().unwrap().0;
 
     html! {
         <div class="filter">
@@ -18,6 +20,101 @@ pub fn filter() -> Html {
             if !(*links_browsers).is_empty() {
                 <Browsers />
             }
+            if !(*links).is_empty() {
+                <Priorities />
+            }
+            if !(*links_domains).is_empty() {
+                <Domains />
+            }
+            if !(*links).is_empty() {
+                <Completion />
+                <CreatedDates />
+                <Opened />
+            }
         </div>
     }
 }
+
+/// How a tag or browser is selected inside the [`LinkFilterState`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Selection {
+    /// Only the links which have it are shown
+    Included,
+    /// The links which have it are hidden
+    Excluded,
+    /// It is not selected
+    None,
+}
+
+impl Selection {
+    /// Get the selection of the `value` from the `included` and `excluded` lists of a [`LinkFilter`]
+    pub fn of<T: PartialEq>(included: &Option<Vec<T>>, excluded: &[T], value: &T) -> Self {
+        if excluded.contains(value) {
+            Self::Excluded
+        } else if matches!(included, Some(included) if included.contains(value)) {
+            Self::Included
+        } else {
+            Self::None
+        }
+    }
+
+    /// Class name of the selected element
+    pub fn class(&self) -> &'static str {
+        match self {
+            Self::Included => "included",
+            Self::Excluded => "excluded",
+            Self::None => "",
+        }
+    }
+}
+
+/// Select the `value` in the next way: not selected → included → excluded → not selected
+///
+/// `included` and `excluded` are the lists of a [`LinkFilter`]. If nothing is included, then `included` will be `None`, so that all links are shown.
+pub fn toggle_selection<T: PartialEq + Clone>(
+    included: &mut Option<Vec<T>>,
+    excluded: &mut Vec<T>,
+    value: &T,
+) {
+    match Selection::of(included, excluded, value) {
+        Selection::None => included.get_or_insert_with(Vec::new).push(value.clone()),
+        Selection::Included => {
+            remove_selection(included, excluded, value);
+            excluded.push(value.clone());
+        }
+        Selection::Excluded => remove_selection(included, excluded, value),
+    }
+}
+
+/// Unselect the `value` from the `included` and `excluded` lists of a [`LinkFilter`]
+pub fn remove_selection<T: PartialEq>(
+    included: &mut Option<Vec<T>>,
+    excluded: &mut Vec<T>,
+    value: &T,
+) {
+    if let Some(values) = included {
+        values.retain(|included| included != value);
+
+        if values.is_empty() {
+            *included = None;
+        }
+    }
+
+    excluded.retain(|excluded| excluded != value);
+}
+
+/// Text of a [`MatchMode`] button
+pub fn match_mode_text(mode: MatchMode) -> &'static str {
+    match mode {
+        MatchMode::Any => "Any (OR)",
+        MatchMode::All => "All (AND)",
+    }
+}
+
+/// The other [`MatchMode`]
+pub fn toggle_match_mode(mode: MatchMode) -> MatchMode {
+    match mode {
+        MatchMode::Any => MatchMode::All,
+        MatchMode::All => MatchMode::Any,
+    }
+}
@@ -0,0 +1,151 @@
+use crate::*;
+use chrono::{DateTime, Local, Utc};
+
+/// A selected value of the [`LinkFilterState`], shown by the [`FilterChips`] component
+#[derive(Clone, PartialEq)]
+enum Chip {
+    Tag(String),
+    Browser(Browser),
+    Priority(char, char),
+    Domain(String),
+    Complete(bool),
+    Created(DateTime<Utc>, DateTime<Utc>),
+    NotOpened(DateTime<Utc>),
+}
+
+/// Show the selected tags, browsers, priorities, domains, completion, creation dates and opening dates of the [`LinkFilterState`] as chips
+///
+/// It is shown above the links, so the user can always see why some links are hidden. Every chip has a button to unselect it, and all of them can be cleared at once.
+///
+/// Nothing is rendered if nothing is selected.
+#[function_component(FilterChips)]
+pub fn filter_chips() -> Html {
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+
+    // (chip, is it excluded?)
+    let mut chips = Vec::new();
+
+    for tag in link_filter.tags.iter().flatten() {
+        chips.push((Chip::Tag(tag.clone()), false));
+    }
+    for tag in &link_filter.excluded_tags {
+        chips.push((Chip::Tag(tag.clone()), true));
+    }
+    for browser in link_filter.browsers.iter().flatten() {
+        chips.push((Chip::Browser(browser.clone()), false));
+    }
+    for browser in &link_filter.excluded_browsers {
+        chips.push((Chip::Browser(browser.clone()), true));
+    }
+    if let Some((from, to)) = link_filter.priority_range {
+        chips.push((Chip::Priority(from, to), false));
+    }
+    for domain in link_filter.domains.iter().flatten() {
+        chips.push((Chip::Domain(domain.clone()), false));
+    }
+    if let Some(complete) = link_filter.complete {
+        chips.push((Chip::Complete(complete), false));
+    }
+    if let Some((from, to)) = link_filter.created_range {
+        chips.push((Chip::Created(from, to), false));
+    }
+    if let Some(since) = link_filter.not_opened_since {
+        chips.push((Chip::NotOpened(since), false));
+    }
+
+    if chips.is_empty() {
+        return html! {};
+    }
+
+    // how the included tags and browsers are matched, only useful if there are more than one
+    let mut modes = Vec::new();
+
+    if matches!(&link_filter.tags, Some(tags) if tags.len() > 1) {
+        modes.push(format!("{} tags", mode_text(link_filter.tags_mode)));
+    }
+    if matches!(&link_filter.browsers, Some(browsers) if browsers.len() > 1) {
+        modes.push(format!("{} browsers", mode_text(link_filter.browsers_mode)));
+    }
+
+    let clear = {
+        let link_filter = link_filter.clone();
+        move |_| link_filter.set(LinkFilter::default())
+    };
+
+    html! {
+        <div class="filter-chips">
+            if !modes.is_empty() {
+                <span class="modes">{format!("Matching {}", modes.join(" and "))}</span>
+            }
+            {
+                chips.into_iter().map(|(chip, excluded)| {
+                    let text = match &chip {
+                        Chip::Tag(tag) => format!("Tag: {tag}"),
+                        Chip::Browser(browser) => format!("Browser: {browser}"),
+                        Chip::Priority(from, to) if from == to => format!("Priority: {from}"),
+                        Chip::Priority(from, to) => format!("Priority: {from} to {to}"),
+                        Chip::Domain(domain) => format!("Domain: {domain}"),
+                        Chip::Complete(true) => String::from("Complete"),
+                        Chip::Complete(false) => String::from("Incomplete"),
+                        Chip::Created(from, to) => format!("Created: {}", created_text(*from, *to)),
+                        Chip::NotOpened(since) if *since == stale_since() => format!("Not opened in {STALE_DAYS} days"),
+                        Chip::NotOpened(since) => format!("Not opened since {}", since.with_timezone(&Local).format("%d %B %Y")),
+                    };
+
+                    let remove = {
+                        let link_filter = link_filter.clone();
+                        move |_| {
+                            let mut filter = (*link_filter).clone();
+
+                            match &chip {
+                                Chip::Tag(tag) => remove_selection(&mut filter.tags, &mut filter.excluded_tags, tag),
+                                Chip::Browser(browser) => remove_selection(&mut filter.browsers, &mut filter.excluded_browsers, browser),
+                                Chip::Priority(..) => filter.priority_range = None,
+                                Chip::Domain(domain) => remove_selection(&mut filter.domains, &mut Vec::new(), domain),
+                                Chip::Complete(_) => filter.complete = None,
+                                Chip::Created(..) => filter.created_range = None,
+                                Chip::NotOpened(_) => filter.not_opened_since = None,
+                            }
+
+                            link_filter.set(filter);
+                        }
+                    };
+
+                    html! {
+                        <span class={classes!("chip", if excluded { "excluded" } else { "included" })}>
+                            if excluded {
+                                <span class="not">{"Not "}</span>
+                            }
+                            {text}
+                            <button class="remove" title="Remove this filter" onclick={remove}>{"×"}</button>
+                        </span>
+                    }
+                }).collect::<Html>()
+            }
+            <button class="clear" onclick={clear}>{"Clear all"}</button>
+        </div>
+    }
+}
+
+/// How the included tags or browsers are matched
+fn mode_text(mode: MatchMode) -> &'static str {
+    match mode {
+        MatchMode::Any => "any of the",
+        MatchMode::All => "all of the",
+    }
+}
+
+/// Name of the [`DateRange`] of the creation dates, or the dates if it is not a [`DateRange`]
+fn created_text(from: DateTime<Utc>, to: DateTime<Utc>) -> String {
+    match DateRange::get_vec()
+        .into_iter()
+        .find(|range| range.bounds() == (from, to))
+    {
+        Some(range) => range.name().to_string(),
+        None => format!(
+            "{} to {}",
+            from.with_timezone(&Local).format("%d %B %Y"),
+            to.with_timezone(&Local).format("%d %B %Y")
+        ),
+    }
+}
@@ -0,0 +1,136 @@
+use crate::*;
+use chrono::Local;
+
+/// How the links are grouped by the [`DisplayLinks`] component
+///
+/// Every group has a title and is shown in a separate box.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GroupBy {
+    /// Group by [`Link::priority`], the highest priority (`A`) first.
+    ///
+    /// This is the default value of this enum.
+    #[default]
+    Priority,
+    /// Group by [`Link::domain`], in alphabetical order
+    Domain,
+    /// Group by [`Link::tags`], in alphabetical order. A link with several tags is shown in every group of its tags.
+    Tag,
+    /// Group by [`Link::browser`], in alphabetical order
+    Browser,
+    /// Group by the month of [`Link::created_at`], the newest month first
+    Date,
+}
+
+impl GroupBy {
+    /// All the values, in the order they are shown to the user
+    pub fn get_vec() -> Vec<Self> {
+        vec![
+            Self::Priority,
+            Self::Domain,
+            Self::Tag,
+            Self::Browser,
+            Self::Date,
+        ]
+    }
+
+    /// Name of the value, shown to the user
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Priority => "Priority",
+            Self::Domain => "Domain",
+            Self::Tag => "Tag",
+            Self::Browser => "Browser",
+            Self::Date => "Date",
+        }
+    }
+
+    /// Group the `links` by this value. The order of the links inside a group is not changed.
+    ///
+    /// It returns the title and the links of every group, in the order the groups should be shown. Empty groups are not returned.
+    ///
+    /// The links are borrowed, so nothing is cloned even if there are thousands of links.
+    pub fn group<'a>(&self, links: &[&'a Link]) -> Vec<(String, Vec<&'a Link>)> {
+        // (key for sorting the groups, title of the group, links)
+        let mut groups: Vec<(String, String, Vec<&'a Link>)> = Vec::new();
+
+        for &link in links {
+            let keys: Vec<(String, String)> = match self {
+                Self::Priority => vec![(link.priority.to_string(), link.priority.to_string())],
+                Self::Domain => {
+                    let domain = link.domain();
+                    // the links without a domain are shown at the end
+                    vec![(
+                        domain.clone().unwrap_or_else(|| String::from("~")),
+                        domain.unwrap_or_else(|| String::from("No domain")),
+                    )]
+                }
+                Self::Tag if link.tags.is_empty() => {
+                    vec![(String::from("~"), String::from("No tags"))]
+                }
+                Self::Tag => link
+                    .tags
+                    .iter()
+                    .map(|tag| (tag.to_lowercase(), tag.clone()))
+                    .collect(),
+                Self::Browser => vec![(link.browser.to_string(), link.browser.to_string())],
+                Self::Date => {
+                    let created_at = link.created_at.with_timezone(&Local);
+                    vec![(
+                        created_at.format("%Y-%m").to_string(),
+                        created_at.format("%B %Y").to_string(),
+                    )]
+                }
+            };
+
+            for (key, title) in keys {
+                match groups
+                    .iter_mut()
+                    .find(|(_, group_title, _)| *group_title == title)
+                {
+                    Some((_, _, group_links)) => group_links.push(link),
+                    None => groups.push((key, title, vec![link])),
+                }
+            }
+        }
+
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+        if *self == Self::Date {
+            // the newest month first
+            groups.reverse();
+        }
+
+        groups
+            .into_iter()
+            .map(|(_, title, links)| (title, links))
+            .collect()
+    }
+}
+
+/// Switch for choosing how the links are grouped
+///
+/// The chosen value is stored in the [`GroupByState`] state.
+#[function_component(GroupBySwitch)]
+pub fn group_by_switch() -> Html {
+    let group_by = use_context::<GroupByState>().unwrap().0;
+
+    html! {
+        <div class="group-by">
+            <span class="label">{"Group by"}</span>
+            {
+                GroupBy::get_vec().into_iter().map(|value| {
+                    let group_by = group_by.clone();
+
+                    html! {
+                        <button
+                            class={classes!("option", if *group_by == value { "selected" } else { "" })}
+                            onclick={move |_| group_by.set(value)}
+                        >
+                            {value.name()}
+                        </button>
+                    }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
@@ -1,19 +1,53 @@
+pub mod activity;
 pub mod app;
+pub mod backups;
 pub mod browsers;
+pub mod completion;
 pub mod create_link;
+pub mod created_dates;
 pub mod display_error;
 pub mod display_links;
+pub mod domains;
 pub mod edit_link;
+pub mod edit_saved_search;
+pub mod edit_settings;
+pub mod edit_tag;
 pub mod filter;
+pub mod filter_chips;
+pub mod group_by;
+pub mod opened;
+pub mod priorities;
+pub mod refresh_links;
+pub mod saved_searches;
+pub mod search;
 pub mod sidebar;
+pub mod sort_switch;
 pub mod tags;
+pub mod trash;
 
+pub use activity::*;
 pub use app::*;
+pub use backups::*;
 pub use browsers::*;
+pub use completion::*;
 pub use create_link::*;
+pub use created_dates::*;
 pub use display_error::*;
 pub use display_links::*;
+pub use domains::*;
 pub use edit_link::*;
+pub use edit_saved_search::*;
+pub use edit_settings::*;
+pub use edit_tag::*;
 pub use filter::*;
+pub use filter_chips::*;
+pub use group_by::*;
+pub use opened::*;
+pub use priorities::*;
+pub use refresh_links::*;
+pub use saved_searches::*;
+pub use search::*;
 pub use sidebar::*;
+pub use sort_switch::*;
 pub use tags::*;
+pub use trash::*;
@@ -0,0 +1,71 @@
+use crate::*;
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+
+/// After how many days without opening a link is shown in the "Not opened" view
+pub const STALE_DAYS: i64 = 90;
+
+/// The time since which the links of the "Not opened" view are not opened: midnight [`STALE_DAYS`] days ago, in the user's timezone.
+///
+/// It starts at midnight, so it doesn't change during a day.
+pub fn stale_since() -> DateTime<Utc> {
+    let date = Local::now().date_naive() - Duration::days(STALE_DAYS);
+
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+        .earliest()
+        .unwrap_or_else(Local::now)
+        .with_timezone(&Utc)
+}
+
+/// Show the links which are opened most, and the links which are not opened for a long time
+///
+/// "Most opened" sorts the links by how many times they were opened (see [`SortOrderState`]). Click it again to sort them by hand.
+///
+/// "Not opened in 90 days" shows only the links which are not opened in the last [`STALE_DAYS`] days, so the user can decide which links to delete. The links which are never opened are counted from when they were created. The selection is stored in the [`LinkFilterState`] state.
+#[function_component(Opened)]
+pub fn opened() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+    let sort_order = use_context::<SortOrderState>().unwrap().0;
+
+    let since = stale_since();
+    let opened_count = links.iter().filter(|link| link.open_count > 0).count();
+    let stale_filter = LinkFilter::default().not_opened_since(since);
+    let stale_count = links
+        .iter()
+        .filter(|link| stale_filter.matches(link))
+        .count();
+
+    let most_opened = *sort_order == SortOrder::OpenCount;
+    let stale = link_filter.not_opened_since == Some(since);
+
+    html! {
+        <div class="filter-opened filterable">
+            <div class="title-bar">
+                <h1 class="title">{"Opened"}</h1>
+            </div>
+            <div class="opened elements">
+                <p class={classes!("opened-view", "element", if most_opened { "included" } else { "" })} onclick={
+                    move |_| {
+                        let value = if most_opened { SortOrder::default() } else { SortOrder::OpenCount };
+                        sort_order.set(value);
+                        save_sort_order(value);
+                    }
+                }>
+                    {"Most opened"}
+                    <span class="count">{opened_count}</span>
+                </p>
+                <p class={classes!("opened-view", "element", if stale { "included" } else { "" })} onclick={
+                    move |_| {
+                        let mut filter = (*link_filter).clone();
+                        filter.not_opened_since = if stale { None } else { Some(since) };
+                        link_filter.set(filter);
+                    }
+                }>
+                    {format!("Not opened in {STALE_DAYS} days")}
+                    <span class="count">{stale_count}</span>
+                </p>
+            </div>
+        </div>
+    }
+}
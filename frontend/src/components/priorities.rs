@@ -0,0 +1,59 @@
+use crate::*;
+
+/// Show all priorities from the links_state
+///
+/// If user clicks a priority, then only the links with that priority will be displayed. Click another priority to display a range of priorities (e.g. from `A` to `C`), and click a priority inside the range to display only that priority again.
+///
+/// The range is stored in the [`LinkFilterState`] state.
+#[function_component(Priorities)]
+pub fn priorities() -> Html {
+    let links_priorities = use_context::<LinksPrioritiesState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+
+    // sort the priorities, the highest priority (`A`) first
+    let mut priorities: Vec<(char, i32)> = (*links_priorities)
+        .iter()
+        .map(|(priority, count)| (*priority, *count))
+        .collect();
+    priorities.sort();
+
+    html! {
+        <div class="filter-priorities filterable">
+            <div class="title-bar">
+                <h1 class="title">{"Priorities"}</h1>
+            </div>
+            <div class="priorities elements">
+            {
+                priorities.into_iter().map(|(priority, count)| {
+                    let selected = matches!(link_filter.priority_range, Some((from, to)) if from <= priority && priority <= to);
+
+                    html! {
+                        <p class={classes!("priority", "element", if selected { "included" } else { "" })} onclick={
+                            let link_filter = link_filter.clone();
+
+                            move |_| {
+                                let mut filter = (*link_filter).clone();
+
+                                filter.priority_range = match filter.priority_range {
+                                    // unselect the only selected priority
+                                    Some((from, to)) if from == priority && to == priority => None,
+                                    // display only this priority
+                                    Some((from, to)) if from <= priority && priority <= to => Some((priority, priority)),
+                                    // extend the range until this priority
+                                    Some((from, to)) => Some((from.min(priority), to.max(priority))),
+                                    None => Some((priority, priority)),
+                                };
+
+                                link_filter.set(filter);
+                            }
+                        }>
+                            {priority}
+                            <span class="count">{count}</span>
+                        </p>
+                    }
+                }).collect::<Html>()
+            }
+            </div>
+        </div>
+    }
+}
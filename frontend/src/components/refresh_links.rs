@@ -0,0 +1,254 @@
+use crate::*;
+use dsaver_core::refresh::LinkRefresh;
+use dsaver_core::storage::image_key;
+
+/// Fetch the title, description and thumbnail of the links again
+///
+/// This component is a popup box and it will appear if you set the [`PopupBox`] to [`PopupBox::RefreshLinks`]. Set the [`RefreshingLinksState`] first.
+///
+/// The links are fetched one by one, and the changes of every link are shown before they are saved. The user can uncheck a link to keep it as it is. After applying, the updated links are put into the [`LinksState`] state. Every applied link can be undone with Ctrl+Z (see [`Activity`]).
+///
+/// A title written by the user (see [`Link::custom_title`]) is never changed.
+#[function_component(RefreshLinks)]
+pub fn refresh_links() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let refreshing_links = use_context::<RefreshingLinksState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+
+    // the fetched links, in the order of `refreshing_links`
+    let refreshes: UseStateHandle<Vec<(Uuid, Result<LinkRefresh, ErrorReporter>)>> =
+        use_state(Vec::new);
+    // ids of the links which the user has unchecked
+    let skipped: UseStateHandle<Vec<Uuid>> = use_state(Vec::new);
+
+    {
+        // Fetch the links one by one when the component is mounted
+        let refreshes = refreshes.clone();
+        let ids = (*refreshing_links).clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let mut fetched = Vec::new();
+
+                    for id in ids {
+                        let result = refresh_link(id.to_string())
+                            .await
+                            .unwrap()
+                            .as_string()
+                            .unwrap();
+
+                        if let Ok(refresh) = string_to_struct::<LinkRefresh>(&result) {
+                            fetched.push((id, Ok(refresh)));
+                        } else if let Ok(error_reporter) =
+                            string_to_struct::<ErrorReporter>(&result)
+                        {
+                            fetched.push((id, Err(error_reporter)));
+                        } else {
+                            console_error!(result);
+                            continue;
+                        }
+
+                        refreshes.set(fetched.clone());
+                    }
+                });
+
+                || ()
+            },
+            (),
+        );
+    }
+
+    let loading = refreshes.len() < refreshing_links.len();
+    let chosen: Vec<LinkRefresh> = refreshes
+        .iter()
+        .filter(|(id, _)| !skipped.contains(id))
+        .filter_map(|(_, result)| result.as_ref().ok())
+        .filter(|refresh| !refresh.is_empty())
+        .cloned()
+        .collect();
+
+    let apply_button = {
+        let links = links.clone();
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
+        let count = chosen.len();
+
+        html! {
+            <button class={classes!("button", "apply", if count == 0 { "disabled" } else { "" })} disabled={count == 0} onclick={
+                move |_| {
+                    let chosen = chosen.clone();
+                    let links = links.clone();
+                    let display_error_data = display_error_data.clone();
+                    let popup_box_state = popup_box_state.clone();
+
+                    spawn_local(async move {
+                        let mut updated = Vec::new();
+
+                        for refresh in chosen {
+                            let result = apply_refresh(struct_to_string(&refresh).unwrap())
+                                .await
+                                .unwrap()
+                                .as_string()
+                                .unwrap();
+
+                            if let Ok(link) = string_to_struct::<Link>(&result) {
+                                updated.push(link);
+                            } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                                // the links which are already saved are shown
+                                replace_links(&links, updated);
+                                show_refresh_error(error_reporter, display_error_data, popup_box_state);
+                                return;
+                            } else {
+                                console_error!(result);
+                            }
+                        }
+
+                        replace_links(&links, updated);
+                        popup_box_state.set(PopupBox::None);
+
+                        console_log!("Successfully refreshed");
+                    });
+                }
+            }>{format!("Apply {count} {}", if count == 1 { "change" } else { "changes" })}</button>
+        }
+    };
+
+    html! {
+        <Popup title="Refresh links" id="refresh-links">
+            <div class="refresh-links">
+                <div class="buttons">
+                    if loading {
+                        <p class="message">{format!("Fetching {}/{} links...", refreshes.len() + 1, refreshing_links.len())}</p>
+                    }
+                    {apply_button}
+                </div>
+                {
+                    refreshes.iter().map(|(id, result)| {
+                        let id = *id;
+                        let name = links
+                            .iter()
+                            .find(|link| link.id == Some(id))
+                            .map(|link| link.title.clone().filter(|title| !title.is_empty()).unwrap_or_else(|| link.url.clone()))
+                            .unwrap_or_default();
+
+                        html! {
+                            <div class="refresh">
+                                <div class="head">
+                                    if let Ok(refresh) = result {
+                                        if !refresh.is_empty() {
+                                            <input
+                                                type="checkbox"
+                                                class="apply-checkbox"
+                                                title="Apply the changes of this link"
+                                                checked={!skipped.contains(&id)}
+                                                onclick={
+                                                    let skipped = skipped.clone();
+                                                    move |_| {
+                                                        let mut new_skipped = (*skipped).clone();
+                                                        if new_skipped.contains(&id) {
+                                                            new_skipped.retain(|skipped_id| *skipped_id != id);
+                                                        } else {
+                                                            new_skipped.push(id);
+                                                        }
+                                                        skipped.set(new_skipped);
+                                                    }
+                                                }
+                                            />
+                                        }
+                                    }
+                                    <p class="name">{name}</p>
+                                </div>
+                                {
+                                    match result {
+                                        Err(error_reporter) => html! {
+                                            <p class="error">{format!("{}: {}", error_reporter.error_title(), error_reporter.actual_error())}</p>
+                                        },
+                                        Ok(refresh) if refresh.is_empty() => html! {
+                                            <p class="message">{"Nothing is changed"}</p>
+                                        },
+                                        Ok(refresh) => html! {
+                                            <RefreshChanges refresh={refresh.clone()} />
+                                        },
+                                    }
+                                }
+                            </div>
+                        }
+                    }).collect::<Html>()
+                }
+            </div>
+        </Popup>
+    }
+}
+
+/// Props for the [`RefreshChanges`] component.
+#[derive(Properties, PartialEq)]
+struct RefreshChangesProps {
+    /// The changes of the link
+    refresh: LinkRefresh,
+}
+
+/// The old and new title, description and thumbnail of a refreshed link
+#[function_component(RefreshChanges)]
+fn refresh_changes(props: &RefreshChangesProps) -> Html {
+    let refresh = &props.refresh;
+    let link = &refresh.link;
+
+    let field = |name: &str, old: &Option<String>, new: &str| {
+        html! {
+            <div class="field">
+                <p class="field-name">{name}</p>
+                <p class="old">{old.clone().unwrap_or_default()}</p>
+                <p class="new">{new}</p>
+            </div>
+        }
+    };
+
+    html! {
+        <div class="changes">
+            if let Some(title) = &refresh.title {
+                {field("Title", &link.title, title)}
+            } else if link.custom_title {
+                <p class="message">{"Your own title is kept"}</p>
+            }
+            if let Some(description) = &refresh.description {
+                {field("Description", &link.description, description)}
+            }
+            if refresh.image_changed() {
+                <div class="field">
+                    <p class="field-name">{"Thumbnail"}</p>
+                    <div class="thumbnails">
+                        // the old thumbnail is cached, and the new one is cached after applying
+                        if let Some(image) = &link.metadata.image {
+                            <img class="old" src={image_src(&image_key(image))} alt="Old thumbnail" />
+                        }
+                        if let Some(image) = &refresh.metadata.image {
+                            <img class="new" src={image.clone()} alt="New thumbnail" />
+                        } else {
+                            <p class="new">{"No thumbnail"}</p>
+                        }
+                    </div>
+                </div>
+            }
+            if refresh.title.is_none() && refresh.description.is_none() && !refresh.image_changed() {
+                <p class="message">{"The site name, icon or other information of the webpage is changed"}</p>
+            }
+        </div>
+    }
+}
+
+/// Show the error in the [`DisplayError`] popup
+fn show_refresh_error(
+    error_reporter: ErrorReporter,
+    display_error_data: UseStateHandle<Option<DisplayErrorInnerData>>,
+    popup_box_state: UseStateHandle<PopupBox>,
+) {
+    display_error_data.set(Some(DisplayErrorInnerData {
+        class: DisplayErrorClass::Error,
+        error_reporter,
+        options_buttons: None,
+        options_message: None,
+    }));
+
+    popup_box_state.set(PopupBox::DisplayError);
+}
@@ -0,0 +1,123 @@
+use crate::*;
+use dsaver_core::search::SearchIndex;
+
+/// Searches saved by the user, shown as smart collections in the sidebar
+///
+/// When the user clicks a search, its query is put into the [`Search`] bar and its filter is put into the [`LinkFilterState`], so only the matched links are displayed.
+///
+/// Every search shows how many links match it. The count is updated whenever the [`LinksState`] state changes. The links are searched with a [`SearchIndex`] like in the search bar, so the count is the same as the number of displayed links.
+///
+/// The current search (the query and the selected tags and browsers) can be saved with the "Save current search" button.
+#[function_component(SavedSearches)]
+pub fn saved_searches() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+    let search_query = use_context::<SearchQueryState>().unwrap().0;
+    let saved_searches = use_context::<SavedSearchesState>().unwrap().0;
+    let editing_saved_search = use_context::<EditingSavedSearchState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+
+    // only needed for the counts
+    let index = if saved_searches.is_empty() {
+        SearchIndex::default()
+    } else {
+        SearchIndex::new(&links)
+    };
+
+    let save_current = {
+        let link_filter = link_filter.clone();
+        let search_query = search_query.clone();
+        let editing_saved_search = editing_saved_search.clone();
+        let popup_box_state = popup_box_state.clone();
+
+        move |_| {
+            editing_saved_search.set(Some(
+                SavedSearch::new(&*search_query, &*search_query).filter((*link_filter).clone()),
+            ));
+            popup_box_state.set(PopupBox::EditSavedSearch);
+        }
+    };
+
+    html! {
+        <div class="saved-searches filterable">
+            <h1 class="title">{"Saved Searches"}</h1>
+            <div class="elements">
+            {
+                (*saved_searches).iter().map(|search| {
+                    let apply = {
+                        let search = search.clone();
+                        let link_filter = link_filter.clone();
+                        let search_query = search_query.clone();
+
+                        move |_| {
+                            search_query.set(search.query.clone());
+                            link_filter.set(search.filter.clone());
+                        }
+                    };
+
+                    let edit = {
+                        let search = search.clone();
+                        let editing_saved_search = editing_saved_search.clone();
+                        let popup_box_state = popup_box_state.clone();
+
+                        move |event: MouseEvent| {
+                            // don't apply the search
+                            event.stop_propagation();
+
+                            editing_saved_search.set(Some(search.clone()));
+                            popup_box_state.set(PopupBox::EditSavedSearch);
+                        }
+                    };
+
+                    let delete = {
+                        let id = search.id;
+                        let saved_searches = saved_searches.clone();
+                        let display_error_data = display_error_data.clone();
+                        let popup_box_state = popup_box_state.clone();
+
+                        move |event: MouseEvent| {
+                            // don't apply the search
+                            event.stop_propagation();
+
+                            let saved_searches = saved_searches.clone();
+                            let display_error_data = display_error_data.clone();
+                            let popup_box_state = popup_box_state.clone();
+
+                            spawn_local(async move {
+                                let result = delete_search(id.to_string()).await.unwrap().as_string().unwrap();
+
+                                if string_to_struct::<SavedSearch>(&result).is_ok() {
+                                    let mut searches = (*saved_searches).clone();
+                                    searches.retain(|search| search.id != id);
+                                    saved_searches.set(searches);
+                                } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                                    display_error_data.set(Some(DisplayErrorInnerData {
+                                        class: DisplayErrorClass::Error,
+                                        error_reporter,
+                                        options_buttons: None,
+                                        options_message: None,
+                                    }));
+                                    popup_box_state.set(PopupBox::DisplayError);
+                                } else {
+                                    console_error!(result);
+                                }
+                            });
+                        }
+                    };
+
+                    html! {
+                        <div class="saved-search element" title={search.query.clone()} onclick={apply}>
+                            <span class="name">{&search.name}</span>
+                            <button class="edit" onclick={edit}>{"Edit"}</button>
+                            <button class="delete" onclick={delete}>{"Delete"}</button>
+                            <span class="count">{search.count(&links, &index)}</span>
+                        </div>
+                    }
+                }).collect::<Html>()
+            }
+            </div>
+            <button class="save-search" onclick={save_current}>{"Save current search"}</button>
+        </div>
+    }
+}
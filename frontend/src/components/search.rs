@@ -0,0 +1,31 @@
+use crate::*;
+
+/// Search bar for the links
+///
+/// The text typed by the user is stored in [`SearchQueryState`]. The [`DisplayLinks`] component searches the links whenever it changes.
+///
+/// Besides words, the user can filter the links with keys like `tag:rust priority:<=C -tag:video`. See [`Query`](dsaver_core::query::Query) for all keys.
+#[function_component(Search)]
+pub fn search() -> Html {
+    let search_query = use_context::<SearchQueryState>().unwrap().0;
+
+    let oninput = {
+        let search_query = search_query.clone();
+        move |event: InputEvent| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            search_query.set(input.value());
+        }
+    };
+
+    html! {
+        <div class="search">
+            <input
+                type="search"
+                class="search-input"
+                placeholder="Search links, e.g. tutorial tag:rust priority:<=C is:incomplete"
+                value={(*search_query).clone()}
+                {oninput}
+            />
+        </div>
+    }
+}
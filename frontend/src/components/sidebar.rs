@@ -1,13 +1,24 @@
 use crate::*;
+use dsaver_core::check::CheckProgress;
 
 /// Sidebar component
 ///
 /// From this component you can change settings and add new links
 ///
-/// You can also filter links by their `tags` and `browser` attributes
+/// You can also filter links by their `tags` and `browser` attributes, show the links of a saved search, show the archive of complete links, or restore the deleted links from the trash
+///
+/// The user can check all the links in the background to find the dead ones, and then show only the broken links
 #[function_component(Sidebar)]
 pub fn sidebar() -> Html {
     let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+    let links = use_context::<LinksState>().unwrap().0;
+    let show_archive = use_context::<ShowArchiveState>().unwrap().0;
+    let show_broken = use_context::<ShowBrokenState>().unwrap().0;
+    let link_check_progress = use_context::<LinkCheckProgressState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+
+    let complete_count = links.iter().filter(|link| link.complete).count();
+    let broken_count = links.iter().filter(|link| link.is_broken()).count();
 
     html! {
         <div class="sidebar" id="sidebar" >
@@ -35,17 +46,122 @@ pub fn sidebar() -> Html {
                         }
 
                     )} onclick={
+                        let popup_box_state = popup_box_state.clone();
                         move |_| {
                             popup_box_state.set(PopupBox::CreateLink);
                         }
                     }>{"Create a New Link"}</button>
                 </div>
+
+                <div class="divider">
+                    <button class="backups-button" onclick={
+                        let popup_box_state = popup_box_state.clone();
+                        move |_| {
+                            popup_box_state.set(PopupBox::Backups);
+                        }
+                    }>{"Backups"}</button>
+                </div>
+
+                <div class="divider">
+                    <button class={classes!("archive-button", if *show_archive { "selected" } else { "" })} onclick={
+                        let show_archive = show_archive.clone();
+                        let show_broken = show_broken.clone();
+                        move |_| {
+                            show_archive.set(!*show_archive);
+                            show_broken.set(false);
+                        }
+                    }>
+                        {"Archive"}
+                        <span class="count">{complete_count}</span>
+                    </button>
+                </div>
+
+                <div class="divider">
+                    <button class={classes!("broken-button", if *show_broken { "selected" } else { "" })} onclick={
+                        let show_archive = show_archive.clone();
+                        let show_broken = show_broken.clone();
+                        move |_| {
+                            show_broken.set(!*show_broken);
+                            show_archive.set(false);
+                        }
+                    }>
+                        {"Broken links"}
+                        <span class="count">{broken_count}</span>
+                    </button>
+                    if let Some(progress) = &*link_check_progress {
+                        <div class="check-progress" title="Checking all the links">
+                            <progress max={progress.total.to_string()} value={progress.checked.to_string()}></progress>
+                            <span class="count">{format!("{}/{}", progress.checked, progress.total)}</span>
+                        </div>
+                    } else {
+                        <button class="check-button" title="Check whether the links still work" onclick={
+                            let link_check_progress = link_check_progress.clone();
+                            let display_error_data = display_error_data.clone();
+                            let popup_box_state = popup_box_state.clone();
+                            move |_| {
+                                let link_check_progress = link_check_progress.clone();
+                                let display_error_data = display_error_data.clone();
+                                let popup_box_state = popup_box_state.clone();
+
+                                spawn_local(async move {
+                                    let result = check_links().await.unwrap().as_string().unwrap();
+
+                                    if let Ok(total) = string_to_struct::<usize>(&result) {
+                                        // it is `0` if the links are already being checked, then the progress comes from the backend
+                                        if total > 0 {
+                                            link_check_progress.set(Some(CheckProgress {
+                                                checked: 0,
+                                                total,
+                                                links: Vec::new(),
+                                            }));
+                                        }
+                                    } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                                        display_error_data.set(Some(DisplayErrorInnerData {
+                                            class: DisplayErrorClass::Error,
+                                            error_reporter,
+                                            options_buttons: None,
+                                            options_message: None,
+                                        }));
+                                        popup_box_state.set(PopupBox::DisplayError);
+                                    } else {
+                                        console_error!(result);
+                                    }
+                                });
+                            }
+                        }>{"Check links"}</button>
+                    }
+                </div>
+
+                <div class="divider">
+                    <button class="activity-button" title="Undo with Ctrl+Z and redo with Ctrl+Shift+Z" onclick={
+                        let popup_box_state = popup_box_state.clone();
+                        move |_| {
+                            popup_box_state.set(PopupBox::Activity);
+                        }
+                    }>{"Activity"}</button>
+                </div>
+
+                <div class="divider">
+                    <button class="trash-button" onclick={
+                        let popup_box_state = popup_box_state.clone();
+                        move |_| {
+                            popup_box_state.set(PopupBox::Trash);
+                        }
+                    }>{"Trash"}</button>
+                </div>
+
+                <div class="divider">
+                    <button class="settings-button" onclick={
+                        move |_| {
+                            popup_box_state.set(PopupBox::EditSettings);
+                        }
+                    }>{"Settings"}</button>
+                </div>
             </div>
 
 
             // IN THE FUTURE
             // <p>{"Show full information"}</p>
-            // <p>{"Show completed links"}</p>
 
             // <button>{"Create a hidden link"}</button>
             // <button>{"Create a hidden tag"}</button>
@@ -67,6 +183,7 @@ pub fn sidebar() -> Html {
                 5. The hidden link's url, title, etc. will be encrypted (with the password given by the user). And the password will be hashed.
             */
 
+            <SavedSearches />
             <Filter />
         </div>
     }
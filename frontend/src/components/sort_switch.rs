@@ -0,0 +1,63 @@
+use crate::*;
+use dsaver_core::settings::Settings;
+
+/// Switch for choosing how the links are sorted
+///
+/// The chosen value is stored in the [`SortOrderState`] state and saved into the settings, so it is remembered when the app is opened again.
+///
+/// With [`SortOrder::Manual`] the user can drag the links to reorder them, see [`DisplayLinks`].
+#[function_component(SortSwitch)]
+pub fn sort_switch() -> Html {
+    let sort_order = use_context::<SortOrderState>().unwrap().0;
+
+    html! {
+        <div class="group-by sort-order">
+            <span class="label">{"Sort by"}</span>
+            {
+                SortOrder::get_vec().into_iter().map(|value| {
+                    let sort_order = sort_order.clone();
+
+                    html! {
+                        <button
+                            class={classes!("option", if *sort_order == value { "selected" } else { "" })}
+                            onclick={move |_| {
+                                sort_order.set(value);
+                                save_sort_order(value);
+                            }}
+                        >
+                            {value.to_string()}
+                        </button>
+                    }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
+
+/// Save the `sort_order` into the settings, keeping the other settings
+pub fn save_sort_order(sort_order: SortOrder) {
+    spawn_local(async move {
+        let result = get_settings().await.unwrap().as_string().unwrap();
+
+        let settings = match string_to_struct::<Settings>(&result) {
+            Ok(settings) => Settings {
+                sort_order,
+                ..settings
+            },
+            Err(_) => {
+                console_error!(result);
+                return;
+            }
+        };
+
+        let result = update_settings(struct_to_string(&settings).unwrap())
+            .await
+            .unwrap()
+            .as_string()
+            .unwrap();
+
+        if string_to_struct::<Settings>(&result).is_err() {
+            console_error!(result);
+        }
+    });
+}
@@ -1,80 +1,44 @@
 use crate::*;
+use dsaver_core::tag::{is_within, tag_tree, TagInfo, TagNode};
 
-/// Show all tags from the links_state
+/// Show all tags from the links_state as a tree
 ///
-/// If user clicks any of these tag elements, then only those links will be displayed wich link.tags is that clicked tag.
+/// Nested tags like `dev/rust/async` are shown inside their parent tags. Click the arrow of a tag to show or hide its child tags.
+///
+/// If user clicks any of these tag elements, then only those links will be displayed which have that tag or one of its child tags. Several tags can be selected, and the button next to the title chooses whether the links must have any or all of them.
+///
+/// Click a selected tag again to hide the links which have it, and once more to unselect it.
+///
+/// Next to every tag is shown how many of its links (including the links of its child tags) are complete, and a button to edit the tag (see [`EditTag`]). The tags are shown with the colors and icons of their [`TagInfosState`].
 #[function_component(Tags)]
 pub fn tags() -> Html {
-    let links_tags = use_context::<LinksTagsState>().unwrap().0;
-    let displayed_tags = use_context::<DisplayedTagsState>().unwrap().0;
+    let links = use_context::<LinksState>().unwrap().0;
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
 
-    // which browser is selected currently
-    let clicked_tag = use_state(|| None);
+    let tree = tag_tree(&links);
+
+    let toggle_mode = {
+        let link_filter = link_filter.clone();
+        move |_| {
+            let mut filter = (*link_filter).clone();
+            filter.tags_mode = toggle_match_mode(filter.tags_mode);
+            link_filter.set(filter);
+        }
+    };
 
     html! {
         <div class="filter-tags filterable">
-            <h1 class="title">{"Tags"}</h1>
+            <div class="title-bar">
+                <h1 class="title">{"Tags"}</h1>
+                <button class="match-mode" title="Whether the links must have any or all of the selected tags" onclick={toggle_mode}>
+                    {match_mode_text(link_filter.tags_mode)}
+                </button>
+            </div>
             <div class="tags elements">
             {
-                (*links_tags).iter().map(|(tag, count)| {
+                tree.into_iter().map(|node| {
                     html! {
-                        <p class={classes!(
-                            "tag",
-                            "element",
-                            if let Some(ctag) = &*clicked_tag {
-                                if ctag == tag {
-                                    "clicked"
-                                } else {
-                                    ""
-                                }
-                            } else {
-                                ""
-                            }
-                        )} onclick={
-                            let tag = tag.clone();
-                            let displayed_tags = displayed_tags.clone();
-                            let clicked_tag = clicked_tag.clone();
-                            let links_tags = links_tags.clone();
-
-                            move |_| {
-                                // selected tag
-                                // the tag is selected will be store here
-                                // after selection this variable will be used to set the state `displayed_tags`
-                                // if no tag is selected then all tags will be displayed
-                                let mut old_displayed_tags: Vec<String> = (*links_tags).clone().into_keys().collect();
-
-                                // check if the user clicked on the same tag or not
-                                if let Some(ctag) = &*clicked_tag {
-                                    if ctag == &tag {
-                                        // user has clicked the same tag.
-                                        // which means that the user wants to unselect the tag
-                                        // show all tags (by default)
-                                        // change the state to be None
-                                        clicked_tag.set(None);
-                                    } else {
-                                        // user has clicked different (not previously clicked one) tag.
-                                        // we need to hide other tag
-                                        // remove all tags except the clicked one
-                                        old_displayed_tags.retain(|old_tag| old_tag == &tag);
-                                        // change the state to be this tag
-                                        clicked_tag.set(Some(tag.clone()));
-                                    }
-                                } else {
-                                    // user has clicked this tag
-                                    // we need to hide other tags
-                                    // remove all tags except the clicked one
-                                    old_displayed_tags.retain(|old_tag| old_tag == &tag);
-                                    // change the state to be this tag
-                                    clicked_tag.set(Some(tag.clone()));
-                                }
-
-                                // set the `old_displayed_tags` to the `displayed_tags` state
-                                displayed_tags.set(old_displayed_tags);
-                            }
-                        }>
-                            {tag}
-                            <span class="count">{count}</span>
-                        </p>
+                        <TagItem key={node.path.clone()} {node} />
                     }
                 }).collect::<Html>()
             }
@@ -82,3 +46,137 @@ pub fn tags() -> Html {
         </div>
     }
 }
+
+/// Props for the [`TagItem`] component.
+#[derive(Properties, PartialEq)]
+struct TagItemProps {
+    /// The tag and its child tags
+    node: TagNode,
+}
+
+/// A tag inside [`Tags`], followed by its child tags when it is expanded
+///
+/// It is expanded at first if one of its child tags is selected.
+#[function_component(TagItem)]
+fn tag_item(props: &TagItemProps) -> Html {
+    let node = &props.node;
+
+    let link_filter = use_context::<LinkFilterState>().unwrap().0;
+    let editing_tag = use_context::<EditingTagState>().unwrap().0;
+    let tag_infos = use_context::<TagInfosState>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+
+    let info = tag_infos.get(&node.path);
+
+    let expanded = use_state(|| {
+        link_filter
+            .tags
+            .iter()
+            .flatten()
+            .chain(&link_filter.excluded_tags)
+            .any(|tag| *tag != node.path && is_within(tag, &node.path))
+    });
+
+    let selection = Selection::of(&link_filter.tags, &link_filter.excluded_tags, &node.path);
+
+    let toggle = {
+        let expanded = expanded.clone();
+
+        move |event: MouseEvent| {
+            // don't select the tag
+            event.stop_propagation();
+            expanded.set(!*expanded);
+        }
+    };
+
+    let edit = {
+        let path = node.path.clone();
+
+        move |event: MouseEvent| {
+            // don't select the tag
+            event.stop_propagation();
+            editing_tag.set(Some(path.clone()));
+            popup_box_state.set(PopupBox::EditTag);
+        }
+    };
+
+    html! {
+        <div class="tag-node">
+            <p
+                class={classes!("tag", "element", selection.class())}
+                // the color of a selected tag shows the selection instead
+                style={tag_style(info).filter(|_| selection == Selection::None).unwrap_or_default()}
+                title={match info.and_then(|info| info.description.as_ref()) {
+                    Some(description) => format!("{}\n{}", node.path, description),
+                    None => node.path.clone(),
+                }}
+                onclick={
+                    let path = node.path.clone();
+                    let link_filter = link_filter.clone();
+
+                    move |_| {
+                        let mut filter = (*link_filter).clone();
+                        toggle_selection(&mut filter.tags, &mut filter.excluded_tags, &path);
+                        link_filter.set(filter);
+                    }
+                }
+            >
+                if !node.children.is_empty() {
+                    <button class="toggle" title="Show or hide the child tags" onclick={toggle}>
+                        {if *expanded { "▾" } else { "▸" }}
+                    </button>
+                }
+                {tag_label(&node.name, info)}
+                <span class="count" title={format!("{} of {} links are complete", node.complete, node.count)}>
+                    {format!("{}/{}", node.complete, node.count)}
+                </span>
+                <button class="edit" title="Rename, merge or delete the tag, or change its color, icon and description" onclick={edit}>{"Edit"}</button>
+            </p>
+
+            if *expanded {
+                <div class="children">
+                {
+                    node.children.iter().map(|child| {
+                        html! {
+                            <TagItem key={child.path.clone()} node={child.clone()} />
+                        }
+                    }).collect::<Html>()
+                }
+                </div>
+            }
+        </div>
+    }
+}
+
+/// The text of a tag, after the icon from its [`TagInfo`]
+pub fn tag_label(tag: &str, info: Option<&TagInfo>) -> String {
+    match info.and_then(|info| info.icon.as_ref()) {
+        Some(icon) => format!("{icon} {tag}"),
+        None => tag.to_string(),
+    }
+}
+
+/// The inline style of a tag with the color from its [`TagInfo`]. The text is black on light colors and white on dark colors.
+///
+/// It is `None` if the tag has no color.
+pub fn tag_style(info: Option<&TagInfo>) -> Option<String> {
+    let color = info?.color.as_ref()?;
+    let hex = color.trim_start_matches('#');
+
+    // `#fa8` is the same as `#ffaa88`
+    let channel = |i: usize| {
+        let part = if hex.len() == 3 {
+            hex.get(i..=i).map(|part| part.repeat(2))
+        } else {
+            hex.get(i * 2..i * 2 + 2).map(String::from)
+        };
+
+        part.and_then(|part| u8::from_str_radix(&part, 16).ok())
+            .map(f32::from)
+            .unwrap_or_default()
+    };
+    let brightness = 0.299 * channel(0) + 0.587 * channel(1) + 0.114 * channel(2);
+    let text = if brightness > 150.0 { "black" } else { "white" };
+
+    Some(format!("background-color: {color}; color: {text};"))
+}
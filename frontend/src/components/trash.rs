@@ -0,0 +1,203 @@
+use crate::*;
+use chrono::Local;
+use dsaver_core::settings::Settings;
+use dsaver_core::storage::TrashedLink;
+
+/// Restore the deleted links or delete them forever
+///
+/// This component is a popup box and it will appear if you set the [`PopupBox`] to [`PopupBox::Trash`].
+///
+/// It shows all the links inside the trash (the last deleted first). After restoring a link, it will be added to the [`LinksState`] state.
+///
+/// The links are deleted forever after the number of days chosen in the [`EditSettings`].
+#[function_component(Trash)]
+pub fn trash() -> Html {
+    let links = use_context::<LinksState>().unwrap().0;
+    let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
+    let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
+
+    // `None` until the trash is loaded
+    let trash = use_state(|| None::<Vec<TrashedLink>>);
+    let retention_days = use_state(|| None::<u32>);
+
+    {
+        // Load the trash and the settings when the component is mounted
+        let trash = trash.clone();
+        let retention_days = retention_days.clone();
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let result = get_trash().await.unwrap().as_string().unwrap();
+
+                    if let Ok(list) = string_to_struct::<Vec<TrashedLink>>(&result) {
+                        trash.set(Some(list));
+                    } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                        show_trash_error(error_reporter, display_error_data, popup_box_state);
+                        return;
+                    } else {
+                        console_error!(result);
+                    }
+
+                    let result = get_settings().await.unwrap().as_string().unwrap();
+
+                    if let Ok(settings) = string_to_struct::<Settings>(&result) {
+                        retention_days.set(Some(settings.trash_retention_days));
+                    } else {
+                        console_error!(result);
+                    }
+                });
+
+                || ()
+            },
+            (),
+        );
+    }
+
+    let empty_button = {
+        let trash = trash.clone();
+        let display_error_data = display_error_data.clone();
+        let popup_box_state = popup_box_state.clone();
+
+        html! {
+            <button class="button empty" onclick={
+                move |_| {
+                    let trash = trash.clone();
+                    let display_error_data = display_error_data.clone();
+                    let popup_box_state = popup_box_state.clone();
+
+                    spawn_local(async move {
+                        let result = empty_trash().await.unwrap().as_string().unwrap();
+
+                        if string_to_struct::<Vec<TrashedLink>>(&result).is_ok() {
+                            trash.set(Some(Vec::new()));
+
+                            console_log!("Successfully emptied the trash");
+                        } else if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                            show_trash_error(error_reporter, display_error_data, popup_box_state);
+                        } else {
+                            console_error!(result);
+                        }
+                    });
+                }
+            }>{"Empty trash"}</button>
+        }
+    };
+
+    html! {
+        <Popup title="Trash" id="trash">
+            <div class="trash">
+            {
+                match &*trash {
+                    None => html! {
+                        <p class="message">{"Loading trash..."}</p>
+                    },
+                    Some(list) if list.is_empty() => html! {
+                        <p class="message">{"The trash is empty. Deleted links are kept here until you delete them forever."}</p>
+                    },
+                    Some(list) => html! {
+                        <>
+                        <div class="buttons">{empty_button}</div>
+                        {
+                            list.iter().map(|trashed| {
+                                let id = trashed.link.id.unwrap();
+
+                                // the link is restored into the `links` state, or removed forever
+                                let button = |text: &'static str, restore: bool| {
+                                    let links = links.clone();
+                                    let trash = trash.clone();
+                                    let display_error_data = display_error_data.clone();
+                                    let popup_box_state = popup_box_state.clone();
+
+                                    html! {
+                                        <button class={classes!("button", if restore { "restore" } else { "purge" })} onclick={
+                                            move |_| {
+                                                let links = links.clone();
+                                                let trash = trash.clone();
+                                                let display_error_data = display_error_data.clone();
+                                                let popup_box_state = popup_box_state.clone();
+
+                                                spawn_local(async move {
+                                                    let result = if restore {
+                                                        restore_link(id.to_string()).await
+                                                    } else {
+                                                        purge_link(id.to_string()).await
+                                                    };
+                                                    let result = result.unwrap().as_string().unwrap();
+
+                                                    if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
+                                                        show_trash_error(error_reporter, display_error_data, popup_box_state);
+                                                        return;
+                                                    }
+
+                                                    if restore {
+                                                        match string_to_struct::<Link>(&result) {
+                                                            Ok(link) => {
+                                                                let mut new_links = (*links).clone();
+                                                                new_links.push(link);
+                                                                links.set(new_links);
+                                                            }
+                                                            Err(_) => {
+                                                                console_error!(result);
+                                                                return;
+                                                            }
+                                                        }
+                                                    }
+
+                                                    let mut new_trash = (*trash).clone().unwrap_or_default();
+                                                    new_trash.retain(|trashed| trashed.link.id != Some(id));
+                                                    trash.set(Some(new_trash));
+                                                });
+                                            }
+                                        }>{text}</button>
+                                    }
+                                };
+
+                                let expires = retention_days
+                                    .and_then(|days| trashed.expires_at(days))
+                                    .map(|expires_at| {
+                                        format!(" · deleted forever on {}", expires_at.with_timezone(&Local).format("%d %B %Y"))
+                                    })
+                                    .unwrap_or_default();
+
+                                html! {
+                                    <div class="trashed">
+                                        <div class="info">
+                                            <p class="title">{trashed.link.title.clone().filter(|title| !title.is_empty()).unwrap_or_else(|| trashed.link.url.clone())}</p>
+                                            <p class="date">
+                                                {format!("Deleted on {}", trashed.deleted_at.with_timezone(&Local).format("%d %B %Y, %H:%M"))}
+                                                {expires}
+                                            </p>
+                                        </div>
+
+                                        {button("Restore", true)}
+                                        {button("Delete forever", false)}
+                                    </div>
+                                }
+                            }).collect::<Html>()
+                        }
+                        </>
+                    },
+                }
+            }
+            </div>
+        </Popup>
+    }
+}
+
+/// Show the error in the [`DisplayError`] popup
+fn show_trash_error(
+    error_reporter: ErrorReporter,
+    display_error_data: UseStateHandle<Option<DisplayErrorInnerData>>,
+    popup_box_state: UseStateHandle<PopupBox>,
+) {
+    display_error_data.set(Some(DisplayErrorInnerData {
+        class: DisplayErrorClass::Error,
+        error_reporter,
+        options_buttons: None,
+        options_message: None,
+    }));
+
+    popup_box_state.set(PopupBox::DisplayError);
+}
@@ -28,54 +28,297 @@ pub mod lib_components;
 
 #[wasm_bindgen(module = "/assets/scripts/communicator.js")]
 extern "C" {
-    /// Get data from user's filesystem.
+    /// Get all the links saved by the user.
     ///
-    /// After calling `.await.unwrap().as_string()` it will return `Option<String>`.
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] or [`ErrorReporter`].
     ///
-    /// It will be `None` if the file is not exits or can't read the file or if any error occurs.
+    /// It will be an empty list if the user has not saved any links yet.
     ///
-    /// If it returns `Some` then you can parse it as a [`Vec<Link`] *if the file contains valid data*.
-    #[wasm_bindgen(js_name = getData, catch)]
-    pub async fn get_data() -> Result<JsValue, JsValue>;
+    /// It will be [`ErrorReporter`] if the Rust backend can't read the links.
+    #[wasm_bindgen(js_name = getLinks, catch)]
+    pub async fn get_links() -> Result<JsValue, JsValue>;
 
-    /// Add data in the file system.
+    /// Validate a new link and save it.
     ///
     /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Link`] or [`ErrorReporter`].
     ///
-    /// After calling `.as_string()` it will not return `None`.
-    ///
-    /// The final `String` can be [`Link`] if this successfully adds the data. This link is a new link after validating the old link passed by this function.
+    /// The final `String` can be [`Link`] if this successfully adds the link. This link is a new link after validating the old link passed by this function.
     ///
     /// The final `String` can be [`ErrorReporter`] if the Rust backend sends [`Err(ErrorReporter)`];
     ///
-    /// You can also use [`store_data`] function to add the data. But if you need to validate and to add `automatic` infos like `title`, status codes, you should use this function because it calls the Rust backend which will fetch informations. *Behind the scene it uses [`store_data`] to store the links.*
+    /// You can also use [`insert_link`] function to add the link. But if you need to validate and to add `automatic` infos like `title`, status codes, you should use this function because it calls the Rust backend which will fetch informations.
+    ///
+    /// # Arguments
+    ///
+    /// `link` - it is a JSON string which contains [`Link`]. This is the new link the user wants to create
+    #[wasm_bindgen(js_name = addLink, catch)]
+    pub async fn add_link(link: String) -> Result<JsValue, JsValue>;
+
+    /// Save a new link without validating it.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Link`] or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `link` - it is a JSON string which contains [`Link`].
+    #[wasm_bindgen(js_name = insertLink, catch)]
+    pub async fn insert_link(link: String) -> Result<JsValue, JsValue>;
+
+    /// Replace the saved link which has the same `id` as the given link.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Link`] or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `link` - it is a JSON string which contains [`Link`].
+    #[wasm_bindgen(js_name = updateLink, catch)]
+    pub async fn update_link(link: String) -> Result<JsValue, JsValue>;
+
+    /// Move the saved link with the given `id` into the trash.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Link`] (the deleted link) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = deleteLink, catch)]
+    pub async fn delete_link(id: String) -> Result<JsValue, JsValue>;
+
+    /// Save the order of the links chosen by the user. The links are shown in this order with [`SortOrder::Manual`].
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `ids` - it is a JSON string which contains `Vec<Uuid>`, the ids of the links in the new order.
+    #[wasm_bindgen(js_name = reorderLinks, catch)]
+    pub async fn reorder_links(ids: String) -> Result<JsValue, JsValue>;
+
+    /// Undo the last change of the user, e.g. adding, editing or deleting a link.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] (all links after undoing) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = undo, catch)]
+    pub async fn undo() -> Result<JsValue, JsValue>;
+
+    /// Make the last undone change again.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] (all links after redoing) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = redo, catch)]
+    pub async fn redo() -> Result<JsValue, JsValue>;
+
+    /// Get the activity log of the user. The newest entry will be first.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<JournalEntry>`](dsaver_core::journal::JournalEntry) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = getActivity, catch)]
+    pub async fn get_activity() -> Result<JsValue, JsValue>;
+
+    /// Get all the links inside the trash. The last deleted link will be first.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<TrashedLink>`](dsaver_core::storage::TrashedLink) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = getTrash, catch)]
+    pub async fn get_trash() -> Result<JsValue, JsValue>;
+
+    /// Move the link with the given `id` out of the trash.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Link`] (the restored link) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = restoreLink, catch)]
+    pub async fn restore_link(id: String) -> Result<JsValue, JsValue>;
+
+    /// Delete forever the link with the given `id` from the trash.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`TrashedLink`](dsaver_core::storage::TrashedLink) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = purgeLink, catch)]
+    pub async fn purge_link(id: String) -> Result<JsValue, JsValue>;
+
+    /// Delete forever all the links inside the trash.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<TrashedLink>`](dsaver_core::storage::TrashedLink) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = emptyTrash, catch)]
+    pub async fn empty_trash() -> Result<JsValue, JsValue>;
+
+    /// Rename the tag `from` to `to` in every link.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] (all links after renaming) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = renameTag, catch)]
+    pub async fn rename_tag(from: String, to: String) -> Result<JsValue, JsValue>;
+
+    /// Replace the tag `from` with the tag `into` in every link.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] (all links after merging) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = mergeTags, catch)]
+    pub async fn merge_tags(from: String, into: String) -> Result<JsValue, JsValue>;
+
+    /// Remove the `tag` from every link.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] (all links after removing the tag) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = deleteTag, catch)]
+    pub async fn delete_tag(tag: String) -> Result<JsValue, JsValue>;
+
+    /// Get the colors, icons and descriptions of the tags, sorted by the tag.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<TagInfo>`](dsaver_core::tag::TagInfo) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = getTagInfos, catch)]
+    pub async fn get_tag_infos() -> Result<JsValue, JsValue>;
+
+    /// Save the color, icon and description of a tag. If it has none of them, then the old info of the tag is removed.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`TagInfo`](dsaver_core::tag::TagInfo) or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `info` - it is a JSON string which contains [`TagInfo`](dsaver_core::tag::TagInfo).
+    #[wasm_bindgen(js_name = saveTagInfo, catch)]
+    pub async fn save_tag_info(info: String) -> Result<JsValue, JsValue>;
+
+    /// Check every saved link in the background, see [`on_link_check`] for the progress.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`usize`] or [`ErrorReporter`].
+    ///
+    /// The number is how many links will be checked. It is `0` if the links are already being checked.
+    #[wasm_bindgen(js_name = checkLinks, catch)]
+    pub async fn check_links() -> Result<JsValue, JsValue>;
+
+    /// Call `progress` with a JSON string of [`CheckProgress`](dsaver_core::check::CheckProgress) whenever the backend saves the checks, and `done` with the number of checked links when all the links are checked.
+    ///
+    /// It returns a callback function which you can call to remove the event listeners when you don't need them anymore
+    #[wasm_bindgen(js_name = onLinkCheck)]
+    pub fn on_link_check(progress: &Function, done: &Function) -> Function;
+
+    /// Fetch the webpage of the saved link with the given `id` again, to see what is changed. Nothing is saved.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`LinkRefresh`](dsaver_core::refresh::LinkRefresh) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = refreshLink, catch)]
+    pub async fn refresh_link(id: String) -> Result<JsValue, JsValue>;
+
+    /// Save the changes of a link returned by [`refresh_link`].
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Link`] (the updated link) or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `refresh` - it is a JSON string which contains [`LinkRefresh`](dsaver_core::refresh::LinkRefresh).
+    #[wasm_bindgen(js_name = applyRefresh, catch)]
+    pub async fn apply_refresh(refresh: String) -> Result<JsValue, JsValue>;
+
+    /// Get the url of the cached image with the `key` (see [`image_key`](dsaver_core::storage::image_key)). Use it as the `src` of an `<img>`.
+    #[wasm_bindgen(js_name = imageSrc)]
+    pub fn image_src(key: &str) -> String;
+
+    /// Get all backups of the links. The newest backup will be first.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Backup>`](dsaver_core::storage::Backup) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = listBackups, catch)]
+    pub async fn list_backups() -> Result<JsValue, JsValue>;
+
+    /// Replace all links with the links of the backup `name`.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] (the restored links) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = restoreBackup, catch)]
+    pub async fn restore_backup(name: String) -> Result<JsValue, JsValue>;
+
+    /// Save the links recovered from the corrupted file.
+    ///
+    /// Call it after [`get_links`] returns an [`ErrorReporter`] with [`ErrorType::DataRecovered`].
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = keepRecoveredLinks, catch)]
+    pub async fn keep_recovered_links() -> Result<JsValue, JsValue>;
+
+    /// Open the copy of the corrupted file with the default program of the OS.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()`, if it can be parsed as a `ErrorReporter` then it means an error occurred while opening the file.
+    #[wasm_bindgen(js_name = openQuarantinedFile, catch)]
+    pub async fn open_quarantined_file() -> Result<JsValue, JsValue>;
+
+    /// Get the links which match the filter, in the order they were added.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Link>`] or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `filter` - it is a JSON string which contains [`LinkFilter`].
+    #[wasm_bindgen(js_name = queryLinks, catch)]
+    pub async fn query_links(filter: String) -> Result<JsValue, JsValue>;
+
+    /// Get the ids of the links which match the filter, in the order they were added.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<Uuid>`] or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `filter` - it is a JSON string which contains [`LinkFilter`].
+    #[wasm_bindgen(js_name = queryLinkIds, catch)]
+    pub async fn query_link_ids(filter: String) -> Result<JsValue, JsValue>;
+
+    /// Search the links which match the query and the filter. The best match will be first.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<SearchHit>`](dsaver_core::search::SearchHit) or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `query` - the text typed by the user.
+    ///
+    /// `filter` - it is a JSON string which contains [`LinkFilter`].
+    #[wasm_bindgen(js_name = searchLinks, catch)]
+    pub async fn search_links(query: String, filter: String) -> Result<JsValue, JsValue>;
+
+    /// Get all the searches saved by the user, in the order they were added.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Vec<SavedSearch>`] or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = getSavedSearches, catch)]
+    pub async fn get_saved_searches() -> Result<JsValue, JsValue>;
+
+    /// Save a new search, or replace the saved search which has the same `id`.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`SavedSearch`] or [`ErrorReporter`].
     ///
     /// # Arguments
     ///
-    /// `full_data` - it is a JSON string which contains [`Vec<Link`]. This is the list of links the user currently have
+    /// `search` - it is a JSON string which contains [`SavedSearch`].
+    #[wasm_bindgen(js_name = saveSearch, catch)]
+    pub async fn save_search(search: String) -> Result<JsValue, JsValue>;
+
+    /// Delete the saved search with the given `id`.
     ///
-    /// `data` - it is a JSON string which contains [`Link`]. This is the new link the user wants to create
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`SavedSearch`] (the deleted search) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = deleteSearch, catch)]
+    pub async fn delete_search(id: String) -> Result<JsValue, JsValue>;
+
+    /// Get the settings of the app.
     ///
-    #[wasm_bindgen(js_name = addData, catch)]
-    pub async fn add_data(full_data: String, data: String) -> Result<JsValue, JsValue>; // Vec<Link>, Link
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Settings`](dsaver_core::settings::Settings) or [`ErrorReporter`].
+    #[wasm_bindgen(js_name = getSettings, catch)]
+    pub async fn get_settings() -> Result<JsValue, JsValue>;
 
-    /// Store data in user's filesystem
+    /// Save the settings of the app. The storage can't be changed with it, use [`migrate_storage`] instead.
     ///
-    /// After calling `.await.unwrap().as_string()` it will return `Option<String>`.
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Settings`](dsaver_core::settings::Settings) or [`ErrorReporter`].
     ///
-    /// If this function succcessfully stores the data, then it will return `None`.
+    /// # Arguments
     ///
-    /// If any errror occurs it will return the error inside the `String`.
-    #[wasm_bindgen(js_name = storeData, catch)]
-    pub async fn store_data(full_data: String) -> Result<JsValue, JsValue>;
+    /// `settings` - it is a JSON string which contains [`Settings`](dsaver_core::settings::Settings).
+    #[wasm_bindgen(js_name = updateSettings, catch)]
+    pub async fn update_settings(settings: String) -> Result<JsValue, JsValue>;
+
+    /// Copy all links into another storage and use it from now on.
+    ///
+    /// After calling `.await.unwrap().as_string().unwrap()` will return a `String` which can be parse as [`Settings`](dsaver_core::settings::Settings) or [`ErrorReporter`].
+    ///
+    /// # Arguments
+    ///
+    /// `backend` - it is a JSON string which contains [`StorageBackend`](dsaver_core::storage::StorageBackend).
+    #[wasm_bindgen(js_name = migrateStorage, catch)]
+    pub async fn migrate_storage(backend: String) -> Result<JsValue, JsValue>;
 
     /// Open browser on the specified browser
     ///
-    /// After calling let result = `.await.unwrap().as_string().unwrap()`, if it can be parsed as a `ErrorReporter` then it means an error occurred while opening the browser. Else it means successfully opened the browser.
+    /// After calling let result = `.await.unwrap().as_string().unwrap()`, if it can be parsed as a `ErrorReporter` then it means an error occurred while opening the browser. Else it means successfully opened the browser, and it can be parsed as `Option<Link>` (the opened link after updating it).
     ///
     /// The argument `browser` must be a json of [`Browser`]
+    ///
+    /// `id` - id of the saved [`Link`] which is being opened. Its `last_opened_at` will be updated (and it may be marked as complete, see [`Settings::complete_on_open`](dsaver_core::settings::Settings::complete_on_open)). Use `None` if the link is not saved.
     #[wasm_bindgen(js_name = openBrowser, catch)]
-    pub async fn open_browser(path: String, browser: String) -> Result<JsValue, JsValue>;
+    pub async fn open_browser(
+        path: String,
+        browser: String,
+        id: Option<String>,
+    ) -> Result<JsValue, JsValue>;
 
     /// Generate some random links on debug/dev mode
     ///
@@ -122,6 +365,12 @@ extern "C" {
     #[wasm_bindgen(js_name = "ifNotClicked")]
     pub fn if_not_clicked(element_id: &str, what_to_do: &Function) -> Function;
 
+    /// Call `undo` when the user presses Ctrl+Z and `redo` when the user presses Ctrl+Shift+Z (or Ctrl+Y), except inside a text field
+    ///
+    /// It returns a callback function which you can call to remove the event listener when you don't need it anymore
+    #[wasm_bindgen(js_name = "onUndoRedo")]
+    pub fn on_undo_redo(undo: &Function, redo: &Function) -> Function;
+
     /// Decrease the opacity of the element
     #[wasm_bindgen(js_name = "downOpacity")]
     pub fn down_opacity(element_id: &str);
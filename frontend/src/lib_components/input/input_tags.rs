@@ -42,7 +42,7 @@ pub enum TagsType {
 ///
 /// It is a child component of [`InputWrapper`].
 ///
-/// It makes a button or text which looks like a tag for you.
+/// It makes a button or text which looks like a tag for you. If a [`TagInfosState`] is provided, the tags are shown with their colors and icons.
 ///
 /// See the [`Form`] component for more information.
 ///
@@ -56,6 +56,9 @@ pub fn input_tags(props: &InputTagsProps) -> Html {
         label_text,
     } = (*props).clone();
 
+    // the colors and icons of the tags, if the app provides them
+    let tag_infos = use_context::<TagInfosState>().map(|state| state.0);
+
     html! {
         <>
         if tags_values.is_empty() {
@@ -65,20 +68,24 @@ pub fn input_tags(props: &InputTagsProps) -> Html {
                 <p class="title">{label_text}</p>
                 {
                     tags_values.into_iter().map(move |tag| {
+                        let info = tag_infos.as_ref().and_then(|infos| infos.get(&tag));
+                        let style = tag_style(info).unwrap_or_default();
+                        let label = tag_label(&tag, info);
+
                         match tag_type.clone() {
                             TagsType::Text => {
                                 html! {
-                                    <span class="tag">{tag}</span>
+                                    <span class="tag" {style}>{label}</span>
                                 }
                             }
                             TagsType::Button(onclick) => {
                                 html! {
-                                    <button class="tag button-tag" onclick={
+                                    <button class="tag button-tag" {style} onclick={
                                         let tag = tag.clone();
                                         move |event| {
                                             onclick.emit((event, tag.clone()))
                                         }
-                                    }>{tag}</button>
+                                    }>{label}</button>
                                 }
                             }
                         }
@@ -5,9 +5,11 @@ pub mod input;
 pub mod open;
 pub mod popup;
 pub mod select;
+pub mod virtual_list;
 
 pub use form::*;
 pub use input::*;
 pub use open::*;
 pub use popup::*;
 pub use select::*;
+pub use virtual_list::*;
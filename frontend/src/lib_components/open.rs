@@ -20,6 +20,11 @@ pub struct OpenProps {
     /// Because the user may not have the browser you specified installed.
     #[prop_or_default]
     pub browser: Browser,
+    /// id of the saved [`Link`] which is being opened.
+    ///
+    /// The backend will remember when the link was opened, and the link will be updated inside the [`LinksState`] state.
+    #[prop_or_default]
+    pub link_id: Option<Uuid>,
     /// Children of the link.
     ///
     /// It can be a text or any html element.
@@ -43,9 +48,11 @@ pub fn open(props: &OpenProps) -> Html {
         class,
         id,
         browser,
+        link_id,
         children,
     } = (*props).clone();
 
+    let links = use_context::<LinksState>().unwrap().0;
     let display_error_data = use_context::<DisplayErrorData>().unwrap().0;
     let popup_box_state = use_context::<PopupBoxState>().unwrap().0;
 
@@ -55,16 +62,21 @@ pub fn open(props: &OpenProps) -> Html {
         move |_| {
             let href = href.clone();
             let browser = browser.clone();
+            let links = links.clone();
             let display_error_data = display_error_data.clone();
             let popup_box_state = popup_box_state.clone();
 
             spawn_local(async move {
                 // Call the rust backend's `open_browser` function to open the link in the browser
-                let result = open_browser(href, struct_to_string(&browser).unwrap())
-                    .await
-                    .unwrap()
-                    .as_string()
-                    .unwrap();
+                let result = open_browser(
+                    href,
+                    struct_to_string(&browser).unwrap(),
+                    link_id.map(|id| id.to_string()),
+                )
+                .await
+                .unwrap()
+                .as_string()
+                .unwrap();
 
                 // If the result can be parsed as an `ErrorReporter`, then it is an error
                 if let Ok(error_reporter) = string_to_struct::<ErrorReporter>(&result) {
@@ -78,6 +90,11 @@ pub fn open(props: &OpenProps) -> Html {
 
                     popup_box_state.set(PopupBox::DisplayError);
                 } else {
+                    // the saved link is updated by the backend, e.g. its `last_opened_at`
+                    if let Ok(Some(link)) = string_to_struct::<Option<Link>>(&result) {
+                        replace_link(&links, link);
+                    }
+
                     console_log!("Successfully opened");
                 }
             });
@@ -0,0 +1,158 @@
+use crate::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use web_sys::{Element, HtmlElement};
+
+/// Props for the [`VirtualList`] component.
+#[derive(Properties, PartialEq)]
+pub struct VirtualListProps {
+    /// How many rows the list has.
+    pub len: usize,
+    /// Render the row of the given index.
+    ///
+    /// It is only called for the rows which are visible (and a few rows around them).
+    pub render: Callback<usize, Html>,
+    /// Height of a row in pixels, used until the row is rendered and its real height is known.
+    #[prop_or(60.0)]
+    pub row_height: f64,
+    /// How many rows are rendered above and below the visible rows, so that nothing is empty while scrolling.
+    #[prop_or(5)]
+    pub overscan: usize,
+    /// class of the list.
+    #[prop_or_default]
+    pub class: String,
+    /// id of the list.
+    #[prop_or_default]
+    pub id: String,
+}
+
+/// A scrollable list which only renders the rows that are visible
+///
+/// Use it for long lists (e.g. thousands of links), because rendering all rows at once makes the app slow. The rows above and below the visible ones are replaced with empty space of the same height, so the scrollbar still looks like the whole list is there.
+///
+/// The rows can have different heights. The height of every rendered row is measured and remembered, and [`VirtualListProps::row_height`] is used for the rows which have not been rendered yet.
+///
+/// The list scrolls by itself, so it needs a height (e.g. `flex: 1` inside a flex column, or a fixed height) in the styles.
+///
+/// `props` - [`VirtualListProps`]
+#[function_component(VirtualList)]
+pub fn virtual_list(props: &VirtualListProps) -> Html {
+    let VirtualListProps {
+        len,
+        render,
+        row_height,
+        overscan,
+        class,
+        id,
+    } = props;
+    let (len, row_height, overscan) = (*len, *row_height, *overscan);
+
+    let container = use_node_ref();
+    // (scroll position, height) of the visible area
+    let viewport = use_state(|| (0.0, 0.0));
+    // measured heights of the rows, by their index
+    let heights: Rc<RefCell<HashMap<usize, f64>>> = use_mut_ref(HashMap::new);
+    // the rendered rows, so that they can be measured after rendering
+    let rendered: Rc<RefCell<Vec<(usize, NodeRef)>>> = use_mut_ref(Vec::new);
+    // changed after measuring, to render again with the real heights
+    let measured = use_state(|| 0_u32);
+
+    // top of every row, and the end of the last row
+    let mut offsets = Vec::with_capacity(len + 1);
+    let mut offset = 0.0;
+    {
+        let heights = heights.borrow();
+        for index in 0..len {
+            offsets.push(offset);
+            offset += heights.get(&index).copied().unwrap_or(row_height);
+        }
+    }
+    offsets.push(offset);
+
+    let (scroll_top, visible_height) = *viewport;
+
+    // the first and the last visible rows
+    let first = offsets[1..].partition_point(|end| *end <= scroll_top);
+    let last = offsets[..len].partition_point(|top| *top < scroll_top + visible_height);
+
+    let start = first.saturating_sub(overscan);
+    let end = (last + overscan).min(len).max(start);
+
+    let rows: Vec<(usize, NodeRef)> = (start..end)
+        .map(|index| (index, NodeRef::default()))
+        .collect();
+    *rendered.borrow_mut() = rows.clone();
+
+    {
+        // measure the rendered rows and the visible area after every render
+        let container = container.clone();
+        let viewport = viewport.clone();
+        let measured = measured.clone();
+        use_effect(move || {
+            let mut changed = false;
+
+            for (index, node_ref) in rendered.borrow().iter() {
+                if let Some(row) = node_ref.cast::<HtmlElement>() {
+                    let height = row.offset_height() as f64;
+                    let old = heights.borrow_mut().insert(*index, height);
+
+                    // ignore tiny differences, otherwise it may render forever
+                    if !matches!(old, Some(old) if (old - height).abs() <= 0.5) {
+                        changed = true;
+                    }
+                }
+            }
+
+            if let Some(container) = container.cast::<Element>() {
+                let size = (
+                    container.scroll_top() as f64,
+                    container.client_height() as f64,
+                );
+
+                if size != *viewport {
+                    viewport.set(size);
+                }
+            }
+
+            if changed {
+                measured.set(measured.wrapping_add(1));
+            }
+
+            || ()
+        });
+    }
+
+    let onscroll = {
+        let container = container.clone();
+        let viewport = viewport.clone();
+        move |_| {
+            if let Some(container) = container.cast::<Element>() {
+                viewport.set((
+                    container.scroll_top() as f64,
+                    container.client_height() as f64,
+                ));
+            }
+        }
+    };
+
+    html! {
+        <div
+            ref={container}
+            class={classes!("virtual-list", class.clone())}
+            id={id.clone()}
+            {onscroll}
+        >
+            <div class="virtual-space" style={format!("height: {}px", offsets[start])} />
+            {
+                rows.into_iter().map(|(index, node_ref)| {
+                    html! {
+                        <div class="virtual-row" key={index} ref={node_ref}>
+                            {render.emit(index)}
+                        </div>
+                    }
+                }).collect::<Html>()
+            }
+            <div class="virtual-space" style={format!("height: {}px", offsets[len] - offsets[end])} />
+        </div>
+    }
+}
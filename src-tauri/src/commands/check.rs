@@ -0,0 +1,211 @@
+use crate::*;
+use curl::easy::Easy;
+use dsaver_core::check::{CheckProgress, CheckQueue, LinkCheck, Next};
+use dsaver_core::check::{DOMAIN_DELAY, MAX_CONCURRENT_CHECKS};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+/// The event which is sent to the frontend with a [`CheckProgress`] while the links are checked, see [`check_links`].
+pub const CHECK_PROGRESS_EVENT: &str = "link-check-progress";
+
+/// The event which is sent to the frontend with the number of checked links when all the links are checked.
+pub const CHECK_DONE_EVENT: &str = "link-check-done";
+
+/// Only this much of a page (in bytes) is downloaded, the status is known before it.
+const MAX_PAGE_SIZE: usize = 64 * 1024;
+
+/// The checks are saved after this many links are checked, or after [`SAVE_INTERVAL`].
+const SAVE_BATCH: usize = 25;
+
+/// How long the checks can wait before they are saved.
+const SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A worker asks the queue again after this time at most, even if it has to wait longer.
+const MAX_WAIT: Duration = Duration::from_millis(200);
+
+/// Whether the links are being checked, so that only one check runs at a time.
+#[derive(Default)]
+pub struct LinkCheckState {
+    running: AtomicBool,
+}
+
+/// Check every saved link in the background, to find the dead links.
+///
+/// [`MAX_CONCURRENT_CHECKS`] links are checked at the same time, but only one link of a website at a time, and the links of the same website wait for [`DOMAIN_DELAY`] between them.
+///
+/// The result of every link is saved in [`Link::check`]. The checks are saved in batches without backing up the links (see [`Storage::save_checks`]), and after every batch a [`CheckProgress`] with the updated links is sent as the [`CHECK_PROGRESS_EVENT`]. When all the links are checked, the [`CHECK_DONE_EVENT`] is sent.
+///
+/// It returns how many links will be checked, or `0` if the links are already being checked.
+///
+/// # Example
+///
+/// ```js
+/// const unlisten = await window.__TAURI__.event.listen("link-check-progress", (event) => {
+///     console.log(`${event.payload.checked}/${event.payload.total}`);
+/// });
+/// const total = await invoke("check_links");
+/// ```
+#[tauri::command]
+pub async fn check_links(
+    app: AppHandle,
+    store: State<'_, StorageState>,
+    checking: State<'_, LinkCheckState>,
+) -> Result<usize, ErrorReporter> {
+    if checking.running.swap(true, Ordering::SeqCst) {
+        return Ok(0);
+    }
+
+    let links = match store.with(|store| store.links()) {
+        Ok(links) => links,
+        Err(err) => {
+            checking.running.store(false, Ordering::SeqCst);
+            return Err(err);
+        }
+    };
+    let total = links.len();
+
+    thread::spawn(move || {
+        // the check is finished even if it panics, so that the links can be checked again
+        let checked =
+            panic::catch_unwind(AssertUnwindSafe(|| check_all(&app, links))).unwrap_or_default();
+
+        app.state::<LinkCheckState>()
+            .running
+            .store(false, Ordering::SeqCst);
+        let _ = app.emit_all(CHECK_DONE_EVENT, checked);
+    });
+
+    Ok(total)
+}
+
+/// Check the `links` with the workers and save the results. It returns how many links are checked.
+fn check_all(app: &AppHandle, links: Vec<Link>) -> usize {
+    let total = links.len();
+    let queue = Arc::new(Mutex::new(CheckQueue::new(links, DOMAIN_DELAY)));
+    let (sender, receiver) = mpsc::channel();
+
+    for _ in 0..MAX_CONCURRENT_CHECKS.min(total) {
+        let queue = queue.clone();
+        let sender = sender.clone();
+
+        thread::spawn(move || loop {
+            // the queue is not locked while checking
+            let next = queue.lock().unwrap().next(Instant::now());
+
+            match next {
+                Next::Check(link) => {
+                    let check = check_url(&link.url);
+                    queue.lock().unwrap().finish(&link, Instant::now());
+
+                    if sender.send((link, check)).is_err() {
+                        return;
+                    }
+                }
+                Next::Wait(wait) => thread::sleep(wait.min(MAX_WAIT)),
+                Next::Done => return,
+            }
+        });
+    }
+    // the receiver stops when every worker is done
+    drop(sender);
+
+    let store = app.state::<StorageState>();
+    let mut checked = 0;
+    let mut batch = Vec::new();
+    let mut saved_at = Instant::now();
+
+    let save = |batch: &mut Vec<(Uuid, LinkCheck)>, checked: usize| {
+        // a link which can't be saved is checked again next time
+        let links = store
+            .with(|store| store.save_checks(batch.drain(..).collect()))
+            .unwrap_or_default();
+
+        let _ = app.emit_all(
+            CHECK_PROGRESS_EVENT,
+            CheckProgress {
+                checked,
+                total,
+                links,
+            },
+        );
+    };
+
+    for (link, check) in receiver {
+        checked += 1;
+        if let Some(id) = link.id {
+            batch.push((id, check));
+        }
+
+        if batch.len() >= SAVE_BATCH || saved_at.elapsed() >= SAVE_INTERVAL {
+            save(&mut batch, checked);
+            saved_at = Instant::now();
+        }
+    }
+    save(&mut batch, checked);
+
+    checked
+}
+
+/// Check whether the page of the `url` works.
+///
+/// The redirects are followed, and the status of the last page is saved. Only the beginning of the page is downloaded.
+///
+/// The website is [`unreachable`](LinkCheck::unreachable) only if its host is not found or it refuses the connection. Other errors (e.g. a timeout) may come from the user's network, so the check is inconclusive.
+pub fn check_url(url: &str) -> LinkCheck {
+    let check = LinkCheck::new(url);
+
+    match fetch_status(url) {
+        Ok((status, effective_url)) => check.status(status).redirect(effective_url),
+        Err(err) if is_gone(&err) => check.unreachable(err.description()),
+        Err(err) => check.error(err.description()),
+    }
+}
+
+/// Whether the error shows that the website is gone: its host is not found or it refuses the connection
+fn is_gone(err: &curl::Error) -> bool {
+    let refused = matches!(err.extra_description(), Some(extra) if extra.contains("refused"));
+
+    err.is_couldnt_resolve_host() || (err.is_couldnt_connect() && refused)
+}
+
+/// Request the page of the `url`. It returns the status and the url of the last page.
+fn fetch_status(url: &str) -> Result<(u32, String), curl::Error> {
+    let mut easy = Easy::new();
+    easy.url(url)?;
+    easy.follow_location(true)?;
+    easy.max_redirections(10)?;
+    easy.connect_timeout(Duration::from_secs(10))?;
+    easy.timeout(Duration::from_secs(20))?;
+    easy.useragent("DSaver")?;
+
+    let mut size = 0;
+    {
+        let mut transfer = easy.transfer();
+        transfer.write_function(|chunk| {
+            size += chunk.len();
+
+            // stop the transfer, the status is already known
+            if size > MAX_PAGE_SIZE {
+                Ok(0)
+            } else {
+                Ok(chunk.len())
+            }
+        })?;
+
+        match transfer.perform() {
+            // the transfer is stopped by the write function
+            Err(err) if !err.is_write_error() => return Err(err),
+            _ => {}
+        }
+    }
+
+    let status = easy.response_code()?;
+    let effective_url = easy.effective_url()?.unwrap_or(url).to_string();
+
+    Ok((status, effective_url))
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use chrono::{Duration, Utc};
 use rand::prelude::*;
 use webpage::{Webpage, WebpageOptions};
 
@@ -99,7 +100,7 @@ pub async fn generate() -> Vec<Link> {
                 .tags_vec(rand_tags)
                 .browser(browser)
                 .priority(priority)
-                .date(String::from("September 15, 2022")),
+                .created_at(Utc::now() - Duration::days(thread_rng().gen_range(0..365))),
         );
     }
 
@@ -0,0 +1,212 @@
+use crate::*;
+use curl::easy::Easy;
+use dsaver_core::storage::{image_key, image_urls, images_dir, ImageCache};
+use std::collections::HashSet;
+use std::error::Error;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::http::{Request, Response, ResponseBuilder};
+use tauri::{AppHandle, Manager};
+
+/// The scheme of the custom protocol which serves the cached images to the webview, see [`image_protocol`].
+pub const IMAGE_PROTOCOL: &str = "dsimg";
+
+/// Images larger than this (in bytes) are not downloaded.
+const MAX_IMAGE_SIZE: usize = 5 * 1024 * 1024;
+
+/// The [`ImageCache`] of the favicons and thumbnails of the links, shared between the commands.
+#[derive(Default)]
+pub struct ImageCacheState {
+    /// The cache, opened by the first command who needs it
+    cache: Mutex<Option<ImageCache>>,
+    /// Keys of the missing images which are already requested by the webview, so that they are downloaded only once
+    missing: Mutex<HashSet<String>>,
+}
+
+impl ImageCacheState {
+    /// Run `f` with the cache. If the cache is not opened yet, then it will be opened first.
+    pub fn with<T, F>(&self, f: F) -> Result<T, ErrorReporter>
+    where
+        F: FnOnce(&mut ImageCache) -> Result<T, ErrorReporter>,
+    {
+        let mut cache = self.cache.lock().unwrap();
+
+        if cache.is_none() {
+            *cache = Some(ImageCache::open(images_dir(home_dir()?))?);
+        }
+
+        f(cache.as_mut().unwrap())
+    }
+}
+
+/// Download the favicon and the thumbnail of the `link` into the cache, unless they are already cached.
+///
+/// An image which can't be downloaded is skipped. The frontend shows a letter avatar instead of a missing favicon.
+pub fn cache_images(images: &ImageCacheState, link: &Link) {
+    for url in image_urls(link) {
+        if images.with(|cache| Ok(cache.contains(url))).unwrap_or(true) {
+            continue;
+        }
+
+        // the cache is not locked while downloading
+        if let Some((content_type, data)) = download_image(url) {
+            let _ = images.with(|cache| cache.insert(url, &content_type, &data));
+        }
+    }
+}
+
+/// Download the images of the `link` like [`cache_images`], but in a background thread.
+///
+/// Use it inside the commands, so that the user doesn't wait for the images.
+pub fn cache_images_later(app: &AppHandle, link: &Link) {
+    let app = app.clone();
+    let link = link.clone();
+
+    thread::spawn(move || cache_images(&app.state::<ImageCacheState>(), &link));
+}
+
+/// Download a missing image in a background thread, if a link or a link inside the trash has an image with the `key`.
+///
+/// The links saved before the images were cached have no cached images, so they are downloaded when the webview asks for them. Every key is tried only once while the app is running.
+fn cache_missing_image(app: &AppHandle, key: &str) {
+    if !app
+        .state::<ImageCacheState>()
+        .missing
+        .lock()
+        .unwrap()
+        .insert(key.to_string())
+    {
+        return;
+    }
+
+    let app = app.clone();
+    let key = key.to_string();
+
+    thread::spawn(move || {
+        let links = app.state::<StorageState>().with(|store| {
+            let mut links = store.links()?;
+            links.extend(store.trashed()?.into_iter().map(|trashed| trashed.link));
+
+            Ok(links)
+        });
+
+        let url = links.ok().and_then(|links| {
+            links.iter().find_map(|link| {
+                image_urls(link)
+                    .find(|url| image_key(url) == key)
+                    .map(String::from)
+            })
+        });
+
+        if let Some(url) = url {
+            if let Some((content_type, data)) = download_image(&url) {
+                let images = app.state::<ImageCacheState>();
+                let _ = images.with(|cache| cache.insert(&url, &content_type, &data));
+            }
+        }
+    });
+}
+
+/// Remove the cached images of the links which are deleted forever.
+///
+/// The images of the links inside the trash are kept.
+pub fn collect_image_garbage(
+    store: &StorageState,
+    images: &ImageCacheState,
+) -> Result<usize, ErrorReporter> {
+    let links = store.with(|store| {
+        let mut links = store.links()?;
+        links.extend(store.trashed()?.into_iter().map(|trashed| trashed.link));
+
+        Ok(links)
+    })?;
+
+    images.with(|cache| cache.collect_garbage(&links))
+}
+
+/// Serve a cached image to the webview.
+///
+/// Register it with [`tauri::Builder::register_uri_scheme_protocol`] for the [`IMAGE_PROTOCOL`]. The webview asks for an image with its [`image_key`]: `dsimg://localhost/<key>` (or `https://dsimg.localhost/<key>` on Windows). If the image is not cached, then the response is `404` and the image is downloaded in the background (see [`cache_missing_image`]), so it is shown the next time.
+///
+/// # Example
+///
+/// ```js
+/// function faviconSrc(key) {
+///     return window.__TAURI__.tauri.convertFileSrc(key, "dsimg");
+/// }
+/// ```
+pub fn image_protocol(app: &AppHandle, request: &Request) -> Result<Response, Box<dyn Error>> {
+    let key = request
+        .uri()
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or_default();
+
+    match app.state::<ImageCacheState>().with(|cache| cache.read(key)) {
+        Ok(Some((image, data))) => ResponseBuilder::new()
+            .mimetype(&image.content_type)
+            // the file of a key never changes
+            .header("Cache-Control", "max-age=31536000, immutable")
+            .body(data),
+        Ok(None) => {
+            cache_missing_image(app, key);
+
+            ResponseBuilder::new()
+                .status(404)
+                // ask again after the image is downloaded
+                .header("Cache-Control", "no-store")
+                .body(Vec::new())
+        }
+        Err(_) => ResponseBuilder::new().status(404).body(Vec::new()),
+    }
+}
+
+/// Download the image of the `url`.
+///
+/// It returns the content type and the image, or `None` if the url is not an image or it is too large.
+fn download_image(url: &str) -> Option<(String, Vec<u8>)> {
+    let mut easy = Easy::new();
+    easy.url(url).ok()?;
+    easy.follow_location(true).ok()?;
+    easy.timeout(Duration::from_secs(15)).ok()?;
+    easy.useragent("DSaver").ok()?;
+
+    let mut data = Vec::new();
+    {
+        let mut transfer = easy.transfer();
+        transfer
+            .write_function(|chunk| {
+                // stop the transfer
+                if data.len() + chunk.len() > MAX_IMAGE_SIZE {
+                    return Ok(0);
+                }
+
+                data.extend_from_slice(chunk);
+                Ok(chunk.len())
+            })
+            .ok()?;
+        transfer.perform().ok()?;
+    }
+
+    if easy.response_code().ok()? != 200 || data.is_empty() {
+        return None;
+    }
+
+    // `image/png; charset=binary` is `image/png`
+    let content_type = easy
+        .content_type()
+        .ok()??
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+
+    if content_type.starts_with("image/") {
+        Some((content_type, data))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+use dsaver_core::journal::JournalEntry;
+use tauri::State;
+
+/// Undo the last change made by the user, e.g. adding, editing or deleting a link.
+///
+/// It returns all the links after undoing. If there is nothing to undo, then nothing is changed.
+///
+/// If the link of the change is deleted forever, then it returns an error with [`ErrorType::LinkNotFound`] and the change is dropped. So the next undo continues with the change before it.
+///
+/// # Example
+///
+/// ```js
+/// async function undo() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const links = await invoke("undo");
+///         console.log("Links after undoing: ", links);
+///     } catch (err) {
+///         console.error("Some error occured while undoing: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn undo(store: State<'_, StorageState>) -> Result<Vec<Link>, ErrorReporter> {
+    let links = store.with_journal(|journal| {
+        store.with(|storage| {
+            journal.undo(storage)?;
+            storage.links()
+        })
+    })?;
+
+    store.reset_index();
+
+    Ok(links)
+}
+
+/// Make the last undone change again.
+///
+/// It returns all the links after redoing. If there is nothing to redo, then nothing is changed.
+///
+/// # Example
+///
+/// ```js
+/// async function redo() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const links = await invoke("redo");
+///         console.log("Links after redoing: ", links);
+///     } catch (err) {
+///         console.error("Some error occured while redoing: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn redo(store: State<'_, StorageState>) -> Result<Vec<Link>, ErrorReporter> {
+    let links = store.with_journal(|journal| {
+        store.with(|storage| {
+            journal.redo(storage)?;
+            storage.links()
+        })
+    })?;
+
+    store.reset_index();
+
+    Ok(links)
+}
+
+/// Get the activity log: every change made, undone and redone by the user. The newest entry will be first.
+///
+/// # Example
+///
+/// ```js
+/// async function getActivity() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const entries = await invoke("get_activity");
+///         entries.forEach(entry => console.log(entry.at, entry.kind, entry.change));
+///     } catch (err) {
+///         console.error("Some error occured while reading the activity: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn get_activity(
+    store: State<'_, StorageState>,
+) -> Result<Vec<JournalEntry>, ErrorReporter> {
+    store.with_journal(|journal| Ok(journal.entries().iter().rev().cloned().collect()))
+}
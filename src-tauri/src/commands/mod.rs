@@ -1,11 +1,27 @@
 //! Useful tauri commands for building the application.
 
+mod check;
 #[cfg(debug_assertions)]
 mod generate;
+mod images;
+mod journal;
 mod open_browser;
+mod refresh;
+mod settings;
+mod storage;
+mod tags;
+mod trash;
 mod validate_link;
 
+pub use check::*;
 #[cfg(debug_assertions)]
 pub use generate::*;
+pub use images::*;
+pub use journal::*;
 pub use open_browser::*;
+pub use refresh::*;
+pub use settings::*;
+pub use storage::*;
+pub use tags::*;
+pub use trash::*;
 pub use validate_link::*;
@@ -1,10 +1,14 @@
 use crate::*;
 use std::fmt::Display;
+use tauri::State;
+use uuid::Uuid;
 
 /// Open the link in the given browser in Windows OS.
 ///
 /// If this function fails to open the browser for any reason, it will give an error inside [`ErrorReporter`] struct.
 ///
+/// If the `id` of a saved link is given, then the opening is added to its `open_history` (and its `open_count` and `last_opened_at` are updated). And the updated link is returned.
+///
 /// You have to make sure that the user is using windows operating system.
 ///
 /// For that you can [`os.platform`] function from tauri api to know which OS is the user using (at runtime).
@@ -16,6 +20,8 @@ use std::fmt::Display;
 ///     const path = "github.com";
 ///     // this variable should be in JSON format
 ///     const browser = JSON.stringify("Firefox");
+///     // id of the saved link. It can be `null` if the link is not saved
+///     const id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
 ///
 ///     // importing tauri apis
 ///     const invoke = window.__TAURI__.invoke;
@@ -28,7 +34,9 @@ use std::fmt::Display;
 ///         // check if the platform is Windows or not
 ///         if (platformName === "win32") {
 ///             // call the command
-///             await invoke("open_browser_windows", { path, browser });
+///             const link = await invoke("open_browser_windows", { path, browser, id });
+///             // the saved link with its new `open_history`, or `null` if the `id` is `null`
+///             console.log("Opened link: ", link);
 ///         }
 ///     } catch (err) {
 ///         console.error("Error occured while opening the browser in your system");
@@ -39,17 +47,25 @@ use std::fmt::Display;
 ///
 /// [`os.platform`]: https://tauri.app/v1/api/js/os#platform-1
 #[tauri::command]
-pub async fn open_browser_windows(path: String, browser: String) -> Result<(), ErrorReporter> {
+pub async fn open_browser_windows(
+    path: String,
+    browser: String,
+    id: Option<Uuid>,
+    store: State<'_, StorageState>,
+) -> Result<Option<Link>, ErrorReporter> {
     let browser: Browser = serde_json::from_str(&browser).unwrap();
     let result = browser.open_in_windows(&path);
 
-    handle_browser_open(result, browser)
+    handle_browser_open(result, browser.clone())?;
+    handle_opened_link(id, browser, &store)
 }
 
 /// Open the link in the given browser in Linux based OS.
 ///
 /// If this function fails to open the browser for any reason, it will give an error inside [`ErrorReporter`] struct.
 ///
+/// If the `id` of a saved link is given, then the opening is added to its `open_history` (and its `open_count` and `last_opened_at` are updated). And the updated link is returned.
+///
 /// You have to make sure that the user is using linux based operating system.
 ///
 /// For that you can [`os.platform`] function from tauri api to know which OS is the user using (at runtime).
@@ -61,6 +77,8 @@ pub async fn open_browser_windows(path: String, browser: String) -> Result<(), E
 ///     const path = "github.com";
 ///     // this variable should be in JSON format
 ///     const browser = JSON.stringify("Firefox");
+///     // id of the saved link. It can be `null` if the link is not saved
+///     const id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
 ///
 ///     // importing tauri apis
 ///     const invoke = window.__TAURI__.invoke;
@@ -73,7 +91,9 @@ pub async fn open_browser_windows(path: String, browser: String) -> Result<(), E
 ///         // check if the platform is linux or not
 ///         if (platformName === "linux") {
 ///             // call the command
-///             await invoke("open_browser_linux", { path, browser });
+///             const link = await invoke("open_browser_linux", { path, browser, id });
+///             // the saved link with its new `open_history`, or `null` if the `id` is `null`
+///             console.log("Opened link: ", link);
 ///         }
 ///     } catch (err) {
 ///         console.error("Error occured while opening the browser in your system");
@@ -84,17 +104,25 @@ pub async fn open_browser_windows(path: String, browser: String) -> Result<(), E
 ///
 /// [`os.platform`]: https://tauri.app/v1/api/js/os#platform-1
 #[tauri::command]
-pub async fn open_browser_linux(path: String, browser: String) -> Result<(), ErrorReporter> {
+pub async fn open_browser_linux(
+    path: String,
+    browser: String,
+    id: Option<Uuid>,
+    store: State<'_, StorageState>,
+) -> Result<Option<Link>, ErrorReporter> {
     let browser: Browser = serde_json::from_str(&browser).unwrap();
     let result = browser.open_in_linux(&path);
 
-    handle_browser_open(result, browser)
+    handle_browser_open(result, browser.clone())?;
+    handle_opened_link(id, browser, &store)
 }
 
 /// Open the link in the given browser in MacOS.
 ///
 /// If this function fails to open the browser for any reason, it will give an error inside [`ErrorReporter`] struct.
 ///
+/// If the `id` of a saved link is given, then the opening is added to its `open_history` (and its `open_count` and `last_opened_at` are updated). And the updated link is returned.
+///
 /// You have to make sure that the user is using mac operating system.
 ///
 /// For that you can [`os.platform`] function from tauri api to know which OS is the user using (at runtime).
@@ -106,6 +134,8 @@ pub async fn open_browser_linux(path: String, browser: String) -> Result<(), Err
 ///     const path = "github.com";
 ///     // this variable should be in JSON format
 ///     const browser = JSON.stringify("Firefox");
+///     // id of the saved link. It can be `null` if the link is not saved
+///     const id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
 ///
 ///     // importing tauri apis
 ///     const invoke = window.__TAURI__.invoke;
@@ -118,7 +148,9 @@ pub async fn open_browser_linux(path: String, browser: String) -> Result<(), Err
 ///         // check if the platform is macos or not
 ///         if (platformName === "darwin") {
 ///             // call the command
-///             await invoke("open_browser_macos", { path, browser });
+///             const link = await invoke("open_browser_macos", { path, browser, id });
+///             // the saved link with its new `open_history`, or `null` if the `id` is `null`
+///             console.log("Opened link: ", link);
 ///         }
 ///     } catch (err) {
 ///         console.error("Error occured while opening the browser in your system");
@@ -129,15 +161,33 @@ pub async fn open_browser_linux(path: String, browser: String) -> Result<(), Err
 ///
 /// [`os.platform`]: https://tauri.app/v1/api/js/os#platform-1
 #[tauri::command]
-pub async fn open_browser_macos(path: String, browser: String) -> Result<(), ErrorReporter> {
+pub async fn open_browser_macos(
+    path: String,
+    browser: String,
+    id: Option<Uuid>,
+    store: State<'_, StorageState>,
+) -> Result<Option<Link>, ErrorReporter> {
     let browser: Browser = serde_json::from_str(&browser).unwrap();
     let result = browser.open_in_macos(&path);
 
-    handle_browser_open(result, browser)
+    handle_browser_open(result, browser.clone())?;
+    handle_opened_link(id, browser, &store)
+}
+
+/// Remember when and in which browser the link `id` was opened, and return the updated link
+fn handle_opened_link(
+    id: Option<Uuid>,
+    browser: Browser,
+    store: &StorageState,
+) -> Result<Option<Link>, ErrorReporter> {
+    match id {
+        Some(id) => store.mark_opened(id, browser),
+        None => Ok(None),
+    }
 }
 
 /// Handle the result of opening the browser
-fn handle_browser_open(
+pub(crate) fn handle_browser_open(
     result: Result<(), std::io::Error>,
     browser: Browser,
 ) -> Result<(), ErrorReporter> {
@@ -0,0 +1,86 @@
+use crate::*;
+use chrono::Utc;
+use dsaver_core::journal::Change;
+use dsaver_core::refresh::LinkRefresh;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Fetch the webpage of a saved link again, and return what is changed.
+///
+/// Nothing is saved, so the user can see the changes of the title, description and thumbnail first. Save them with [`apply_refresh`].
+///
+/// The title which is written by the user (see [`Link::custom_title`]) is never changed. If the webpage can't be fetched, then it returns the same error as [`validate_link`].
+///
+/// # Example
+///
+/// ```js
+/// const refresh = await invoke("refresh_link", { id: link.id });
+///
+/// if (refresh.title !== null) {
+///     console.log(`The title is changed to ${refresh.title}`);
+/// }
+/// ```
+#[tauri::command]
+pub async fn refresh_link(
+    id: Uuid,
+    store: State<'_, StorageState>,
+) -> Result<LinkRefresh, ErrorReporter> {
+    let saved = store.with(|store| store.get(id))?.ok_or_else(|| {
+        ErrorReporterBuilder {
+            actual_error: "None",
+            why_error: vec!["The link is already deleted"],
+            how_to_fix: vec!["Restart the app to see the latest links"],
+            error_title: "Link Not Found",
+            when_error: "refreshing the link",
+            error_type: ErrorType::LinkNotFound,
+        }
+        .build()
+    })?;
+
+    // the title and description are fetched only if they are `None`
+    let fetched = validate_link(serde_json::to_string(&Link::new(&saved.url)).unwrap()).await?;
+
+    Ok(LinkRefresh::new(saved, fetched))
+}
+
+/// Save the changes of a link returned by [`refresh_link`].
+///
+/// The changes are put into the saved link, so the edits which are made after refreshing are kept. The new favicon and thumbnail are downloaded into the cache in the background.
+///
+/// It returns the updated link. The refresh can be undone with [`undo`].
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`LinkRefresh`].
+#[tauri::command]
+pub async fn apply_refresh(
+    app: AppHandle,
+    refresh: String,
+    store: State<'_, StorageState>,
+) -> Result<Link, ErrorReporter> {
+    let refresh: LinkRefresh = serde_json::from_str(&refresh).unwrap();
+
+    let (saved, link) = store.with(|store| {
+        // if the link is deleted, then updating it returns an error
+        let saved = match refresh.link.id {
+            Some(id) => store.get(id)?,
+            None => None,
+        }
+        .unwrap_or_else(|| refresh.link.clone());
+
+        let mut link = refresh.apply(saved.clone());
+        link.updated_at = Utc::now();
+
+        Ok((saved, store.update(link)?))
+    })?;
+
+    cache_images_later(&app, &link);
+
+    store.with_index(|index| index.insert(&link));
+    store.record(Change::Edited {
+        before: saved,
+        after: link.clone(),
+    });
+
+    Ok(link)
+}
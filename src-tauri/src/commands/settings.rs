@@ -0,0 +1,47 @@
+use crate::*;
+use dsaver_core::settings::{settings_file, Settings};
+
+/// Get the settings of the application from `~/.DSaver/settings.json`.
+///
+/// If the user has not changed any settings yet, then it will return the default settings.
+///
+/// # Example
+///
+/// ```js
+/// async function getSettings() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const settings = await invoke("get_settings");
+///         console.log("The links are stored in: ", settings.storage);
+///     } catch (err) {
+///         console.error("Some error occured while reading the settings: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn get_settings() -> Result<Settings, ErrorReporter> {
+    Settings::load(settings_file(home_dir()?))
+}
+
+/// Save the settings of the application.
+///
+/// The storage can't be changed with this command, because the links need to be copied. Use [`migrate_storage`] for it.
+///
+/// The settings are validated before saving (see [`Settings::validated`]). It returns the saved settings.
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`Settings`].
+#[tauri::command]
+pub async fn update_settings(settings: String) -> Result<Settings, ErrorReporter> {
+    let path = settings_file(home_dir()?);
+    let mut settings = serde_json::from_str::<Settings>(&settings)
+        .unwrap()
+        .validated();
+
+    settings.storage = Settings::load(&path)?.storage;
+    settings.save(&path)?;
+
+    Ok(settings)
+}
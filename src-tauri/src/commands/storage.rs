@@ -0,0 +1,853 @@
+use crate::*;
+use chrono::{DateTime, Utc};
+use dsaver_core::check::LinkCheck;
+use dsaver_core::journal::{journal_file, Change, Journal};
+use dsaver_core::query::Query;
+use dsaver_core::search::{SearchHit, SearchIndex};
+use dsaver_core::settings::{settings_file, Settings};
+use dsaver_core::storage::{links_file, purge_expired, Backup, LinkStore, Recovery, Storage};
+use dsaver_core::storage::{LinksFile, StorageBackend, TrashedLink};
+use dsaver_core::tag::TagInfo;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// The [`Storage`] of the application.
+///
+/// This state is managed by tauri, so every command can access it by taking a [`State<StorageState>`] argument.
+///
+/// The storage chosen in the [`Settings`] is opened by the first command who needs it. By default it is the JSON file `~/.DSaver/links.json`.
+///
+/// When the storage is opened, the links which were in the trash for more than [`Settings::trash_retention_days`] are deleted forever.
+///
+/// If the JSON file is corrupted, then the links are recovered with [`LinkStore::recover`] and the command will return an error with [`ErrorType::DataRecovered`]. The recovered links are read-only until the user chooses to keep them (see [`keep_recovered_links`]) or restores a backup, so the corrupted file is not overwritten by another change.
+///
+/// # Example
+///
+/// ```ignore
+/// tauri::Builder::default()
+///     .manage(StorageState::default())
+///     .invoke_handler(tauri::generate_handler![get_links])
+///     .run(tauri::generate_context!())
+///     .expect("error while running tauri application");
+/// ```
+#[derive(Default)]
+pub struct StorageState {
+    /// The opened storage
+    storage: Mutex<Option<Box<dyn Storage>>>,
+    /// The last recovery of the corrupted file
+    recovery: Mutex<Option<Recovery>>,
+    /// The search index of the links. It is built by the first search
+    index: Mutex<Option<SearchIndex>>,
+    /// The journal of the user's changes. It is opened by the first change
+    journal: Mutex<Option<Journal>>,
+}
+
+impl StorageState {
+    /// Run `f` with the storage. If the storage is not opened yet, then it will be opened first.
+    pub fn with<T, F>(&self, f: F) -> Result<T, ErrorReporter>
+    where
+        F: FnOnce(&mut dyn Storage) -> Result<T, ErrorReporter>,
+    {
+        let mut storage = self.storage.lock().unwrap();
+
+        if storage.is_none() {
+            let home_dir = home_dir()?;
+            let settings = Settings::load(settings_file(&home_dir))?;
+            let backend = settings.storage;
+
+            match backend.open(&home_dir) {
+                Ok(mut opened) => {
+                    purge_expired(opened.as_mut(), settings.trash_retention_days)?;
+                    *storage = Some(opened);
+                }
+                Err(err)
+                    if backend == StorageBackend::Json
+                        && err.error_type() == &ErrorType::CorruptedData =>
+                {
+                    let (recovered, recovery) = LinkStore::recover(links_file(&home_dir))?;
+                    let report = recovery.report();
+
+                    *storage = Some(Box::new(recovered));
+                    *self.recovery.lock().unwrap() = Some(recovery);
+
+                    return Err(report);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        let storage = storage.as_mut().unwrap().as_mut();
+
+        match self.recovery.lock().unwrap().clone() {
+            Some(recovery) => f(&mut ReadOnlyStorage { storage, recovery }),
+            None => f(storage),
+        }
+    }
+
+    /// Run `f` with the journal `~/.DSaver/journal.jsonl`. If the journal is not opened yet, then it will be opened first.
+    pub(crate) fn with_journal<T, F>(&self, f: F) -> Result<T, ErrorReporter>
+    where
+        F: FnOnce(&mut Journal) -> Result<T, ErrorReporter>,
+    {
+        let mut journal = self.journal.lock().unwrap();
+
+        if journal.is_none() {
+            *journal = Some(Journal::open(journal_file(home_dir()?))?);
+        }
+
+        f(journal.as_mut().unwrap())
+    }
+
+    /// Remember a change made by the user, so that it can be undone.
+    ///
+    /// The change is already saved, so the command doesn't fail if the journal can't be written. The change just can't be undone.
+    pub(crate) fn record(&self, change: Change) {
+        if let Err(err) = self.with_journal(|journal| journal.record(change)) {
+            eprintln!("The change can't be recorded in the journal: {err:?}");
+        }
+    }
+
+    /// Forget the changes of the links which are deleted forever, so that undoing doesn't stop at them.
+    pub(crate) fn forget(&self, ids: &[Uuid]) {
+        if let Err(err) = self.with_journal(|journal| journal.forget(ids)) {
+            eprintln!("The changes can't be removed from the journal: {err:?}");
+        }
+    }
+
+    /// Run `f` with the search index if it is already built.
+    ///
+    /// Call it after changing a link, so that the index doesn't need to be built again.
+    pub(crate) fn with_index<F: FnOnce(&mut SearchIndex)>(&self, f: F) {
+        if let Some(index) = self.index.lock().unwrap().as_mut() {
+            f(index);
+        }
+    }
+
+    /// Remove the search index. It will be built again by the next search.
+    ///
+    /// Call it after replacing all the links.
+    pub(crate) fn reset_index(&self) {
+        *self.index.lock().unwrap() = None;
+    }
+
+    /// Search the links which match the `query` and the `filter`. The best match will be first.
+    ///
+    /// The `query` is parsed as a [`Query`] and searched with [`SearchIndex::search_query`]: its words without a key are searched with the index, and the rest of the terms filter the links. If the query doesn't have any word, then all matched links are returned in the order they were added.
+    pub fn search(
+        &self,
+        query: &str,
+        filter: &LinkFilter,
+    ) -> Result<Vec<SearchHit>, ErrorReporter> {
+        let query = Query::parse(query)?;
+        let links = self.with(|store| store.query(filter))?;
+        let mut index = self.index.lock().unwrap();
+
+        if index.is_none() {
+            *index = Some(SearchIndex::new(&self.with(|store| store.links())?));
+        }
+
+        Ok(index.as_ref().unwrap().search_query(&query, &links))
+    }
+
+    /// Remember that the link `id` was opened in the `browser`, see [`Link::opened`].
+    ///
+    /// If the user has chosen [`Settings::complete_on_open`], then the link is marked as complete too.
+    ///
+    /// It returns the updated link, or `None` if the link doesn't exist.
+    pub(crate) fn mark_opened(
+        &self,
+        id: Uuid,
+        browser: Browser,
+    ) -> Result<Option<Link>, ErrorReporter> {
+        let complete_on_open = Settings::load(settings_file(home_dir()?))?.complete_on_open;
+
+        self.with(|store| match store.get(id)? {
+            Some(mut link) => {
+                link.opened(browser);
+                link.complete |= complete_on_open;
+
+                store.update(link).map(Some)
+            }
+            None => Ok(None),
+        })
+    }
+}
+
+/// Get all the links saved by the user.
+///
+/// If the user has not saved any links yet, then it will return an empty list.
+///
+/// It will return an error inside [`ErrorReporter`] if the storage can't be read or it is corrupted.
+///
+/// # Example
+///
+/// ```js
+/// async function getLinks() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const links = await invoke("get_links");
+///         console.log("All links: ", links);
+///     } catch (err) {
+///         console.error("Some error occured while reading the links: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn get_links(store: State<'_, StorageState>) -> Result<Vec<Link>, ErrorReporter> {
+    store.with(|store| store.links())
+}
+
+/// Validate a new link with [`validate_link`] and then save it.
+///
+/// It will return the saved link which contains the fetched `title` and `description`. Its `created_at` and `updated_at` are set to the current time.
+///
+/// The favicon and the thumbnail of the link are downloaded into the image cache in the background, see [`cache_images_later`].
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`Link`].
+///
+/// # Example
+///
+/// ```js
+/// async function addLink() {
+///     const invoke = window.__TAURI__.invoke;
+///     const link = {
+///         url: "https://www.github.com",
+///         tags: ["Code"],
+///         priority: 'A',
+///         browser: "Firefox",
+///         complete: false
+///     };
+///
+///     try {
+///         const newLink = await invoke("add_link", { link: JSON.stringify(link) });
+///         console.log("Saved link: ", newLink);
+///     } catch (err) {
+///         console.error("Some error occured while adding the link: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn add_link(
+    app: AppHandle,
+    link: String,
+    store: State<'_, StorageState>,
+) -> Result<Link, ErrorReporter> {
+    let link = validate_link(link).await?.created_at(Utc::now());
+    let link = store.with(|store| store.insert(link))?;
+
+    cache_images_later(&app, &link);
+
+    store.with_index(|index| index.insert(&link));
+    store.record(Change::Created(link.clone()));
+
+    Ok(link)
+}
+
+/// Save a new link without validating it.
+///
+/// Use it when the user wants to keep a link even though [`validate_link`] failed. Its `created_at` and `updated_at` are set to the current time.
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`Link`].
+#[tauri::command]
+pub async fn insert_link(
+    link: String,
+    store: State<'_, StorageState>,
+) -> Result<Link, ErrorReporter> {
+    let link = serde_json::from_str::<Link>(&link)
+        .unwrap()
+        .created_at(Utc::now());
+    let link = store.with(|store| store.insert(link))?;
+
+    store.with_index(|index| index.insert(&link));
+    store.record(Change::Created(link.clone()));
+
+    Ok(link)
+}
+
+/// Replace a saved link with the given one. Both links must have the same `id`.
+///
+/// The `updated_at` of the link is set to the current time. The `created_at`, `last_opened_at`, `open_count`, `open_history`, `manual_order` and `metadata` of the saved link are kept. The `check` of the saved link is kept only if the url is not changed.
+///
+/// The edit can be undone with [`undo`].
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`Link`].
+#[tauri::command]
+pub async fn update_link(
+    link: String,
+    store: State<'_, StorageState>,
+) -> Result<Link, ErrorReporter> {
+    let mut link: Link = serde_json::from_str(&link).unwrap();
+
+    let (saved, link) = store.with(|store| {
+        let saved = match link.id {
+            Some(id) => store.get(id)?,
+            None => None,
+        };
+
+        // The frontend may have an old copy of the link, so keep the timestamps, the order and the metadata from the storage
+        if let Some(saved) = &saved {
+            link.created_at = saved.created_at;
+            link.last_opened_at = saved.last_opened_at;
+            link.open_count = saved.open_count;
+            link.open_history = saved.open_history.clone();
+            link.manual_order = saved.manual_order;
+            link.metadata = saved.metadata.clone();
+            link.check = saved.check.clone().filter(|check| check.url == link.url);
+        }
+        link.updated_at = Utc::now();
+
+        Ok((saved, store.update(link)?))
+    })?;
+
+    store.with_index(|index| index.insert(&link));
+
+    if let Some(saved) = saved {
+        store.record(Change::Edited {
+            before: saved,
+            after: link.clone(),
+        });
+    }
+
+    Ok(link)
+}
+
+/// Move the link with the given `id` into the trash.
+///
+/// It returns the deleted link. The link can be brought back with [`restore_link`] or [`undo`].
+#[tauri::command]
+pub async fn delete_link(id: Uuid, store: State<'_, StorageState>) -> Result<Link, ErrorReporter> {
+    let link = store.with(|store| store.trash(id))?.link;
+
+    store.with_index(|index| index.remove(id));
+    store.record(Change::Trashed(link.clone()));
+
+    Ok(link)
+}
+
+/// Save the order of the links chosen by the user by dragging them.
+///
+/// The links are shown in the order of `ids` when the sort order is [`SortOrder::Manual`]. The links which are not in `ids` are shown after them.
+///
+/// It returns all the links.
+///
+/// # Arguments
+///
+/// This function takes an serialized string of `Vec<Uuid>`.
+///
+/// # Example
+///
+/// ```js
+/// async function reorderLinks(links) {
+///     const invoke = window.__TAURI__.invoke;
+///     // move the last link to the top
+///     const ids = [links[links.length - 1].id, ...links.slice(0, -1).map(link => link.id)];
+///
+///     try {
+///         const links = await invoke("reorder_links", { ids: JSON.stringify(ids) });
+///         console.log("Reordered links: ", links);
+///     } catch (err) {
+///         console.error("Some error occured while reordering the links: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn reorder_links(
+    ids: String,
+    store: State<'_, StorageState>,
+) -> Result<Vec<Link>, ErrorReporter> {
+    let ids: Vec<Uuid> = serde_json::from_str(&ids).unwrap();
+
+    store.with(|store| store.reorder(&ids))
+}
+
+/// Get all backups of the links file. The newest backup will be first.
+///
+/// A backup is created in `~/.DSaver/backups/` every time the links are saved. The SQLite storage doesn't have backups, so the list will be empty.
+///
+/// # Example
+///
+/// ```js
+/// async function listBackups() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const backups = await invoke("list_backups");
+///         backups.forEach(backup => console.log(backup.name, backup.created_at, backup.size));
+///     } catch (err) {
+///         console.error("Some error occured while reading the backups: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn list_backups(store: State<'_, StorageState>) -> Result<Vec<Backup>, ErrorReporter> {
+    store.with(|store| store.backups())
+}
+
+/// Replace all links with the links of the backup `name`.
+///
+/// It returns the restored links.
+///
+/// The current links are backed up before restoring. So the user can go back by restoring that backup.
+#[tauri::command]
+pub async fn restore_backup(
+    name: String,
+    store: State<'_, StorageState>,
+) -> Result<Vec<Link>, ErrorReporter> {
+    let links = store.with(|store| {
+        store.restore_backup(&name)?;
+
+        store.links()
+    })?;
+
+    // the corrupted file is replaced by the backup
+    *store.recovery.lock().unwrap() = None;
+    store.reset_index();
+
+    Ok(links)
+}
+
+/// Save the links recovered from the corrupted file, replacing the corrupted file.
+///
+/// Call it after [`get_links`] returns an error with [`ErrorType::DataRecovered`]. It returns the recovered links.
+///
+/// The original file is still available in `~/.DSaver/quarantine/`.
+#[tauri::command]
+pub async fn keep_recovered_links(
+    store: State<'_, StorageState>,
+) -> Result<Vec<Link>, ErrorReporter> {
+    // the storage can be changed only without the recovery
+    let recovery = store.recovery.lock().unwrap().take();
+
+    let result = store.with(|store| {
+        let links = store.links()?;
+        store.replace_all(links.clone())?;
+
+        Ok(links)
+    });
+
+    if result.is_err() {
+        *store.recovery.lock().unwrap() = recovery;
+    }
+
+    result
+}
+
+/// Open the quarantined copy of the corrupted file with the default program of the OS.
+///
+/// So that the user can see what's wrong with it and fix it by hand.
+#[tauri::command]
+pub async fn open_quarantined_file(store: State<'_, StorageState>) -> Result<(), ErrorReporter> {
+    let path = match &*store.recovery.lock().unwrap() {
+        Some(recovery) => recovery.quarantined.display().to_string(),
+        None => {
+            return Err(ErrorReporterBuilder {
+                actual_error: "None",
+                why_error: vec!["No corrupted file is found"],
+                how_to_fix: vec!["Restart the app"],
+                error_title: "File Not Found",
+                when_error: "opening the corrupted file",
+                error_type: ErrorType::FileNotFound,
+            }
+            .build())
+        }
+    };
+
+    let browser = Browser::SysDefault;
+
+    #[cfg(target_os = "windows")]
+    let result = browser.open_in_windows(&path);
+    #[cfg(target_os = "macos")]
+    let result = browser.open_in_macos(&path);
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let result = browser.open_in_linux(&path);
+
+    handle_browser_open(result, browser)
+}
+
+/// Get the links which match the `filter`, in the order they were added.
+///
+/// The filter is executed by the storage (e.g. with the indexes of SQLite), so the frontend doesn't need to filter all the links.
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`LinkFilter`].
+///
+/// # Example
+///
+/// ```js
+/// async function queryLinks() {
+///     const invoke = window.__TAURI__.invoke;
+///     // links with tag `Rust` and priority `B`
+///     const filter = { tags: ["Rust"], browsers: null, priority: "B" };
+///
+///     try {
+///         const links = await invoke("query_links", { filter: JSON.stringify(filter) });
+///         console.log("Matched links: ", links);
+///     } catch (err) {
+///         console.error("Some error occured while reading the links: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn query_links(
+    filter: String,
+    store: State<'_, StorageState>,
+) -> Result<Vec<Link>, ErrorReporter> {
+    let filter: LinkFilter = serde_json::from_str(&filter).unwrap();
+
+    store.with(|store| store.query(&filter))
+}
+
+/// Get the ids of the links which match the `filter`, in the order they were added.
+///
+/// It is the same as [`query_links`], but only the ids are sent to the frontend. Use it when the frontend already has the links.
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`LinkFilter`].
+///
+/// # Example
+///
+/// ```js
+/// const ids = await invoke("query_link_ids", { filter: JSON.stringify({ tags: ["Rust"] }) });
+/// ```
+#[tauri::command]
+pub async fn query_link_ids(
+    filter: String,
+    store: State<'_, StorageState>,
+) -> Result<Vec<Uuid>, ErrorReporter> {
+    let filter: LinkFilter = serde_json::from_str(&filter).unwrap();
+    let links = store.with(|store| store.query(&filter))?;
+
+    Ok(links.into_iter().filter_map(|link| link.id).collect())
+}
+
+/// Search the links by their `title`, `url`, `description` and `tags`.
+///
+/// Only the links which match the `filter` are searched. The best match will be first, and every [`SearchHit`] contains the matched words, so they can be highlighted.
+///
+/// The `query` can also filter the links with keys, like `tag:rust priority:<=C -tag:video tutorial`. See [`Query`] for all keys. A word without a key can have typos or can be the beginning of a word (see [`SearchIndex`]).
+///
+/// It will return an error with [`ErrorType::InvalidQuery`] if the query can't be parsed.
+///
+/// # Arguments
+///
+/// `filter` - an serialized string of [`LinkFilter`].
+///
+/// # Example
+///
+/// ```js
+/// async function searchLinks() {
+///     const invoke = window.__TAURI__.invoke;
+///     const filter = { tags: null, browsers: null, priority: null };
+///
+///     try {
+///         const hits = await invoke("search_links", { query: "tutorial tag:rust is:incomplete", filter: JSON.stringify(filter) });
+///         hits.forEach(hit => console.log(hit.id, hit.score, hit.highlights));
+///     } catch (err) {
+///         console.error("Some error occured while searching the links: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn search_links(
+    query: String,
+    filter: String,
+    store: State<'_, StorageState>,
+) -> Result<Vec<SearchHit>, ErrorReporter> {
+    let filter: LinkFilter = serde_json::from_str(&filter).unwrap();
+
+    store.search(&query, &filter)
+}
+
+/// Get all the searches saved by the user, in the order they were added.
+///
+/// # Example
+///
+/// ```js
+/// async function getSavedSearches() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const searches = await invoke("get_saved_searches");
+///         searches.forEach(search => console.log(search.name, search.query));
+///     } catch (err) {
+///         console.error("Some error occured while reading the saved searches: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn get_saved_searches(
+    store: State<'_, StorageState>,
+) -> Result<Vec<SavedSearch>, ErrorReporter> {
+    store.with(|store| store.saved_searches())
+}
+
+/// Save a new search, or replace the saved search which has the same `id`.
+///
+/// It returns the saved search. It will return an error with [`ErrorType::InvalidQuery`] if the `query` of the search can't be parsed.
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`SavedSearch`].
+///
+/// # Example
+///
+/// ```js
+/// async function saveSearch() {
+///     const invoke = window.__TAURI__.invoke;
+///     const search = {
+///         id: crypto.randomUUID(),
+///         name: "Unread Rust videos",
+///         query: "is:unopened tag:video",
+///         filter: { tags: ["Rust"], browsers: null, priority: null }
+///     };
+///
+///     try {
+///         await invoke("save_search", { search: JSON.stringify(search) });
+///     } catch (err) {
+///         console.error("Some error occured while saving the search: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn save_search(
+    search: String,
+    store: State<'_, StorageState>,
+) -> Result<SavedSearch, ErrorReporter> {
+    let search: SavedSearch = serde_json::from_str(&search).unwrap();
+    search.parse_query()?;
+
+    store.with(|store| store.save_search(search))
+}
+
+/// Delete the saved search with the given `id`.
+///
+/// It returns the deleted search. The links are not changed.
+#[tauri::command]
+pub async fn delete_search(
+    id: Uuid,
+    store: State<'_, StorageState>,
+) -> Result<SavedSearch, ErrorReporter> {
+    store.with(|store| store.delete_search(id))
+}
+
+/// Copy all links into the storage `backend` and use it from now on.
+///
+/// The old storage is not changed, so the user can switch back without losing anything. But the links which are already inside the new storage will be replaced.
+///
+/// It returns the new [`Settings`], or an error with [`ErrorType::DataRecovered`] while the user hasn't chosen what to do with the recovered links.
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`StorageBackend`].
+///
+/// # Example
+///
+/// ```js
+/// async function useSqlite() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         await invoke("migrate_storage", { backend: JSON.stringify("Sqlite") });
+///     } catch (err) {
+///         console.error("Some error occured while moving the links: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn migrate_storage(
+    backend: String,
+    store: State<'_, StorageState>,
+) -> Result<Settings, ErrorReporter> {
+    let backend: StorageBackend = serde_json::from_str(&backend).unwrap();
+    let home_dir = home_dir()?;
+    let mut settings = Settings::load(settings_file(&home_dir))?;
+
+    if settings.storage != backend {
+        // the recovered links are not moved until the user chooses what to do with them
+        if let Some(recovery) = &*store.recovery.lock().unwrap() {
+            return Err(recovery.report());
+        }
+
+        let mut new_storage = backend.open(&home_dir)?;
+
+        store.with(|store| dsaver_core::storage::migrate_storage(store, new_storage.as_mut()))?;
+
+        settings.storage = backend;
+        settings.save(settings_file(&home_dir))?;
+
+        *store.storage.lock().unwrap() = Some(new_storage);
+        store.reset_index();
+    }
+
+    Ok(settings)
+}
+
+/// Get the user's home directory
+pub(crate) fn home_dir() -> Result<PathBuf, ErrorReporter> {
+    match tauri::api::path::home_dir() {
+        Some(home_dir) => Ok(home_dir),
+        None => Err(ErrorReporterBuilder {
+            actual_error: "None",
+            why_error: vec!["Your home directory not found"],
+            how_to_fix: vec!["Put your home directory into the path variable"],
+            error_title: "Directory Not Found",
+            when_error: "finding the home directory",
+            error_type: ErrorType::DirectoryNotFound,
+        }
+        .build()),
+    }
+}
+
+/// The links recovered from a corrupted file, see [`StorageState`].
+///
+/// They can be read, but every change returns an error until the user keeps them or restores a backup. Otherwise the next change would replace the corrupted file before the user has chosen what to do.
+struct ReadOnlyStorage<'a> {
+    /// The recovered storage
+    storage: &'a mut dyn Storage,
+    /// How the links were recovered
+    recovery: Recovery,
+}
+
+impl ReadOnlyStorage<'_> {
+    /// The error of every change
+    fn report(&self, when: &str) -> ErrorReporter {
+        ErrorReporterBuilder {
+            actual_error: "None",
+            why_error: vec![
+                "The links are recovered from a corrupted file",
+                "They are not saved until you choose what to do with them",
+            ],
+            how_to_fix: vec![
+                "Keep the recovered links",
+                "Restore the links from a backup",
+            ],
+            error_title: "Recovered Links Not Saved",
+            when_error: when,
+            error_type: ErrorType::DataRecovered {
+                recovered: self.recovery.recovered,
+                lost: self.recovery.lost,
+            },
+        }
+        .build()
+    }
+}
+
+impl Storage for ReadOnlyStorage<'_> {
+    fn links(&self) -> Result<Vec<Link>, ErrorReporter> {
+        self.storage.links()
+    }
+
+    fn get(&self, id: Uuid) -> Result<Option<Link>, ErrorReporter> {
+        self.storage.get(id)
+    }
+
+    fn insert(&mut self, _link: Link) -> Result<Link, ErrorReporter> {
+        Err(self.report("adding a new link"))
+    }
+
+    fn update(&mut self, _link: Link) -> Result<Link, ErrorReporter> {
+        Err(self.report("updating the link"))
+    }
+
+    fn delete(&mut self, _id: Uuid) -> Result<Link, ErrorReporter> {
+        Err(self.report("deleting the link"))
+    }
+
+    fn replace_all(&mut self, _links: Vec<Link>) -> Result<(), ErrorReporter> {
+        Err(self.report("replacing the links"))
+    }
+
+    fn reorder(&mut self, _ids: &[Uuid]) -> Result<Vec<Link>, ErrorReporter> {
+        Err(self.report("reordering the links"))
+    }
+
+    fn save_checks(&mut self, _checks: Vec<(Uuid, LinkCheck)>) -> Result<Vec<Link>, ErrorReporter> {
+        Err(self.report("saving the checks of the links"))
+    }
+
+    fn update_tags(
+        &mut self,
+        _links: Vec<Link>,
+        _infos_before: &[TagInfo],
+        _infos_after: &[TagInfo],
+    ) -> Result<Vec<Link>, ErrorReporter> {
+        Err(self.report("changing the tags"))
+    }
+
+    fn trash(&mut self, _id: Uuid) -> Result<TrashedLink, ErrorReporter> {
+        Err(self.report("moving the link into the trash"))
+    }
+
+    fn trashed(&self) -> Result<Vec<TrashedLink>, ErrorReporter> {
+        self.storage.trashed()
+    }
+
+    fn restore(&mut self, _id: Uuid) -> Result<Link, ErrorReporter> {
+        Err(self.report("restoring the link"))
+    }
+
+    fn purge(&mut self, _id: Uuid) -> Result<TrashedLink, ErrorReporter> {
+        Err(self.report("deleting the link forever"))
+    }
+
+    fn replace_trash(&mut self, _trash: Vec<TrashedLink>) -> Result<(), ErrorReporter> {
+        Err(self.report("replacing the trash"))
+    }
+
+    // the expired links are deleted after the recovered links are kept
+    fn purge_before(&mut self, _before: DateTime<Utc>) -> Result<Vec<TrashedLink>, ErrorReporter> {
+        Ok(Vec::new())
+    }
+
+    fn saved_searches(&self) -> Result<Vec<SavedSearch>, ErrorReporter> {
+        self.storage.saved_searches()
+    }
+
+    fn save_search(&mut self, _search: SavedSearch) -> Result<SavedSearch, ErrorReporter> {
+        Err(self.report("saving the search"))
+    }
+
+    fn delete_search(&mut self, _id: Uuid) -> Result<SavedSearch, ErrorReporter> {
+        Err(self.report("deleting the saved search"))
+    }
+
+    fn tag_infos(&self) -> Result<Vec<TagInfo>, ErrorReporter> {
+        self.storage.tag_infos()
+    }
+
+    fn save_tag_info(&mut self, _info: TagInfo) -> Result<TagInfo, ErrorReporter> {
+        Err(self.report("saving the info of the tag"))
+    }
+
+    fn delete_tag_info(&mut self, _tag: &str) -> Result<TagInfo, ErrorReporter> {
+        Err(self.report("deleting the info of the tag"))
+    }
+
+    fn replace_file(&mut self, _file: LinksFile) -> Result<(), ErrorReporter> {
+        Err(self.report("replacing the links"))
+    }
+
+    fn query(&self, filter: &LinkFilter) -> Result<Vec<Link>, ErrorReporter> {
+        self.storage.query(filter)
+    }
+
+    fn backups(&self) -> Result<Vec<Backup>, ErrorReporter> {
+        self.storage.backups()
+    }
+
+    // restoring a backup is one of the user's choices
+    fn restore_backup(&mut self, name: &str) -> Result<(), ErrorReporter> {
+        self.storage.restore_backup(name)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::*;
+use dsaver_core::journal::Change;
+use dsaver_core::storage::Storage;
+use dsaver_core::tag::{self, TagInfo};
+use tauri::State;
+
+/// Rename the tag `from` to `to` in every link which has it. Its child tags (like `dev/rust` of `dev`) are moved too.
+///
+/// It returns all the links after renaming. The rename can be undone with [`undo`] as a single change.
+///
+/// It will return an error with [`ErrorType::InvalidTag`] if `to` has spaces or some links already have it (use [`merge_tags`] instead), and [`ErrorType::TagNotFound`] if no link has the tag `from`.
+///
+/// # Example
+///
+/// ```js
+/// async function renameTag() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const links = await invoke("rename_tag", { from: "Code", to: "Programming" });
+///         console.log("Links after renaming: ", links);
+///     } catch (err) {
+///         console.error("Some error occured while renaming the tag: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn rename_tag(
+    from: String,
+    to: String,
+    store: State<'_, StorageState>,
+) -> Result<Vec<Link>, ErrorReporter> {
+    change_tags(&store, |storage| tag::rename_tag(storage, &from, &to))
+}
+
+/// Replace the tag `from` with the tag `into` in every link which has it. A link which has both tags keeps only `into`. The child tags of `from` are moved into `into`.
+///
+/// It returns all the links after merging. The merge can be undone with [`undo`] as a single change.
+///
+/// It will return an error with [`ErrorType::TagNotFound`] if no link has one of the tags, and [`ErrorType::InvalidTag`] if `into` is `from` or one of its child tags.
+#[tauri::command]
+pub async fn merge_tags(
+    from: String,
+    into: String,
+    store: State<'_, StorageState>,
+) -> Result<Vec<Link>, ErrorReporter> {
+    change_tags(&store, |storage| tag::merge_tags(storage, &from, &into))
+}
+
+/// Remove the tag `tag` and its child tags from every link which has them. The links are not deleted.
+///
+/// It returns all the links after removing the tag. It can be undone with [`undo`] as a single change.
+///
+/// It will return an error with [`ErrorType::TagNotFound`] if no link has the tag.
+#[tauri::command]
+pub async fn delete_tag(
+    tag: String,
+    store: State<'_, StorageState>,
+) -> Result<Vec<Link>, ErrorReporter> {
+    change_tags(&store, |storage| tag::delete_tag(storage, &tag))
+}
+
+/// Get the colors, icons and descriptions of the tags, sorted by the tag.
+///
+/// A tag without any of them has no info.
+///
+/// # Example
+///
+/// ```js
+/// async function getTagInfos() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const infos = await invoke("get_tag_infos");
+///         infos.forEach(info => console.log(info.tag, info.color, info.icon));
+///     } catch (err) {
+///         console.error("Some error occured while reading the tags: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn get_tag_infos(store: State<'_, StorageState>) -> Result<Vec<TagInfo>, ErrorReporter> {
+    store.with(|store| store.tag_infos())
+}
+
+/// Save the color, icon and description of a tag, replacing its old info.
+///
+/// It returns the saved info. If the info has no color, icon or description, then the old info of the tag is removed instead.
+///
+/// It will return an error with [`ErrorType::InvalidTag`] if the tag or the color is not valid (see [`TagInfo::validate`]).
+///
+/// # Arguments
+///
+/// This function takes an serialized string of [`TagInfo`].
+///
+/// # Example
+///
+/// ```js
+/// async function saveTagInfo() {
+///     const invoke = window.__TAURI__.invoke;
+///     const info = { tag: "dev/rust", color: "#dea584", icon: "🦀", description: null };
+///
+///     try {
+///         await invoke("save_tag_info", { info: JSON.stringify(info) });
+///     } catch (err) {
+///         console.error("Some error occured while saving the tag: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn save_tag_info(
+    info: String,
+    store: State<'_, StorageState>,
+) -> Result<TagInfo, ErrorReporter> {
+    let info: TagInfo = serde_json::from_str(&info).unwrap();
+    info.validate()?;
+
+    store.with(|store| {
+        if !info.is_empty() {
+            return store.save_tag_info(info);
+        }
+
+        // nothing to store
+        if store.tag_infos()?.iter().any(|saved| saved.tag == info.tag) {
+            store.delete_tag_info(&info.tag)?;
+        }
+
+        Ok(info)
+    })
+}
+
+/// Make the change `f` in the storage and record it in the journal.
+///
+/// It returns all the links after the change.
+fn change_tags<F>(store: &StorageState, f: F) -> Result<Vec<Link>, ErrorReporter>
+where
+    F: FnOnce(&mut dyn Storage) -> Result<Change, ErrorReporter>,
+{
+    let (change, links) = store.with(|storage| {
+        let change = f(storage)?;
+
+        Ok((change, storage.links()?))
+    })?;
+
+    // the tags are indexed too
+    store.reset_index();
+    store.record(change);
+
+    Ok(links)
+}
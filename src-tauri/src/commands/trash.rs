@@ -0,0 +1,121 @@
+use crate::*;
+use dsaver_core::journal::Change;
+use dsaver_core::settings::{settings_file, Settings};
+use dsaver_core::storage::{purge_expired, TrashedLink};
+use tauri::State;
+use uuid::Uuid;
+
+/// Get all the links inside the trash. The last deleted link will be first.
+///
+/// The links which were in the trash for more than [`Settings::trash_retention_days`] are deleted forever first, with their cached images.
+///
+/// # Example
+///
+/// ```js
+/// async function getTrash() {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const trash = await invoke("get_trash");
+///         trash.forEach(trashed => console.log(trashed.link.url, trashed.deleted_at));
+///     } catch (err) {
+///         console.error("Some error occured while reading the trash: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn get_trash(
+    store: State<'_, StorageState>,
+    images: State<'_, ImageCacheState>,
+) -> Result<Vec<TrashedLink>, ErrorReporter> {
+    let retention_days = Settings::load(settings_file(home_dir()?))?.trash_retention_days;
+
+    let (purged, trash) = store.with(|store| {
+        let purged = purge_expired(store, retention_days)?;
+
+        Ok((purged, store.trashed()?))
+    })?;
+
+    if !purged.is_empty() {
+        store.forget(&ids(&purged));
+        let _ = collect_image_garbage(&store, &images);
+    }
+
+    Ok(trash)
+}
+
+/// Move the link with the given `id` out of the trash.
+///
+/// It returns the restored link. It is added after all the other links. The restore can be undone with [`undo`].
+///
+/// # Example
+///
+/// ```js
+/// async function restoreLink(id) {
+///     const invoke = window.__TAURI__.invoke;
+///
+///     try {
+///         const link = await invoke("restore_link", { id });
+///         console.log("Restored link: ", link);
+///     } catch (err) {
+///         console.error("Some error occured while restoring the link: ", err);
+///     }
+/// }
+/// ```
+#[tauri::command]
+pub async fn restore_link(id: Uuid, store: State<'_, StorageState>) -> Result<Link, ErrorReporter> {
+    let link = store.with(|store| store.restore(id))?;
+
+    store.with_index(|index| index.insert(&link));
+    store.record(Change::Restored(link.clone()));
+
+    Ok(link)
+}
+
+/// Delete forever the link with the given `id` from the trash.
+///
+/// It returns the deleted link. It can't be undone, and the earlier changes of the link can't be undone anymore either. The cached images of the link are removed too, see [`collect_image_garbage`].
+#[tauri::command]
+pub async fn purge_link(
+    id: Uuid,
+    store: State<'_, StorageState>,
+    images: State<'_, ImageCacheState>,
+) -> Result<TrashedLink, ErrorReporter> {
+    let trashed = store.with(|store| store.purge(id))?;
+    store.forget(&[id]);
+
+    // the link is already deleted, a leftover image is removed next time
+    let _ = collect_image_garbage(&store, &images);
+
+    Ok(trashed)
+}
+
+/// Delete forever all the links inside the trash.
+///
+/// It returns the deleted links. It can't be undone, and the earlier changes of the links can't be undone anymore either. The cached images of the links are removed too, see [`collect_image_garbage`].
+#[tauri::command]
+pub async fn empty_trash(
+    store: State<'_, StorageState>,
+    images: State<'_, ImageCacheState>,
+) -> Result<Vec<TrashedLink>, ErrorReporter> {
+    let trash = store.with(|store| {
+        let trash = store.trashed()?;
+        store.replace_trash(Vec::new())?;
+
+        Ok(trash)
+    })?;
+    store.forget(&ids(&trash));
+
+    // the links are already deleted, a leftover image is removed next time
+    let _ = collect_image_garbage(&store, &images);
+
+    Ok(trash)
+}
+
+/// The ids of the `trashed` links.
+fn ids(trashed: &[TrashedLink]) -> Vec<Uuid> {
+    trashed
+        .iter()
+        .filter_map(|trashed| trashed.link.id)
+        .collect()
+}
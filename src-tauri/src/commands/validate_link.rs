@@ -1,4 +1,5 @@
 use crate::*;
+use dsaver_core::metadata::{find_favicon, resolve_url, PageMetadata};
 use uuid::Uuid;
 use webpage::{Webpage, WebpageOptions};
 
@@ -8,7 +9,9 @@ use webpage::{Webpage, WebpageOptions};
 ///
 /// It will fetch the title, description and thumbnail for the link. And if they are available then it will return them on a new instance of [`Link`].
 ///
-/// *FUTURE* If the website returns 404 error, then it will return an error.
+/// The other information about the webpage (the Open Graph or Twitter card image, site name, canonical url, favicon, language and content type) is put in [`Link::metadata`], see [`page_metadata`].
+///
+/// If the website returns a 4xx or 5xx status (after following the redirects), then it will return a warning, see [`check_status`].
 ///
 /// # Arguments
 ///
@@ -16,7 +19,9 @@ use webpage::{Webpage, WebpageOptions};
 ///
 /// # Warning
 ///
-/// If the website is not available or the URL is invalid then it will return an error inside [`ErrorReporter`].
+/// If the website is not available or the URL is invalid then it will return an error inside [`ErrorReporter`] with [`ErrorType::InvalidOrNotFound`].
+///
+/// If the website is available but the page is not, then the error has [`ErrorType::PageNotFound`] or [`ErrorType::HttpError`]. Show it as a warning and let the user save the link anyway (with [`insert_link`]).
 ///
 /// You can show those error massages to the users without modifying the messages or adding additional message.
 ///
@@ -38,8 +43,7 @@ use webpage::{Webpage, WebpageOptions};
 ///        tags: ["Videos", "Music"],
 ///        priority: 'C',
 ///        browser: "Firefox",
-///        complete: false,
-///        date: "14 January 2022"
+///        complete: false
 ///    };
 ///
 ///    try {
@@ -61,13 +65,15 @@ use webpage::{Webpage, WebpageOptions};
 /// ```
 #[tauri::command]
 pub async fn validate_link(link: String) -> Result<Link, ErrorReporter> {
-    // TODO: If the webstie returns 404 error, then warn the user. Create a new variant for LinkSavingError then put the new data in that variant, and if the user confirms that he wants to keep the link, then add that
     let link: Link = serde_json::from_str(&link).unwrap();
 
     // First fetch the website and also check if the `url` is valid or not
     match Webpage::from_url(&link.url, WebpageOptions::default()) {
         // Found the website.
         Ok(req_info) => {
+            // The website is working, but the page may not
+            check_status(&req_info.http.url, req_info.http.response_code)?;
+
             // If the `id` in None, then create a new one
             let id = match link.id {
                 Some(id) => Some(id),
@@ -96,16 +102,27 @@ pub async fn validate_link(link: String) -> Result<Link, ErrorReporter> {
                 },
             };
 
+            let metadata = page_metadata(&req_info);
+
             Ok(Link {
                 id,
                 url: req_info.http.url,
                 title,
+                custom_title: link.custom_title,
                 description,
                 tags: link.tags,
                 browser: link.browser,
                 complete: link.complete,
                 priority: link.priority,
-                date: link.date,
+                created_at: link.created_at,
+                updated_at: link.updated_at,
+                last_opened_at: link.last_opened_at,
+                open_count: link.open_count,
+                open_history: link.open_history,
+                manual_order: link.manual_order,
+                metadata,
+                // the page is just fetched, so an old check doesn't tell anything
+                check: None,
             })
         }
         // website not working or URL not valid
@@ -127,3 +144,94 @@ pub async fn validate_link(link: String) -> Result<Link, ErrorReporter> {
         }
     }
 }
+
+/// Check the final HTTP `status` of the webpage at `url`.
+///
+/// It returns an error with [`ErrorType::PageNotFound`] for `404` and `410`, and [`ErrorType::HttpError`] for the other 4xx and 5xx statuses. Every other status is fine.
+pub fn check_status(url: &str, status: u32) -> Result<(), ErrorReporter> {
+    let actual_error = format!("The website returned the status {status} for {url}");
+    let how_to_fix = vec![
+        "Open the url in your browser to check it",
+        "Add the link anyway if you want to check it later",
+    ];
+
+    let (error_title, why_error, error_type) = match status {
+        404 | 410 => (
+            "Page Not Found",
+            vec!["The page is deleted or moved", "The url has a typo"],
+            ErrorType::PageNotFound,
+        ),
+        400..=499 => (
+            "Page Not Available",
+            vec![
+                "The page needs a login",
+                "The website doesn't allow apps to read the page",
+            ],
+            ErrorType::HttpError { status },
+        ),
+        500..=599 => (
+            "Website Error",
+            vec!["The website is down right now", "The website has a bug"],
+            ErrorType::HttpError { status },
+        ),
+        _ => return Ok(()),
+    };
+
+    Err(ErrorReporterBuilder {
+        actual_error: &actual_error,
+        why_error,
+        how_to_fix,
+        error_title,
+        when_error: "checking the link",
+        error_type,
+    }
+    .build())
+}
+
+/// Get the [`PageMetadata`] of a fetched webpage.
+///
+/// The Open Graph tags are used first, then the Twitter card tags. If the webpage has no icon in its html, then `/favicon.ico` of the website is used. The urls are made absolute.
+pub fn page_metadata(page: &Webpage) -> PageMetadata {
+    let (html, http) = (&page.html, &page.http);
+    let meta = |name: &str| {
+        html.meta
+            .get(name)
+            .filter(|content| !content.trim().is_empty())
+            .cloned()
+    };
+
+    let image = html
+        .opengraph
+        .images
+        .first()
+        .map(|image| image.url.clone())
+        .or_else(|| meta("twitter:image"))
+        .or_else(|| meta("twitter:image:src"))
+        .map(|image| resolve_url(&http.url, image.trim()));
+
+    let site_name = html
+        .opengraph
+        .properties
+        .get("site_name")
+        .cloned()
+        .or_else(|| meta("application-name"));
+
+    let favicon = find_favicon(&http.body).unwrap_or_else(|| String::from("/favicon.ico"));
+
+    // `text/html; charset=utf-8` is `text/html`
+    let content_type = http
+        .content_type
+        .split(';')
+        .next()
+        .map(|content_type| content_type.trim().to_lowercase())
+        .filter(|content_type| !content_type.is_empty());
+
+    PageMetadata {
+        image,
+        site_name,
+        canonical_url: html.url.clone(),
+        favicon: Some(resolve_url(&http.url, &favicon)),
+        language: html.language.clone(),
+        content_type,
+    }
+}
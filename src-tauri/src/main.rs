@@ -9,11 +9,49 @@ use dsaver::*;
 #[cfg(debug_assertions)]
 fn main() {
     tauri::Builder::default()
+        .manage(StorageState::default())
+        .manage(ImageCacheState::default())
+        .manage(LinkCheckState::default())
+        .register_uri_scheme_protocol(IMAGE_PROTOCOL, image_protocol)
         .invoke_handler(tauri::generate_handler![
             validate_link,
             open_browser_windows,
             open_browser_linux,
             open_browser_macos,
+            get_links,
+            add_link,
+            insert_link,
+            update_link,
+            delete_link,
+            reorder_links,
+            undo,
+            redo,
+            get_activity,
+            get_trash,
+            restore_link,
+            purge_link,
+            empty_trash,
+            rename_tag,
+            merge_tags,
+            delete_tag,
+            get_tag_infos,
+            save_tag_info,
+            list_backups,
+            restore_backup,
+            keep_recovered_links,
+            open_quarantined_file,
+            query_links,
+            query_link_ids,
+            search_links,
+            get_saved_searches,
+            save_search,
+            delete_search,
+            migrate_storage,
+            get_settings,
+            update_settings,
+            check_links,
+            refresh_link,
+            apply_refresh,
             generate,
         ])
         .run(tauri::generate_context!())
@@ -24,11 +62,49 @@ fn main() {
 #[cfg(not(debug_assertions))]
 fn main() {
     tauri::Builder::default()
+        .manage(StorageState::default())
+        .manage(ImageCacheState::default())
+        .manage(LinkCheckState::default())
+        .register_uri_scheme_protocol(IMAGE_PROTOCOL, image_protocol)
         .invoke_handler(tauri::generate_handler![
             validate_link,
             open_browser_windows,
             open_browser_linux,
             open_browser_macos,
+            get_links,
+            add_link,
+            insert_link,
+            update_link,
+            delete_link,
+            reorder_links,
+            undo,
+            redo,
+            get_activity,
+            get_trash,
+            restore_link,
+            purge_link,
+            empty_trash,
+            rename_tag,
+            merge_tags,
+            delete_tag,
+            get_tag_infos,
+            save_tag_info,
+            list_backups,
+            restore_backup,
+            keep_recovered_links,
+            open_quarantined_file,
+            query_links,
+            query_link_ids,
+            search_links,
+            get_saved_searches,
+            save_search,
+            delete_search,
+            migrate_storage,
+            get_settings,
+            update_settings,
+            check_links,
+            refresh_link,
+            apply_refresh,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,43 @@
+use dsaver::check_url;
+use std::net::TcpListener;
+
+mod common;
+use common::stub_server;
+
+#[test]
+fn checks_the_status() {
+    let server = stub_server();
+
+    let check = check_url(&format!("{server}/200"));
+    assert_eq!(check.status, Some(200));
+    assert_eq!(check.redirect, None);
+    assert!(!check.is_broken());
+
+    let check = check_url(&format!("{server}/404"));
+    assert_eq!(check.status, Some(404));
+    assert!(check.is_broken());
+}
+
+#[test]
+fn follows_redirects() {
+    let server = stub_server();
+
+    let check = check_url(&format!("{server}/redirect"));
+    assert_eq!(check.url, format!("{server}/redirect"));
+    assert_eq!(check.status, Some(200));
+    assert_eq!(check.redirect, Some(format!("{server}/200")));
+}
+
+#[test]
+fn reports_unreachable_websites() {
+    // nothing listens on the port after the listener is dropped
+    let address = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let check = check_url(&format!("http://{address}/200"));
+    assert_eq!(check.status, None);
+    assert!(check.error.is_some());
+    assert!(check.is_broken());
+}
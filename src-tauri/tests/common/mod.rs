@@ -0,0 +1,44 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+
+/// Start a HTTP server which answers `/<status>` with that status, and `/redirect` with a redirect to `/200`.
+///
+/// It returns the address of the server, like `http://127.0.0.1:4000`.
+pub fn stub_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            // skip the headers
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).unwrap_or(0) <= 2 {
+                    break;
+                }
+            }
+
+            let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+            let (status, location) = match path {
+                "/redirect" => (301, "Location: /200\r\n"),
+                _ => (path.trim_start_matches('/').parse().unwrap_or(200), ""),
+            };
+            let body = format!("<html><head><title>Status {status}</title></head></html>");
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} Stub\r\n{location}Content-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+    });
+
+    address
+}
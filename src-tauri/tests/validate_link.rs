@@ -0,0 +1,67 @@
+use dsaver::{check_status, validate_link};
+use dsaver_core::prelude::*;
+use std::net::TcpListener;
+
+mod common;
+use common::stub_server;
+
+fn validate(url: String) -> Result<Link, ErrorReporter> {
+    let link = serde_json::to_string(&Link::new(url)).unwrap();
+
+    tauri::async_runtime::block_on(validate_link(link))
+}
+
+#[test]
+fn accepts_working_pages() {
+    let server = stub_server();
+
+    let link = validate(format!("{server}/200")).unwrap();
+    assert_eq!(link.title.as_deref(), Some("Status 200"));
+
+    // the final status is checked
+    let link = validate(format!("{server}/redirect")).unwrap();
+    assert_eq!(link.url, format!("{server}/200"));
+}
+
+#[test]
+fn warns_about_missing_pages() {
+    let server = stub_server();
+    let error_type = |status: u32| {
+        validate(format!("{server}/{status}"))
+            .unwrap_err()
+            .error_type()
+            .clone()
+    };
+
+    assert_eq!(error_type(404), ErrorType::PageNotFound);
+    assert_eq!(error_type(410), ErrorType::PageNotFound);
+    assert_eq!(error_type(403), ErrorType::HttpError { status: 403 });
+    assert_eq!(error_type(503), ErrorType::HttpError { status: 503 });
+}
+
+#[test]
+fn fails_on_unreachable_websites() {
+    // nothing listens on the port after the listener is dropped
+    let address = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    assert_eq!(
+        validate(format!("http://{address}/200"))
+            .unwrap_err()
+            .error_type(),
+        &ErrorType::InvalidOrNotFound
+    );
+}
+
+#[test]
+fn checks_the_status() {
+    for status in [200, 204, 301, 304] {
+        assert!(check_status("https://example.com", status).is_ok());
+    }
+
+    let error = check_status("https://example.com", 500).unwrap_err();
+    assert_eq!(error.error_type(), &ErrorType::HttpError { status: 500 });
+    assert_eq!(error.error_title(), "Website Error");
+}